@@ -2,7 +2,7 @@ use crate::{ActionCharacter, Project, Reaper, ReaperError, ReaperResult, Section
 use c_str_macro::c_str;
 use reaper_medium::{
     AcceleratorBehavior, AcceleratorKeyCode, ActionValueChange, CommandId, Hwnd, ProjectContext,
-    ReaperStr, ReaperString, SectionContext, WindowContext,
+    ReaperStr, ReaperString, RequiredViewMode, SectionContext, SectionId, WindowContext,
 };
 
 use helgoboss_midi::{U14, U7};
@@ -10,6 +10,8 @@ use reaper_medium::ProjectContext::{CurrentProject, Proj};
 use reaper_medium::SectionContext::Sec;
 use std::borrow::Cow;
 use std::cell::{Ref, RefCell};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 
 use enumflags2::BitFlags;
 use reaper_low::{raw, Swell};
@@ -29,6 +31,49 @@ pub enum KeyBindingKind {
     GlobalText,
 }
 
+/// An error which can occur when invoking an action.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ActionInvocationError {
+    /// The action or its section doesn't exist.
+    ActionNotAvailable,
+    /// The action belongs to a MIDI editor section but no matching MIDI editor is open.
+    MidiEditorNotOpen,
+    /// The action belongs to the media explorer section but no media explorer window was given.
+    MediaExplorerNotOpen,
+    /// The action belongs to a custom section but no action hook has handled it.
+    NotHandled,
+    /// The action belongs to a section whose actions can't be invoked programmatically (the MIDI
+    /// inline editor).
+    UnsupportedSection,
+}
+
+impl ActionInvocationError {
+    pub fn message(&self) -> &'static str {
+        use ActionInvocationError::*;
+        match self {
+            ActionNotAvailable => "action not available",
+            MidiEditorNotOpen => "no MIDI editor open",
+            MediaExplorerNotOpen => "no media explorer window available",
+            NotHandled => "action not handled by any hook",
+            UnsupportedSection => "actions of this section can't be invoked programmatically",
+        }
+    }
+}
+
+impl Error for ActionInvocationError {}
+
+impl Display for ActionInvocationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl From<ActionInvocationError> for ReaperError {
+    fn from(e: ActionInvocationError) -> Self {
+        Self::new(e.message())
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 struct RuntimeData {
     section: Section,
@@ -105,18 +150,11 @@ impl Action {
     }
 
     fn find_index(&self, runtime_data: &RuntimeData) -> Option<u32> {
-        // TODO-low Use kbd_enumerateActions
-        let section_id = runtime_data.section.id();
-        Reaper::get()
-            .medium_reaper()
-            .section_from_unique_id(section_id, |s| {
-                (0..s.action_list_cnt())
-                    .map(move |i| s.get_action_by_index(i).unwrap())
-                    .enumerate()
-                    .find(|(_, kbd_cmd)| kbd_cmd.cmd() == runtime_data.command_id)
-                    .map(|(i, _)| i as u32)
-            })
-            .unwrap()
+        let section = runtime_data.section;
+        (0..)
+            .map_while(|i| section.enumerate_action(i, |command_id, _| (i, command_id)))
+            .find(|(_, command_id)| *command_id == runtime_data.command_id)
+            .map(|(i, _)| i)
     }
 
     pub fn is_available(&self) -> bool {
@@ -209,11 +247,79 @@ impl Action {
         Ok(name)
     }
 
+    /// Returns the number of shortcuts assigned to this action.
+    pub fn shortcut_count(&self) -> ReaperResult<u32> {
+        let rd = self.load_if_necessary_or_complain()?;
+        let count = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .count_action_shortcuts(Sec(&rd.section.raw()), rd.command_id)
+        };
+        Ok(count)
+    }
+
+    /// Returns the descriptions of all shortcuts assigned to this action, e.g. `Ctrl+Shift+A`.
+    pub fn shortcut_descriptions(&self) -> ReaperResult<Vec<String>> {
+        let rd = self.load_if_necessary_or_complain()?;
+        let reaper = Reaper::get().medium_reaper();
+        let section = unsafe { rd.section.raw() };
+        let count = unsafe { reaper.count_action_shortcuts(Sec(&section), rd.command_id) };
+        (0..count)
+            .map(|i| {
+                let desc = unsafe {
+                    reaper.get_action_shortcut_desc(Sec(&section), rd.command_id, i, 256)?
+                };
+                Ok(desc.into_string())
+            })
+            .collect()
+    }
+
+    /// Removes the shortcut at the given index from this action.
+    pub fn delete_shortcut(&self, index: u32) -> ReaperResult<()> {
+        let rd = self.load_if_necessary_or_complain()?;
+        unsafe {
+            Reaper::get().medium_reaper().delete_action_shortcut(
+                Sec(&rd.section.raw()),
+                rd.command_id,
+                index,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Opens REAPER's dialog for assigning a shortcut to this action.
+    ///
+    /// Passing an existing shortcut index replaces that shortcut, `None` adds a new one. Returns
+    /// whether a shortcut has been assigned.
+    pub fn open_shortcut_dialog(&self, index: Option<u32>) -> ReaperResult<bool> {
+        let rd = self.load_if_necessary_or_complain()?;
+        let reaper = Reaper::get();
+        let assigned = unsafe {
+            reaper.medium_reaper().do_action_shortcut_dialog(
+                reaper.main_window(),
+                Sec(&rd.section.raw()),
+                rd.command_id,
+                index,
+            )
+        };
+        Ok(assigned)
+    }
+
+    /// Makes toolbar buttons and other UI elements reflect the current toggle state of this
+    /// action.
+    pub fn refresh_toolbar(&self) -> ReaperResult<()> {
+        let rd = self.load_if_necessary_or_complain()?;
+        Reaper::get()
+            .medium_reaper()
+            .refresh_toolbar_2(rd.section.id(), rd.command_id);
+        Ok(())
+    }
+
     pub fn invoke_as_trigger(
         &self,
         project: Option<Project>,
         window: Option<Hwnd>,
-    ) -> ReaperResult<()> {
+    ) -> Result<(), ActionInvocationError> {
         self.invoke_absolute(1.0, project, false, window)
     }
 
@@ -222,12 +328,10 @@ impl Action {
         amount: i32,
         project: Option<Project>,
         window: Option<Hwnd>,
-    ) -> ReaperResult<()> {
+    ) -> Result<(), ActionInvocationError> {
         let relative_value = 64 + amount;
         let cropped_relative_value =
             unsafe { U7::new_unchecked(relative_value.clamp(0, 127) as u8) };
-        // reaper::kbd_RunCommandThroughHooks(section_.sectionInfo(), &actionCommandId, &val,
-        // &valhw, &relmode, reaper::GetMainHwnd());
         self.invoke_directly(
             ActionValueChange::Relative2(cropped_relative_value),
             window,
@@ -244,14 +348,7 @@ impl Action {
         project: Option<Project>,
         enforce_7_bit_control: bool,
         window: Option<Hwnd>,
-    ) -> ReaperResult<()> {
-        // TODO-low I have no idea how to launch an action in a specific section. The first function
-        // doesn't seem to launch the action :(
-        // bool (*kbd_RunCommandThroughHooks)(KbdSectionInfo* section, int* actionCommandID, int*
-        // val, int* valhw, int* relmode, HWND hwnd); int (*KBD_OnMainActionEx)(int cmd, int
-        // val, int valhw, int relmode, HWND hwnd, ReaProject* proj);
-        // reaper::kbd_RunCommandThroughHooks(section_.sectionInfo(), &actionCommandId, &val,
-        // &valhw, &relmode, reaper::GetMainHwnd());
+    ) -> Result<(), ActionInvocationError> {
         let value_change = if enforce_7_bit_control {
             let discrete_value = unsafe {
                 U7::new_unchecked((normalized_value * U7::MAX.get() as f64).round() as u8)
@@ -271,32 +368,51 @@ impl Action {
                 Some(p) => Proj(p.raw()),
             },
         )
-        // Main_OnCommandEx would trigger the actionInvoked event but it has not enough
-        // parameters for passing values etc.          reaper::
-        // Main_OnCommandEx(actionCommandId, 0, project ? project->reaProject() : nullptr);
     }
 
+    /// Invokes this action in its section.
+    ///
+    /// - MIDI editor and event list actions go to the given window or, if no window is given, to
+    ///   the last focused MIDI editor in the matching view mode. The value change is ignored.
+    /// - Media explorer actions go to the given window. The value change is ignored.
+    /// - Actions of main sections are performed via `KBD_OnMainActionEx`, so they receive the
+    ///   value change.
+    /// - Actions of the MIDI inline editor can't be invoked because REAPER doesn't offer a way to
+    ///   send commands to an inline editor. This fails with
+    ///   [`ActionInvocationError::UnsupportedSection`].
+    /// - Actions of any other section (e.g. sections registered by extensions) are run through
+    ///   the action hooks.
     pub fn invoke_directly(
         &self,
         value_change: ActionValueChange,
         window: Option<Hwnd>,
         project: ProjectContext,
-    ) -> ReaperResult<()> {
-        let rd = self.load_if_necessary_or_complain()?;
+    ) -> Result<(), ActionInvocationError> {
+        let rd = self
+            .load_if_necessary_or_complain()
+            .map_err(|_| ActionInvocationError::ActionNotAvailable)?;
         let action_command_id = rd.command_id;
         let window_context = window
             .map(WindowContext::Win)
             .unwrap_or(WindowContext::NoWindow);
         let reaper = &Reaper::get().medium_reaper;
-        match rd.section.id().get() {
-            32060 | 32061 => {
-                // MIDI editor
-                let hwnd = window.ok_or("no MIDI editor window available")?;
-                reaper.midi_editor_on_command(hwnd, action_command_id)?
+        let section_id = rd.section.id();
+        match section_id {
+            SectionId::MIDI_EDITOR | SectionId::MIDI_EVENT_LIST_EDITOR => {
+                let result = if let Some(hwnd) = window {
+                    reaper.midi_editor_on_command(hwnd, action_command_id)
+                } else {
+                    let view_mode = if section_id == SectionId::MIDI_EDITOR {
+                        RequiredViewMode::Normal
+                    } else {
+                        RequiredViewMode::ListView
+                    };
+                    reaper.midi_editor_last_focused_on_command(action_command_id, view_mode)
+                };
+                result.map_err(|_| ActionInvocationError::MidiEditorNotOpen)?;
             }
-            32063 => {
-                // Media explorer
-                let hwnd = window.ok_or("no media explorer window available")?;
+            SectionId::MEDIA_EXPLORER => {
+                let hwnd = window.ok_or(ActionInvocationError::MediaExplorerNotOpen)?;
                 unsafe {
                     Swell::get().PostMessage(
                         hwnd.as_ptr(),
@@ -306,7 +422,7 @@ impl Action {
                     );
                 }
             }
-            _ => unsafe {
+            SectionId::MAIN | SectionId::MAIN_ALT_RECORDING => unsafe {
                 reaper.kbd_on_main_action_ex(
                     action_command_id,
                     value_change,
@@ -314,6 +430,26 @@ impl Action {
                     project,
                 );
             },
+            SectionId::MIDI_INLINE_EDITOR => {
+                // Action hooks only reach extensions, not the inline editor itself
+                return Err(ActionInvocationError::UnsupportedSection);
+            }
+            _ => {
+                let handled = rd
+                    .section
+                    .with_raw(|s| unsafe {
+                        reaper.kbd_run_command_through_hooks(
+                            Sec(s),
+                            action_command_id,
+                            value_change,
+                            window_context,
+                        )
+                    })
+                    .ok_or(ActionInvocationError::ActionNotAvailable)?;
+                if !handled {
+                    return Err(ActionInvocationError::NotHandled);
+                }
+            }
         }
         Ok(())
     }
@@ -0,0 +1,324 @@
+use helgoboss_midi::{U14, U7};
+use reaper_medium::{ActionValueChange, CommandId, SectionId, WindowContext};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// An action invocation as seen by [`ActionInterceptor`]s.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ActionInvocation {
+    pub section_id: SectionId,
+    pub command_id: CommandId,
+    pub value_change: ActionValueChange,
+    pub window: WindowContext,
+}
+
+impl ActionInvocation {
+    /// Returns the value between 0.0 and 1.0 if this is an absolute invocation (e.g. triggered
+    /// by a MIDI CC knob in absolute mode or by a keyboard shortcut).
+    pub fn absolute_value(&self) -> Option<f64> {
+        use ActionValueChange::*;
+        let value = match self.value_change {
+            AbsoluteLowRes(v) => v.get() as f64 / U7::MAX.get() as f64,
+            AbsoluteHighRes(v) => v.get() as f64 / U14::MAX.get() as f64,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Returns the signed increment if this is a relative invocation (e.g. triggered by an
+    /// endless encoder or the mousewheel).
+    pub fn relative_increment(&self) -> Option<i32> {
+        use ActionValueChange::*;
+        let increment = match self.value_change {
+            Relative1(v) => {
+                let v = v.get() as i32;
+                if v < 64 {
+                    v
+                } else {
+                    v - 128
+                }
+            }
+            Relative2(v) => v.get() as i32 - 64,
+            Relative3(v) => {
+                let v = v.get() as i32;
+                if v < 64 {
+                    v
+                } else {
+                    64 - v
+                }
+            }
+            _ => return None,
+        };
+        Some(increment)
+    }
+}
+
+/// What should happen with an intercepted action invocation.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Interception {
+    /// Lets the invocation continue (interceptors with lower priority get to see it as well).
+    Proceed,
+    /// Swallows the invocation.
+    Veto,
+    /// Swallows the invocation and invokes the given command of the same section instead, with
+    /// the same value change.
+    ///
+    /// The replacement invocation is not intercepted again.
+    Replace(CommandId),
+}
+
+/// Observes, vetoes or replaces action invocations.
+///
+/// Closures of type `FnMut(&ActionInvocation) -> Interception` implement this trait.
+pub trait ActionInterceptor {
+    fn intercept(&mut self, invocation: &ActionInvocation) -> Interception;
+}
+
+impl<F: FnMut(&ActionInvocation) -> Interception> ActionInterceptor for F {
+    fn intercept(&mut self, invocation: &ActionInvocation) -> Interception {
+        self(invocation)
+    }
+}
+
+/// Identifies an interceptor added via [`Reaper::add_action_interceptor()`].
+///
+/// [`Reaper::add_action_interceptor()`]: crate::Reaper::add_action_interceptor
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ActionInterceptorId(u64);
+
+/// An ordered chain of action interceptors.
+///
+/// Interceptors may add or remove interceptors (including themselves) while the chain is running.
+/// Added interceptors take part from the next invocation on, removed ones are not consulted
+/// anymore.
+#[derive(Default)]
+pub struct ActionInterceptorRegistry {
+    next_id: Cell<u64>,
+    entries: RefCell<Vec<Rc<InterceptorEntry>>>,
+    entered: Cell<bool>,
+}
+
+struct InterceptorEntry {
+    id: ActionInterceptorId,
+    priority: i32,
+    removed: Cell<bool>,
+    interceptor: RefCell<Box<dyn ActionInterceptor>>,
+}
+
+impl ActionInterceptorRegistry {
+    /// Adds an interceptor.
+    ///
+    /// Interceptors with higher priority see invocations first. Interceptors with the same
+    /// priority are consulted in the order in which they were added.
+    pub fn add(
+        &self,
+        priority: i32,
+        interceptor: impl ActionInterceptor + 'static,
+    ) -> ActionInterceptorId {
+        let id = ActionInterceptorId(self.next_id.get());
+        self.next_id.set(id.0 + 1);
+        let entry = InterceptorEntry {
+            id,
+            priority,
+            removed: Cell::new(false),
+            interceptor: RefCell::new(Box::new(interceptor)),
+        };
+        let mut entries = self.entries.borrow_mut();
+        let index = entries.partition_point(|e| e.priority >= priority);
+        entries.insert(index, Rc::new(entry));
+        id
+    }
+
+    /// Removes the given interceptor and returns whether it was present.
+    pub fn remove(&self, id: ActionInterceptorId) -> bool {
+        let mut entries = self.entries.borrow_mut();
+        let Some(index) = entries.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        entries.remove(index).removed.set(true);
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    /// Executes the given function unless this is a nested call.
+    ///
+    /// Used to prevent that invocations triggered while handling an invocation (e.g.
+    /// replacements) are intercepted again. Returns `None` if this is a nested call.
+    pub fn enter<R>(&self, f: impl FnOnce(&Self) -> R) -> Option<R> {
+        if self.entered.replace(true) {
+            return None;
+        }
+        let _guard = EnteredGuard(&self.entered);
+        Some(f(self))
+    }
+
+    /// Feeds the given invocation through the chain.
+    ///
+    /// Stops at the first interceptor which vetoes or replaces the invocation.
+    pub fn intercept(&self, invocation: &ActionInvocation) -> Interception {
+        // Iterate over a snapshot so that interceptors can add or remove interceptors
+        let entries = self.entries.borrow().clone();
+        for entry in entries {
+            if entry.removed.get() {
+                continue;
+            }
+            // Already borrowed if the interceptor itself caused this invocation
+            let Ok(mut interceptor) = entry.interceptor.try_borrow_mut() else {
+                continue;
+            };
+            let interception = interceptor.intercept(invocation);
+            if interception != Interception::Proceed {
+                return interception;
+            }
+        }
+        Interception::Proceed
+    }
+}
+
+struct EnteredGuard<'a>(&'a Cell<bool>);
+
+impl Drop for EnteredGuard<'_> {
+    fn drop(&mut self) {
+        self.0.set(false);
+    }
+}
+
+impl Debug for ActionInterceptorRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActionInterceptorRegistry")
+            .field("interceptor_count", &self.entries.borrow().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invocation(command_id: u32, value_change: ActionValueChange) -> ActionInvocation {
+        ActionInvocation {
+            section_id: SectionId::new(0),
+            command_id: CommandId::new(command_id),
+            value_change,
+            window: WindowContext::NoWindow,
+        }
+    }
+
+    fn trigger(command_id: u32) -> ActionInvocation {
+        invocation(command_id, ActionValueChange::AbsoluteLowRes(U7::MAX))
+    }
+
+    #[test]
+    fn chain_order_and_decisions() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let registry = ActionInterceptorRegistry::default();
+        let observer = {
+            let log = log.clone();
+            registry.add(0, move |inv: &ActionInvocation| {
+                log.borrow_mut().push(("observer", inv.command_id.get()));
+                Interception::Proceed
+            })
+        };
+        let veto = {
+            let log = log.clone();
+            registry.add(10, move |inv: &ActionInvocation| {
+                log.borrow_mut().push(("veto", inv.command_id.get()));
+                if inv.command_id.get() == 1013 {
+                    Interception::Veto
+                } else {
+                    Interception::Proceed
+                }
+            })
+        };
+        {
+            let log = log.clone();
+            registry.add(10, move |inv: &ActionInvocation| {
+                log.borrow_mut().push(("replace", inv.command_id.get()));
+                if inv.command_id.get() == 40044 {
+                    Interception::Replace(CommandId::new(1007))
+                } else {
+                    Interception::Proceed
+                }
+            });
+        }
+        assert_eq!(registry.intercept(&trigger(1013)), Interception::Veto);
+        assert_eq!(
+            registry.intercept(&trigger(40044)),
+            Interception::Replace(CommandId::new(1007))
+        );
+        assert_eq!(registry.intercept(&trigger(1)), Interception::Proceed);
+        assert_eq!(
+            log.borrow().as_slice(),
+            &[
+                ("veto", 1013),
+                ("veto", 40044),
+                ("replace", 40044),
+                ("veto", 1),
+                ("replace", 1),
+                ("observer", 1),
+            ]
+        );
+        assert!(registry.remove(veto));
+        assert!(!registry.remove(veto));
+        assert_eq!(registry.intercept(&trigger(1013)), Interception::Proceed);
+        assert!(registry.remove(observer));
+        assert!(!registry.is_empty());
+    }
+
+    #[test]
+    fn add_and_remove_while_intercepting() {
+        let registry = Rc::new(ActionInterceptorRegistry::default());
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let own_id = Rc::new(Cell::new(None));
+        let id = {
+            let weak_registry = Rc::downgrade(&registry);
+            let log = log.clone();
+            let own_id = own_id.clone();
+            registry.add(0, move |_: &ActionInvocation| {
+                log.borrow_mut().push("one-shot");
+                let registry = weak_registry.upgrade().unwrap();
+                assert!(registry.remove(own_id.get().unwrap()));
+                let log = log.clone();
+                registry.add(0, move |_: &ActionInvocation| {
+                    log.borrow_mut().push("added");
+                    Interception::Proceed
+                });
+                Interception::Proceed
+            })
+        };
+        own_id.set(Some(id));
+        assert_eq!(registry.intercept(&trigger(1)), Interception::Proceed);
+        assert_eq!(registry.intercept(&trigger(1)), Interception::Proceed);
+        assert_eq!(log.borrow().as_slice(), &["one-shot", "added"]);
+        assert!(!registry.remove(id));
+    }
+
+    #[test]
+    fn nested_enter() {
+        let registry = ActionInterceptorRegistry::default();
+        let nested = registry.enter(|r| r.enter(|_| ()));
+        assert_eq!(nested, Some(None));
+        assert_eq!(registry.enter(|_| 5), Some(5));
+    }
+
+    #[test]
+    fn value_decoding() {
+        use ActionValueChange::*;
+        let abs = invocation(1, AbsoluteHighRes(U14::MAX));
+        assert_eq!(abs.absolute_value(), Some(1.0));
+        assert_eq!(abs.relative_increment(), None);
+        let inc = |value_change| invocation(1, value_change).relative_increment();
+        assert_eq!(inc(Relative1(U7::new(1))), Some(1));
+        assert_eq!(inc(Relative1(U7::new(127))), Some(-1));
+        assert_eq!(inc(Relative2(U7::new(65))), Some(1));
+        assert_eq!(inc(Relative2(U7::new(63))), Some(-1));
+        assert_eq!(inc(Relative3(U7::new(1))), Some(1));
+        assert_eq!(inc(Relative3(U7::new(65))), Some(-1));
+        assert_eq!(invocation(1, Relative3(U7::new(65))).absolute_value(), None);
+    }
+}
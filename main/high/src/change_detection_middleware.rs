@@ -1,17 +1,23 @@
+use crate::envelope::get_envelope_state;
 use crate::{
-    get_media_track_guid, ControlSurfaceEvent, Fx, FxParameter, Guid, Project, Reaper, Track,
-    TrackRoute,
+    diff_markers, first_affected_tempo_position, get_media_track_guid, read_markers,
+    read_tempo_markers, ControlSurfaceEvent, EnvelopeChange, EnvelopeRef, EnvelopeState, Fx,
+    FxChain, FxParameterRef, FxRef, Guid, Item, MarkerChange, MarkerInfo, Project, Reaper, Take,
+    TempoMarkerInfo, Track, TrackRef, TrackRouteRef,
 };
-use reaper_medium::ProjectContext::{CurrentProject, Proj};
+use reaper_medium::ProjectContext::Proj;
 use reaper_medium::{
-    reaper_str, AutomationMode, Bpm, ExtSetFxParamArgs, GlobalAutomationModeOverride,
-    InputMonitoringMode, MediaTrack, Pan, PanMode, PlayState, PlaybackSpeedFactor, ReaProject,
-    ReaperNormalizedFxParamValue, ReaperPanValue, ReaperStr, ReaperVersion, ReaperVolumeValue,
-    RecordingInput, TrackAttributeKey, TrackFxChainType, TrackLocation, TrackSendCategory,
-    TrackSendDirection, VersionDependentFxLocation, VersionDependentTrackFxLocation,
+    reaper_str, AutomationMode, Bpm, DurationInSeconds, ExtSetFxParamArgs,
+    GlobalAutomationModeOverride, InputMonitoringMode, ItemAttributeKey, MediaItem, MediaItemTake,
+    MediaTrack, Pan, PanMode, PcmSource, PlayState, PlaybackSpeedFactor, PositionInSeconds,
+    ReaProject, ReaperNormalizedFxParamValue, ReaperPanValue, ReaperStr, ReaperVersion,
+    ReaperVolumeValue, RecordingInput, TrackAttributeKey, TrackEnvelope, TrackFxChainType,
+    TrackFxLocation, TrackLocation, TrackSendCategory, TrackSendDirection,
+    VersionDependentFxLocation, VersionDependentTrackFxLocation,
 };
 use std::cell::{Cell, RefCell, RefMut};
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub struct ChangeDetectionMiddleware {
@@ -25,11 +31,71 @@ pub struct ChangeDetectionMiddleware {
 
 type ProjectDataMap = HashMap<ReaProject, ProjectData>;
 
+/// Keeps the state of one open project, no matter if it's the current one or a background tab.
 #[derive(Debug, Default)]
 struct ProjectData {
     track_datas: TrackDataMap,
+    /// Project state change count at the time of the last track set check.
+    ///
+    /// Used to check background projects for track set changes only if something has changed.
+    last_state_change_count: Option<u32>,
+    item_snapshots: ItemSnapshotMap,
+    /// Project state change count at the time of the last item check.
+    last_item_check_state_change_count: Option<u32>,
+    last_item_check: Option<Instant>,
+    markers: Vec<MarkerInfo>,
+    tempo_markers: Vec<TempoMarkerInfo>,
+    /// Project state change count at the time of the last marker and tempo map check.
+    last_marker_check_state_change_count: Option<u32>,
+    envelope_snapshots: EnvelopeSnapshotMap,
+    /// Project state change count at the time of the last envelope check.
+    last_envelope_check_state_change_count: Option<u32>,
+    last_envelope_check: Option<Instant>,
 }
 
+type EnvelopeSnapshotMap = HashMap<TrackEnvelope, EnvelopeSnapshot>;
+
+/// Envelope state for detecting envelope changes.
+///
+/// Read via the envelope API instead of the state chunk because reading chunks of all envelopes
+/// on each project change would be way too expensive.
+#[derive(Clone, PartialEq, Debug)]
+struct EnvelopeSnapshot {
+    track: MediaTrack,
+    /// Needed to identify the track after the envelope has been removed together with its track.
+    track_guid: Guid,
+    state: EnvelopeState,
+}
+
+type ItemSnapshotMap = HashMap<MediaItem, ItemSnapshot>;
+
+/// Item properties for detecting item changes.
+///
+/// There are no control surface callbacks for items, so we take snapshots whenever the project
+/// state has changed and diff them.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct ItemSnapshot {
+    /// Needed to identify the item after it has been removed.
+    guid: Guid,
+    track: MediaTrack,
+    position: PositionInSeconds,
+    length: DurationInSeconds,
+    selected: bool,
+    muted: bool,
+    active_take: Option<MediaItemTake>,
+    active_take_source: Option<PcmSource>,
+}
+
+/// Projects with more items than this are checked for item changes at most once per
+/// [`LARGE_PROJECT_ITEM_CHECK_INTERVAL`].
+const LARGE_PROJECT_ITEM_COUNT: usize = 2000;
+const LARGE_PROJECT_ITEM_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Projects with more envelope points than this are checked for envelope changes at most once per
+/// [`LARGE_PROJECT_ENVELOPE_CHECK_INTERVAL`].
+const LARGE_PROJECT_ENVELOPE_POINT_COUNT: usize = 20_000;
+const LARGE_PROJECT_ENVELOPE_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
 type TrackDataMap = HashMap<MediaTrack, TrackData>;
 
 /// Keeps current track values for detecting real value changes.
@@ -172,14 +238,28 @@ impl ChangeDetectionMiddleware {
     /// This should be called regularly. It takes care of detecting changes that can only be
     /// detected via polling. Some things only when project state count has changed, others every
     /// single time.
+    ///
+    /// Covers all open projects, not just the current one. REAPER notifies control surfaces about
+    /// track set changes in the current project only, so background projects are checked here.
     pub fn run(&mut self, handle_change: &mut impl FnMut(ChangeEvent)) {
         let reaper = Reaper::get();
-        let project = reaper.current_project();
-        if let Some(mut project_data) = self.find_project_data(project.raw()) {
+        let open_projects: Vec<_> = reaper.projects().collect();
+        self.remove_closed_projects(&open_projects, handle_change);
+        let current_project = reaper.current_project();
+        for project in open_projects {
+            if project != current_project && self.state_has_changed_since_last_check(project) {
+                self.detect_track_set_changes(project, &mut *handle_change);
+            }
+            self.detect_item_changes(project, handle_change);
+            self.detect_marker_changes(project, false, handle_change);
+            self.detect_envelope_changes(project, handle_change);
+        }
+        let mut project_datas = self.project_datas.borrow_mut();
+        for (rea_project, project_data) in project_datas.iter_mut() {
             self.poll_for_more_track_prop_changes(
                 reaper.medium_reaper(),
-                project.raw(),
-                &mut project_data,
+                *rea_project,
+                project_data,
                 handle_change,
             );
         }
@@ -200,7 +280,7 @@ impl ChangeDetectionMiddleware {
             let new_value = unsafe { get_track_visibility(reaper, *track) };
             if old_value != new_value {
                 td.visibility = new_value;
-                let track = Track::new(*track, Some(project));
+                let track = TrackRef::new(*track, Some(project));
                 handle_change(ChangeEvent::TrackVisibilityChanged(
                     TrackVisibilityChangedEvent {
                         track,
@@ -241,7 +321,7 @@ impl ChangeDetectionMiddleware {
                 // ExtSetPanExt is not triggered: When Programmatically changing pan via
                 // `CSurf_SetSurfacePan`, e.g. when users changes pan via ReaLearn, not via REAPER
                 // UI.
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 handle_change(ChangeEvent::TrackPanChanged(TrackPanChangedEvent {
                     touched: false,
                     track,
@@ -254,13 +334,13 @@ impl ChangeDetectionMiddleware {
                     None => return false,
                     Some(td) => td,
                 };
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 if td.volume != args.volume {
                     let old = td.volume;
                     td.volume = args.volume;
                     handle_change(ChangeEvent::TrackVolumeChanged(TrackVolumeChangedEvent {
-                        touched: !self.track_parameter_is_automated(&track, reaper_str!("Volume")),
-                        track: track.clone(),
+                        touched: !self.track_parameter_is_automated(track, reaper_str!("Volume")),
+                        track,
                         old_value: old,
                         new_value: args.volume,
                     }));
@@ -271,7 +351,7 @@ impl ChangeDetectionMiddleware {
                 if td.automation_mode != new_automation_mode {
                     let old = td.automation_mode;
                     td.automation_mode = new_automation_mode;
-                    let track = Track::new(args.track, None);
+                    let track = TrackRef::new(args.track, None);
                     handle_change(ChangeEvent::TrackAutomationModeChanged(TrackAutomationModeChangedEvent {
                         track,
                         old_value: old,
@@ -297,9 +377,9 @@ impl ChangeDetectionMiddleware {
                 let old = td.mute;
                 if td.mute != args.is_mute {
                     td.mute = args.is_mute;
-                    let track = Track::new(args.track, None);
+                    let track = TrackRef::new(args.track, None);
                     handle_change(ChangeEvent::TrackMuteChanged(TrackMuteChangedEvent {
-                        touched: !self.track_parameter_is_automated(&track, reaper_str!("Mute")),
+                        touched: !self.track_parameter_is_automated(track, reaper_str!("Mute")),
                         track,
                         old_value: old,
                         new_value: args.is_mute,
@@ -314,7 +394,7 @@ impl ChangeDetectionMiddleware {
                 let old = td.selected;
                 if td.selected != args.is_selected {
                     td.selected = args.is_selected;
-                    let track = Track::new(args.track, None);
+                    let track = TrackRef::new(args.track, None);
                     handle_change(ChangeEvent::TrackSelectedChanged(
                         TrackSelectedChangedEvent {
                             track,
@@ -332,7 +412,7 @@ impl ChangeDetectionMiddleware {
                 let old = td.solo;
                 if td.solo != args.is_solo {
                     td.solo = args.is_solo;
-                    let track = Track::new(args.track, None);
+                    let track = TrackRef::new(args.track, None);
                     handle_change(ChangeEvent::TrackSoloChanged(TrackSoloChangedEvent {
                         track,
                         old_value: old,
@@ -348,7 +428,7 @@ impl ChangeDetectionMiddleware {
                 let old = td.recarm;
                 if td.recarm != args.is_armed {
                     td.recarm = args.is_armed;
-                    let track = Track::new(args.track, None);
+                    let track = TrackRef::new(args.track, None);
                     handle_change(ChangeEvent::TrackArmChanged(TrackArmChangedEvent {
                         track,
                         old_value: old,
@@ -361,7 +441,7 @@ impl ChangeDetectionMiddleware {
                     self.decrease_num_track_set_changes_left_to_be_propagated();
                     return true;
                 }
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 handle_change(ChangeEvent::TrackNameChanged(TrackNameChangedEvent {
                     track,
                 }));
@@ -374,7 +454,7 @@ impl ChangeDetectionMiddleware {
                 let old = td.recmonitor;
                 if td.recmonitor != args.mode {
                     td.recmonitor = args.mode;
-                    let track = Track::new(args.track, None);
+                    let track = TrackRef::new(args.track, None);
                     handle_change(ChangeEvent::TrackInputMonitoringChanged(
                         TrackInputMonitoringChangedEvent {
                             track,
@@ -392,7 +472,7 @@ impl ChangeDetectionMiddleware {
                 if td.recinput != recinput {
                     let old = td.recinput;
                     td.recinput = recinput;
-                    let track = Track::new(args.track, None);
+                    let track = TrackRef::new(args.track, None);
                     handle_change(ChangeEvent::TrackInputChanged(TrackInputChangedEvent {
                         track,
                         old_value: RecordingInput::from_raw(old),
@@ -404,10 +484,12 @@ impl ChangeDetectionMiddleware {
             ExtSetFxParamRecFx(args) => self.fx_param_set(args, true, handle_change),
             ExtSetFxEnabled(args) => {
                 // Unfortunately, we don't have a ReaProject* here. Therefore we pass a nullptr.
-                let track = Track::new(args.track, None);
-                if let Some(fx) = self.fx_from_parm_fx_index(&track, args.fx_location, None, None) {
+                let track = TrackRef::new(args.track, None);
+                if let Some(fx) = track.to_track().and_then(|t| {
+                    self.fx_from_parm_fx_index(&t, args.fx_location, None, None)
+                }) {
                     handle_change(ChangeEvent::FxEnabledChanged(FxEnabledChangedEvent {
-                        fx,
+                        fx: create_fx_ref(track, &fx),
                         new_value: args.is_enabled,
                     }));
                 }
@@ -421,10 +503,10 @@ impl ChangeDetectionMiddleware {
                 if !changed {
                     return true;
                 }
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 let is_automated =
-                    self.track_parameter_is_automated(&track, reaper_str!("Send Volume"));
-                let route = TrackRoute::new(track, TrackSendDirection::Send, args.send_index);
+                    self.track_parameter_is_automated(track, reaper_str!("Send Volume"));
+                let route = TrackRouteRef::new(track, TrackSendDirection::Send, args.send_index);
                 handle_change(ChangeEvent::TrackRouteVolumeChanged(
                     TrackRouteVolumeChangedEvent {
                         touched: !is_automated,
@@ -443,10 +525,10 @@ impl ChangeDetectionMiddleware {
                 if !changed {
                     return true;
                 }
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 let is_automated =
-                    self.track_parameter_is_automated(&track, reaper_str!("Send Pan"));
-                let route = TrackRoute::new(track, TrackSendDirection::Send, args.send_index);
+                    self.track_parameter_is_automated(track, reaper_str!("Send Pan"));
+                let route = TrackRouteRef::new(track, TrackSendDirection::Send, args.send_index);
                 handle_change(ChangeEvent::TrackRoutePanChanged(TrackRoutePanChangedEvent {
                     touched: !is_automated,
                     route,
@@ -463,10 +545,11 @@ impl ChangeDetectionMiddleware {
                 if !changed {
                     return true;
                 }
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 let is_automated =
-                    self.track_parameter_is_automated(&track, reaper_str!("Send Volume"));
-                let route = TrackRoute::new(track, TrackSendDirection::Receive, args.receive_index);
+                    self.track_parameter_is_automated(track, reaper_str!("Send Volume"));
+                let route =
+                    TrackRouteRef::new(track, TrackSendDirection::Receive, args.receive_index);
                 handle_change(ChangeEvent::TrackRouteVolumeChanged(
                     TrackRouteVolumeChangedEvent {
                         touched: !is_automated,
@@ -485,10 +568,11 @@ impl ChangeDetectionMiddleware {
                 if !changed {
                     return true;
                 }
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 let is_automated =
-                    self.track_parameter_is_automated(&track, reaper_str!("Send Pan"));
-                let route = TrackRoute::new(track, TrackSendDirection::Receive, args.receive_index);
+                    self.track_parameter_is_automated(track, reaper_str!("Send Pan"));
+                let route =
+                    TrackRouteRef::new(track, TrackSendDirection::Receive, args.receive_index);
                 handle_change(ChangeEvent::TrackRoutePanChanged(TrackRoutePanChangedEvent {
                     touched: !is_automated,
                     route,
@@ -506,10 +590,10 @@ impl ChangeDetectionMiddleware {
                 }
                 let old = td.pan;
                 td.pan = args.pan;
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 handle_change(ChangeEvent::TrackPanChanged(TrackPanChangedEvent {
-                    touched: !self.track_parameter_is_automated(&track, reaper_str!("Pan")),
-                    track: track.clone(),
+                    touched: !self.track_parameter_is_automated(track, reaper_str!("Pan")),
+                    track,
                     old_value: old,
                     new_value: AvailablePanValue::Complete(args.pan),
                 }));
@@ -531,13 +615,13 @@ impl ChangeDetectionMiddleware {
                     TrackFx(track_fx_ref) => {
                         // Unfortunately, we don't have a ReaProject* here. Therefore we pass a
                         // nullptr.
-                        let track = Track::new(fx_ref.track, None);
-                        if let Some(fx) =
-                            self.fx_from_parm_fx_index(&track, track_fx_ref, None, None)
-                        {
+                        let track = TrackRef::new(fx_ref.track, None);
+                        if let Some(fx) = track.to_track().and_then(|t| {
+                            self.fx_from_parm_fx_index(&t, track_fx_ref, None, None)
+                        }) {
                             // Because CSURF_EXT_SETFXCHANGE doesn't fire if FX pasted in REAPER <
                             // 5.95-pre2 and on chunk manipulations
-                            if let Some(mut td) = self.find_track_data(track.raw_unchecked()) {
+                            if let Some(mut td) = self.find_track_data(track.raw()) {
                                 self.detect_fx_changes_on_track(
                                     &mut td.fx_chain_pair,
                                     track,
@@ -547,7 +631,7 @@ impl ChangeDetectionMiddleware {
                                     &mut handle_change,
                                 );
                                 handle_change(ChangeEvent::FxFocused(FxFocusedEvent {
-                                    fx: Some(fx),
+                                    fx: Some(create_fx_ref(track, &fx)),
                                 }));
                             }
                         }
@@ -556,11 +640,13 @@ impl ChangeDetectionMiddleware {
             }
             ExtSetFxOpen(args) => {
                 // Unfortunately, we don't have a ReaProject* here. Therefore we pass a nullptr.
-                let track = Track::new(args.track, None);
-                if let Some(fx) = self.fx_from_parm_fx_index(&track, args.fx_location, None, None) {
+                let track = TrackRef::new(args.track, None);
+                if let Some(fx) = track.to_track().and_then(|t| {
+                    self.fx_from_parm_fx_index(&t, args.fx_location, None, None)
+                }) {
                     // Because CSURF_EXT_SETFXCHANGE doesn't fire if FX pasted in REAPER < 5.95-pre2
                     // and on chunk manipulations
-                    if let Some(mut td) = self.find_track_data(track.raw_unchecked()) {
+                    if let Some(mut td) = self.find_track_data(track.raw()) {
                         self.detect_fx_changes_on_track(
                             &mut td.fx_chain_pair,
                             track,
@@ -569,6 +655,7 @@ impl ChangeDetectionMiddleware {
                             fx.is_input_fx(),
                             &mut handle_change,
                         );
+                        let fx = create_fx_ref(track, &fx);
                         let change_event = if args.is_open {
                             ChangeEvent::FxOpened(FxOpenedEvent { fx })
                         } else {
@@ -579,8 +666,8 @@ impl ChangeDetectionMiddleware {
                 }
             }
             ExtSetFxChange(args) => {
-                let track = Track::new(args.track, None);
-                if let Some(mut td) = self.find_track_data(track.raw_unchecked()) {
+                let track = TrackRef::new(args.track, None);
+                if let Some(mut td) = self.find_track_data(track.raw()) {
                     match args.fx_chain_type {
                         Some(t) => {
                             let is_input_fx = t == TrackFxChainType::InputFxChain;
@@ -630,11 +717,15 @@ impl ChangeDetectionMiddleware {
                 }
             }
             ExtTrackFxPresetChanged(args) => {
-                let track = Track::new(args.track, None);
+                let track = TrackRef::new(args.track, None);
                 let fx = track
-                    .fx_by_query_index(args.fx_location.to_raw())
-                    .expect("preset changed but FX not found");
-                handle_change(ChangeEvent::FxPresetChanged(FxPresetChangedEvent { fx }));
+                    .to_track()
+                    .and_then(|t| t.fx_by_query_index(args.fx_location.to_raw()));
+                if let Some(fx) = fx {
+                    handle_change(ChangeEvent::FxPresetChanged(FxPresetChangedEvent {
+                        fx: create_fx_ref(track, &fx),
+                    }));
+                }
             }
             SetPlayState(args) => {
                 handle_change(ChangeEvent::PlayStateChanged(PlayStateChangedEvent {
@@ -653,9 +744,9 @@ impl ChangeDetectionMiddleware {
                 }));
             }
             ExtSetProjectMarkerChange(_) => {
-                handle_change(ChangeEvent::BookmarksChanged(BookmarksChangedEvent {
-                    project: Reaper::get().current_project()
-                }));
+                let project = Reaper::get().current_project();
+                handle_change(ChangeEvent::BookmarksChanged(BookmarksChangedEvent { project }));
+                self.detect_marker_changes(project, true, &mut handle_change);
             }
             CloseNoReset |
             SetAutoMode(_) |
@@ -734,21 +825,24 @@ impl ChangeDetectionMiddleware {
         }))
     }
 
-    fn track_parameter_is_automated(&self, track: &Track, parameter_name: &ReaperStr) -> bool {
-        if !track.is_available() {
+    fn track_parameter_is_automated(&self, track: TrackRef, parameter_name: &ReaperStr) -> bool {
+        if !track.is_valid() {
             return false;
         }
-        let env = unsafe {
-            Reaper::get()
-                .medium_reaper()
-                .get_track_envelope_by_name(track.raw_unchecked(), parameter_name)
-        };
+        let reaper = Reaper::get().medium_reaper();
+        let env = unsafe { reaper.get_track_envelope_by_name(track.raw(), parameter_name) };
         if env.is_none() {
             return false;
         }
+        use GlobalAutomationModeOverride::*;
+        let effective_automation_mode = match reaper.get_global_automation_override() {
+            None => Some(unsafe { reaper.get_track_automation_mode(track.raw()) }),
+            Some(Bypass) => None,
+            Some(Mode(am)) => Some(am),
+        };
         use AutomationMode::*;
         !matches!(
-            track.effective_automation_mode(),
+            effective_automation_mode,
             None | Some(TrimRead) | Some(Write)
         )
     }
@@ -816,12 +910,15 @@ impl ChangeDetectionMiddleware {
         mut handle_change: impl FnMut(ChangeEvent),
     ) {
         // Unfortunately, we don't have a ReaProject* here. Therefore we pass a nullptr.
-        let track = Track::new(args.track, None);
+        let track = TrackRef::new(args.track, None);
         let is_input_fx = if self.supports_detection_of_input_fx {
             is_input_fx_if_supported
         } else {
+            let Some(full_track) = track.to_track() else {
+                return;
+            };
             self.is_probably_input_fx(
-                &track,
+                &full_track,
                 args.fx_index,
                 Some(args.param_index),
                 Some(args.param_value),
@@ -839,33 +936,32 @@ impl ChangeDetectionMiddleware {
         ) {
             return;
         }
-        let fx_chain = if is_input_fx {
-            track.input_fx_chain()
+        let fx_location = if is_input_fx {
+            TrackFxLocation::InputFxChain(args.fx_index)
         } else {
-            track.normal_fx_chain()
+            TrackFxLocation::NormalFxChain(args.fx_index)
         };
-        if let Some(fx) = fx_chain.fx_by_index(args.fx_index) {
-            let parameter = fx.parameter_by_index(args.param_index);
-            handle_change(ChangeEvent::FxParameterValueChanged(
-                FxParameterValueChangedEvent {
-                    touched: {
-                        if let Some(last_touched_param) = Reaper::get().last_touched_fx_parameter()
-                        {
-                            parameter == last_touched_param
-                        } else {
-                            false
-                        }
-                    },
-                    parameter,
-                    new_value: args.param_value,
-                },
-            ));
-        }
+        let Ok(fx_guid) = (unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .track_fx_get_fx_guid(args.track, fx_location)
+        }) else {
+            return;
+        };
+        let fx = FxRef::new(track, is_input_fx, Guid::new(fx_guid));
+        let parameter = FxParameterRef::new(fx, args.param_index);
+        handle_change(ChangeEvent::FxParameterValueChanged(
+            FxParameterValueChangedEvent {
+                touched: is_last_touched_fx_parameter(parameter),
+                parameter,
+                new_value: args.param_value,
+            },
+        ));
     }
 
     fn set_track_list_change(&self, handle_change: impl FnMut(ChangeEvent)) {
-        // TODO-low Not multi-project compatible!
         let new_active_project = Reaper::get().current_project();
+        // REAPER propagates the track set changes of the current project only
         self.num_track_set_changes_left_to_be_propagated
             .replace(new_active_project.track_count() + 1);
         self.react_to_track_list_change(new_active_project, handle_change);
@@ -883,16 +979,25 @@ impl ChangeDetectionMiddleware {
                 new_project: new_active_project,
             }));
         }
-        self.remove_invalid_rea_projects(&mut handle_change);
-        self.detect_track_set_changes(handle_change);
+        let open_projects: Vec<_> = Reaper::get().projects().collect();
+        self.remove_closed_projects(&open_projects, &mut handle_change);
+        // When just switching tabs, the cached data of the other projects is still up-to-date
+        // unless they have changed in the background.
+        for project in open_projects {
+            if project == new_active_project || self.state_has_changed_since_last_check(project) {
+                self.detect_track_set_changes(project, &mut handle_change);
+            }
+        }
     }
 
-    fn remove_invalid_rea_projects(&self, handle_change: &mut impl FnMut(ChangeEvent)) {
+    fn remove_closed_projects(
+        &self,
+        open_projects: &[Project],
+        handle_change: &mut impl FnMut(ChangeEvent),
+    ) {
+        let open_projects: HashSet<_> = open_projects.iter().map(|p| p.raw()).collect();
         self.project_datas.borrow_mut().retain(|rea_project, _| {
-            if Reaper::get()
-                .medium_reaper()
-                .validate_ptr_2(CurrentProject, *rea_project)
-            {
+            if open_projects.contains(rea_project) {
                 true
             } else {
                 handle_change(ChangeEvent::ProjectClosed(ProjectClosedEvent {
@@ -903,10 +1008,157 @@ impl ChangeDetectionMiddleware {
         });
     }
 
-    fn detect_track_set_changes(&self, handle_change: impl FnMut(ChangeEvent)) {
-        let project = Reaper::get().current_project();
+    /// Returns `true` if the given project is not known yet or its state has changed since the
+    /// last track set check.
+    fn state_has_changed_since_last_check(&self, project: Project) -> bool {
+        match self.project_datas.borrow().get(&project.raw()) {
+            None => true,
+            Some(pd) => pd.last_state_change_count != Some(project.state_change_count()),
+        }
+    }
+
+    /// Detects item changes by diffing item snapshots.
+    ///
+    /// Only does something if the project state has changed. The first check of a project just
+    /// takes the initial snapshots without emitting any events.
+    fn detect_item_changes(&self, project: Project, handle_change: &mut impl FnMut(ChangeEvent)) {
+        let state_change_count = project.state_change_count();
+        let mut project_datas = self.project_datas.borrow_mut();
+        let Some(project_data) = project_datas.get_mut(&project.raw()) else {
+            return;
+        };
+        if project_data.last_item_check_state_change_count == Some(state_change_count) {
+            return;
+        }
+        // In large projects, taking snapshots is expensive, so we don't do it too often. Changes
+        // are not lost, they are just reported a bit later.
+        if project_data.item_snapshots.len() > LARGE_PROJECT_ITEM_COUNT {
+            let checked_recently = project_data
+                .last_item_check
+                .map(|t| t.elapsed() < LARGE_PROJECT_ITEM_CHECK_INTERVAL)
+                .unwrap_or(false);
+            if checked_recently {
+                return;
+            }
+        }
+        let is_initial_check = project_data.last_item_check_state_change_count.is_none();
+        project_data.last_item_check_state_change_count = Some(state_change_count);
+        project_data.last_item_check = Some(Instant::now());
+        let new_snapshots = take_item_snapshots(project);
+        if !is_initial_check {
+            emit_item_changes(
+                project,
+                &project_data.item_snapshots,
+                &new_snapshots,
+                handle_change,
+            );
+        }
+        project_data.item_snapshots = new_snapshots;
+    }
+
+    /// Detects marker, region and tempo map changes by diffing snapshots.
+    ///
+    /// Unless `force` is `true`, only does something if the project state has changed. The first
+    /// check of a project just takes the initial snapshots without emitting any events.
+    fn detect_marker_changes(
+        &self,
+        project: Project,
+        force: bool,
+        handle_change: &mut impl FnMut(ChangeEvent),
+    ) {
+        let state_change_count = project.state_change_count();
+        let mut project_datas = self.project_datas.borrow_mut();
+        let Some(project_data) = project_datas.get_mut(&project.raw()) else {
+            return;
+        };
+        if !force && project_data.last_marker_check_state_change_count == Some(state_change_count) {
+            return;
+        }
+        let is_initial_check = project_data.last_marker_check_state_change_count.is_none();
+        project_data.last_marker_check_state_change_count = Some(state_change_count);
+        let new_markers = read_markers(project);
+        let new_tempo_markers = read_tempo_markers(project);
+        if !is_initial_check {
+            for change in diff_markers(&project_data.markers, &new_markers) {
+                let event = match change {
+                    MarkerChange::Added(marker) => {
+                        ChangeEvent::MarkerAdded(MarkerAddedEvent { project, marker })
+                    }
+                    MarkerChange::Removed(marker) => {
+                        ChangeEvent::MarkerRemoved(MarkerRemovedEvent { project, marker })
+                    }
+                    MarkerChange::Changed { old, new } => {
+                        ChangeEvent::MarkerChanged(MarkerChangedEvent { project, old, new })
+                    }
+                };
+                handle_change(event);
+            }
+            let first_affected_position =
+                first_affected_tempo_position(&project_data.tempo_markers, &new_tempo_markers);
+            if let Some(first_affected_position) = first_affected_position {
+                handle_change(ChangeEvent::TempoMapChanged(TempoMapChangedEvent {
+                    project,
+                    first_affected_position,
+                }));
+            }
+        }
+        project_data.markers = new_markers;
+        project_data.tempo_markers = new_tempo_markers;
+    }
+
+    /// Detects envelope changes by diffing envelope snapshots.
+    ///
+    /// Only does something if the project state has changed. The first check of a project just
+    /// takes the initial snapshots without emitting any events.
+    fn detect_envelope_changes(
+        &self,
+        project: Project,
+        handle_change: &mut impl FnMut(ChangeEvent),
+    ) {
+        let state_change_count = project.state_change_count();
+        let mut project_datas = self.project_datas.borrow_mut();
+        let Some(project_data) = project_datas.get_mut(&project.raw()) else {
+            return;
+        };
+        if project_data.last_envelope_check_state_change_count == Some(state_change_count) {
+            return;
+        }
+        // Same as with items
+        let point_count: usize = project_data
+            .envelope_snapshots
+            .values()
+            .map(|s| s.state.points.len())
+            .sum();
+        if point_count > LARGE_PROJECT_ENVELOPE_POINT_COUNT {
+            let checked_recently = project_data
+                .last_envelope_check
+                .map(|t| t.elapsed() < LARGE_PROJECT_ENVELOPE_CHECK_INTERVAL)
+                .unwrap_or(false);
+            if checked_recently {
+                return;
+            }
+        }
+        let is_initial_check = project_data
+            .last_envelope_check_state_change_count
+            .is_none();
+        project_data.last_envelope_check_state_change_count = Some(state_change_count);
+        project_data.last_envelope_check = Some(Instant::now());
+        let new_snapshots = take_envelope_snapshots(project);
+        if !is_initial_check {
+            emit_envelope_changes(
+                project,
+                &project_data.envelope_snapshots,
+                &new_snapshots,
+                handle_change,
+            );
+        }
+        project_data.envelope_snapshots = new_snapshots;
+    }
+
+    fn detect_track_set_changes(&self, project: Project, handle_change: impl FnMut(ChangeEvent)) {
         let mut project_datas = self.project_datas.borrow_mut();
         let project_data = project_datas.entry(project.raw()).or_default();
+        project_data.last_state_change_count = Some(project.state_change_count());
         let track_datas = &mut project_data.track_datas;
         let old_track_count = track_datas.len() as u32;
         // +1 for master track
@@ -936,9 +1188,10 @@ impl ChangeDetectionMiddleware {
             {
                 true
             } else {
-                if let Ok(track) = project.track_by_guid(&data.guid) {
-                    handle_change(ChangeEvent::TrackRemoved(TrackRemovedEvent { track }));
-                }
+                handle_change(ChangeEvent::TrackRemoved(TrackRemovedEvent {
+                    track: TrackRef::new(*media_track, Some(project.raw())),
+                    guid: data.guid,
+                }));
                 false
             }
         });
@@ -950,8 +1203,7 @@ impl ChangeDetectionMiddleware {
         track_datas: &mut TrackDataMap,
         mut handle_change: impl FnMut(ChangeEvent),
     ) {
-        for t in project.master_track().into_iter().chain(project.tracks()) {
-            let mt = t.raw_unchecked();
+        for mt in media_tracks(project) {
             track_datas.entry(mt).or_insert_with(|| {
                 let func = Reaper::get().medium_reaper();
                 let mut td = unsafe {
@@ -1010,12 +1262,11 @@ impl ChangeDetectionMiddleware {
                 };
                 // TODO-low Use try_borrow_mut(). Then this just doesn't do anything if this event
                 //  is currently thrown already. Right now it would panic, which is unreasonable.
-                handle_change(ChangeEvent::TrackAdded(TrackAddedEvent {
-                    track: t.clone(),
-                }));
+                let track = TrackRef::new(mt, Some(project.raw()));
+                handle_change(ChangeEvent::TrackAdded(TrackAddedEvent { track }));
                 self.detect_fx_changes_on_track(
                     &mut td.fx_chain_pair,
-                    t,
+                    track,
                     false,
                     true,
                     true,
@@ -1029,20 +1280,20 @@ impl ChangeDetectionMiddleware {
     fn detect_fx_changes_on_track(
         &self,
         fx_chain_pair: &mut FxChainPair,
-        track: Track,
+        track: TrackRef,
         notify_listeners_about_changes: bool,
         check_normal_fx_chain: bool,
         check_input_fx_chain: bool,
         handle_change: &mut impl FnMut(ChangeEvent),
     ) {
-        if !track.is_available() {
+        let Some(full_track) = track.to_track() else {
             return;
-        }
+        };
         let added_or_removed_output_fx = if check_normal_fx_chain {
             self.detect_fx_changes_on_track_internal(
-                &track,
+                track,
+                &full_track.normal_fx_chain(),
                 &mut fx_chain_pair.output_fx_guids,
-                false,
                 notify_listeners_about_changes,
                 handle_change,
             )
@@ -1051,9 +1302,9 @@ impl ChangeDetectionMiddleware {
         };
         let added_or_removed_input_fx = if check_input_fx_chain {
             self.detect_fx_changes_on_track_internal(
-                &track,
+                track,
+                &full_track.input_fx_chain(),
                 &mut fx_chain_pair.input_fx_guids,
-                true,
                 notify_listeners_about_changes,
                 handle_change,
             )
@@ -1071,26 +1322,21 @@ impl ChangeDetectionMiddleware {
     // Returns true if FX was added or removed
     fn detect_fx_changes_on_track_internal(
         &self,
-        track: &Track,
+        track: TrackRef,
+        fx_chain: &FxChain,
         old_fx_guids: &mut HashSet<Guid>,
-        is_input_fx: bool,
         notify_listeners_about_changes: bool,
         handle_change: &mut impl FnMut(ChangeEvent),
     ) -> bool {
         let old_fx_count = old_fx_guids.len() as u32;
-        let fx_chain = if is_input_fx {
-            track.input_fx_chain()
-        } else {
-            track.normal_fx_chain()
-        };
         let new_fx_count = fx_chain.fx_count();
         use std::cmp::Ordering::*;
         match new_fx_count.cmp(&old_fx_count) {
             Less => {
                 self.remove_invalid_fx(
                     track,
+                    fx_chain,
                     old_fx_guids,
-                    is_input_fx,
                     notify_listeners_about_changes,
                     handle_change,
                 );
@@ -1103,8 +1349,8 @@ impl ChangeDetectionMiddleware {
             Greater => {
                 self.add_missing_fx(
                     track,
+                    fx_chain,
                     old_fx_guids,
-                    is_input_fx,
                     notify_listeners_about_changes,
                     handle_change,
                 );
@@ -1115,24 +1361,22 @@ impl ChangeDetectionMiddleware {
 
     fn remove_invalid_fx(
         &self,
-        track: &Track,
+        track: TrackRef,
+        fx_chain: &FxChain,
         old_fx_guids: &mut HashSet<Guid>,
-        is_input_fx: bool,
         notify_listeners_about_changes: bool,
         mut handle_change: impl FnMut(ChangeEvent),
     ) {
-        let new_fx_guids = self.fx_guids_on_track(track, is_input_fx);
+        let new_fx_guids: HashSet<_> = fx_chain
+            .fxs()
+            .map(|fx| fx.guid().expect("No FX GUID set"))
+            .collect();
         old_fx_guids.retain(|old_fx_guid| {
             if new_fx_guids.contains(old_fx_guid) {
                 true
             } else {
                 if notify_listeners_about_changes {
-                    let fx_chain = if is_input_fx {
-                        track.input_fx_chain()
-                    } else {
-                        track.normal_fx_chain()
-                    };
-                    let removed_fx = fx_chain.fx_by_guid(old_fx_guid);
+                    let removed_fx = FxRef::new(track, fx_chain.is_input_fx(), *old_fx_guid);
                     handle_change(ChangeEvent::FxRemoved(FxRemovedEvent { fx: removed_fx }));
                 }
                 false
@@ -1140,35 +1384,20 @@ impl ChangeDetectionMiddleware {
         });
     }
 
-    fn fx_guids_on_track(&self, track: &Track, is_input_fx: bool) -> HashSet<Guid> {
-        let fx_chain = if is_input_fx {
-            track.input_fx_chain()
-        } else {
-            track.normal_fx_chain()
-        };
-        fx_chain
-            .fxs()
-            .map(|fx| fx.guid().expect("No FX GUID set"))
-            .collect()
-    }
-
     fn add_missing_fx(
         &self,
-        track: &Track,
+        track: TrackRef,
+        fx_chain: &FxChain,
         fx_guids: &mut HashSet<Guid>,
-        is_input_fx: bool,
         notify_listeners_about_changes: bool,
         mut handle_change: impl FnMut(ChangeEvent),
     ) {
-        let fx_chain = if is_input_fx {
-            track.input_fx_chain()
-        } else {
-            track.normal_fx_chain()
-        };
         for fx in fx_chain.fxs() {
             let was_inserted = fx_guids.insert(fx.guid().expect("No FX GUID set"));
             if was_inserted && notify_listeners_about_changes {
-                handle_change(ChangeEvent::FxAdded(FxAddedEvent { fx }));
+                handle_change(ChangeEvent::FxAdded(FxAddedEvent {
+                    fx: create_fx_ref(track, &fx),
+                }));
             }
         }
     }
@@ -1204,12 +1433,11 @@ impl ChangeDetectionMiddleware {
                 || new_track_send_count != track_data.track_send_count
                 || new_receive_count != track_data.receive_count
             {
-                // TODO-high Use lightweight tracks so that creating them is essentially a no-op!
-                let track = Track::new(*media_track, None);
+                let track = TrackRef::new(*media_track, Some(project.raw()));
                 if new_output_send_count != track_data.output_send_count {
                     handle_change(ChangeEvent::HardwareOutputSendCountChanged(
                         HardwareOutputSendCountChangedEvent {
-                            track: track.clone(),
+                            track,
                             old: track_data.output_send_count,
                             new: new_output_send_count,
                         },
@@ -1219,7 +1447,7 @@ impl ChangeDetectionMiddleware {
                 if new_track_send_count != track_data.track_send_count {
                     handle_change(ChangeEvent::TrackSendCountChanged(
                         TrackSendCountChangedEvent {
-                            track: track.clone(),
+                            track,
                             old: track_data.track_send_count,
                             new: new_track_send_count,
                         },
@@ -1228,7 +1456,7 @@ impl ChangeDetectionMiddleware {
                 }
                 if new_receive_count != track_data.receive_count {
                     handle_change(ChangeEvent::ReceiveCountChanged(ReceiveCountChangedEvent {
-                        track: track.clone(),
+                        track,
                         old: track_data.receive_count,
                         new: new_receive_count,
                     }));
@@ -1282,6 +1510,23 @@ pub enum ChangeEvent {
     RepeatStateChanged(RepeatStateChangedEvent),
     ProjectClosed(ProjectClosedEvent),
     BookmarksChanged(BookmarksChangedEvent),
+    ItemAdded(ItemAddedEvent),
+    ItemRemoved(ItemRemovedEvent),
+    ItemMoved(ItemMovedEvent),
+    ItemSelectionChanged(ItemSelectionChangedEvent),
+    ItemMuteChanged(ItemMuteChangedEvent),
+    ActiveTakeChanged(ActiveTakeChangedEvent),
+    TakeSourceChanged(TakeSourceChangedEvent),
+    MarkerAdded(MarkerAddedEvent),
+    MarkerRemoved(MarkerRemovedEvent),
+    MarkerChanged(MarkerChangedEvent),
+    TempoMapChanged(TempoMapChangedEvent),
+    EnvelopeAdded(EnvelopeAddedEvent),
+    EnvelopeRemoved(EnvelopeRemovedEvent),
+    EnvelopePointsChanged(EnvelopePointsChangedEvent),
+    EnvelopeActiveChanged(EnvelopeActiveChangedEvent),
+    EnvelopeVisibilityChanged(EnvelopeVisibilityChangedEvent),
+    EnvelopeArmChanged(EnvelopeArmChangedEvent),
 }
 
 impl ChangeEvent {
@@ -1299,36 +1544,34 @@ impl ChangeEvent {
     pub fn is_still_valid(&self) -> bool {
         match self {
             ChangeEvent::ProjectSwitched(evt) => evt.new_project.is_available(),
-            ChangeEvent::TrackVolumeChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackPanChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackRouteVolumeChanged(evt) => evt.route.is_available(),
-            ChangeEvent::TrackRoutePanChanged(evt) => evt.route.is_available(),
-            ChangeEvent::TrackAdded(evt) => evt.track.is_available(),
+            ChangeEvent::TrackVolumeChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackPanChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackRouteVolumeChanged(evt) => evt.route.is_valid(),
+            ChangeEvent::TrackRoutePanChanged(evt) => evt.route.is_valid(),
+            ChangeEvent::TrackAdded(evt) => evt.track.is_valid(),
             ChangeEvent::TrackRemoved(_) => true,
             ChangeEvent::TracksReordered(evt) => evt.project.is_available(),
-            ChangeEvent::ReceiveCountChanged(evt) => evt.track.is_available(),
-            ChangeEvent::HardwareOutputSendCountChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackSendCountChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackNameChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackInputChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackInputMonitoringChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackArmChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackMuteChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackSoloChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackSelectedChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackVisibilityChanged(evt) => evt.track.is_available(),
-            ChangeEvent::TrackAutomationModeChanged(evt) => evt.track.is_available(),
-            ChangeEvent::FxAdded(evt) => evt.fx.is_available(),
+            ChangeEvent::ReceiveCountChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::HardwareOutputSendCountChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackSendCountChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackNameChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackInputChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackInputMonitoringChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackArmChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackMuteChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackSoloChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackSelectedChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackVisibilityChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::TrackAutomationModeChanged(evt) => evt.track.is_valid(),
+            ChangeEvent::FxAdded(evt) => evt.fx.is_valid(),
             ChangeEvent::FxRemoved(_) => true,
-            ChangeEvent::FxEnabledChanged(evt) => evt.fx.is_available(),
-            ChangeEvent::FxOpened(evt) => evt.fx.is_available(),
-            ChangeEvent::FxClosed(evt) => evt.fx.is_available(),
-            ChangeEvent::FxFocused(evt) => {
-                evt.fx.as_ref().map(|fx| fx.is_available()).unwrap_or(true)
-            }
-            ChangeEvent::FxReordered(evt) => evt.track.is_available(),
-            ChangeEvent::FxParameterValueChanged(evt) => evt.parameter.is_available(),
-            ChangeEvent::FxPresetChanged(evt) => evt.fx.is_available(),
+            ChangeEvent::FxEnabledChanged(evt) => evt.fx.is_valid(),
+            ChangeEvent::FxOpened(evt) => evt.fx.is_valid(),
+            ChangeEvent::FxClosed(evt) => evt.fx.is_valid(),
+            ChangeEvent::FxFocused(evt) => evt.fx.map(|fx| fx.is_valid()).unwrap_or(true),
+            ChangeEvent::FxReordered(evt) => evt.track.is_valid(),
+            ChangeEvent::FxParameterValueChanged(evt) => evt.parameter.is_valid(),
+            ChangeEvent::FxPresetChanged(evt) => evt.fx.is_valid(),
             ChangeEvent::MasterTempoChanged(evt) => evt.project.is_available(),
             ChangeEvent::MasterPlayRateChanged(evt) => evt.project.is_available(),
             ChangeEvent::GlobalAutomationOverrideChanged(evt) => evt.project.is_available(),
@@ -1336,6 +1579,85 @@ impl ChangeEvent {
             ChangeEvent::RepeatStateChanged(evt) => evt.project.is_available(),
             ChangeEvent::ProjectClosed(_) => true,
             ChangeEvent::BookmarksChanged(evt) => evt.project.is_available(),
+            ChangeEvent::ItemAdded(evt) => item_is_valid(evt.project, evt.item),
+            ChangeEvent::ItemRemoved(_) => true,
+            ChangeEvent::ItemMoved(evt) => item_is_valid(evt.project, evt.item),
+            ChangeEvent::ItemSelectionChanged(evt) => item_is_valid(evt.project, evt.item),
+            ChangeEvent::ItemMuteChanged(evt) => item_is_valid(evt.project, evt.item),
+            ChangeEvent::ActiveTakeChanged(evt) => item_is_valid(evt.project, evt.item),
+            ChangeEvent::TakeSourceChanged(evt) => item_is_valid(evt.project, evt.item),
+            ChangeEvent::MarkerAdded(evt) => evt.project.is_available(),
+            ChangeEvent::MarkerRemoved(evt) => evt.project.is_available(),
+            ChangeEvent::MarkerChanged(evt) => evt.project.is_available(),
+            ChangeEvent::TempoMapChanged(evt) => evt.project.is_available(),
+            ChangeEvent::EnvelopeAdded(evt) => evt.envelope.is_valid(),
+            ChangeEvent::EnvelopeRemoved(_) => true,
+            ChangeEvent::EnvelopePointsChanged(evt) => evt.envelope.is_valid(),
+            ChangeEvent::EnvelopeActiveChanged(evt) => evt.envelope.is_valid(),
+            ChangeEvent::EnvelopeVisibilityChanged(evt) => evt.envelope.is_valid(),
+            ChangeEvent::EnvelopeArmChanged(evt) => evt.envelope.is_valid(),
+        }
+    }
+
+    /// Returns the project to which this change event refers.
+    ///
+    /// For [`ChangeEvent::ProjectSwitched`] this is the new project. Returns `None` for
+    /// [`ChangeEvent::FxFocused`] if no FX is focused.
+    pub fn project(&self) -> Option<Project> {
+        match self {
+            ChangeEvent::ProjectSwitched(evt) => Some(evt.new_project),
+            ChangeEvent::TrackVolumeChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackPanChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackRouteVolumeChanged(evt) => Some(evt.route.track().project()),
+            ChangeEvent::TrackRoutePanChanged(evt) => Some(evt.route.track().project()),
+            ChangeEvent::TrackAdded(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackRemoved(evt) => Some(evt.track.project()),
+            ChangeEvent::TracksReordered(evt) => Some(evt.project),
+            ChangeEvent::ReceiveCountChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::HardwareOutputSendCountChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackSendCountChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackNameChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackInputChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackInputMonitoringChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackArmChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackMuteChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackSoloChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackSelectedChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackVisibilityChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::TrackAutomationModeChanged(evt) => Some(evt.track.project()),
+            ChangeEvent::FxAdded(evt) => Some(evt.fx.track().project()),
+            ChangeEvent::FxRemoved(evt) => Some(evt.fx.track().project()),
+            ChangeEvent::FxEnabledChanged(evt) => Some(evt.fx.track().project()),
+            ChangeEvent::FxOpened(evt) => Some(evt.fx.track().project()),
+            ChangeEvent::FxClosed(evt) => Some(evt.fx.track().project()),
+            ChangeEvent::FxFocused(evt) => evt.fx.map(|fx| fx.track().project()),
+            ChangeEvent::FxReordered(evt) => Some(evt.track.project()),
+            ChangeEvent::FxParameterValueChanged(evt) => Some(evt.parameter.fx().track().project()),
+            ChangeEvent::FxPresetChanged(evt) => Some(evt.fx.track().project()),
+            ChangeEvent::MasterTempoChanged(evt) => Some(evt.project),
+            ChangeEvent::MasterPlayRateChanged(evt) => Some(evt.project),
+            ChangeEvent::GlobalAutomationOverrideChanged(evt) => Some(evt.project),
+            ChangeEvent::PlayStateChanged(evt) => Some(evt.project),
+            ChangeEvent::RepeatStateChanged(evt) => Some(evt.project),
+            ChangeEvent::ProjectClosed(evt) => Some(evt.project),
+            ChangeEvent::BookmarksChanged(evt) => Some(evt.project),
+            ChangeEvent::ItemAdded(evt) => Some(evt.project),
+            ChangeEvent::ItemRemoved(evt) => Some(evt.project),
+            ChangeEvent::ItemMoved(evt) => Some(evt.project),
+            ChangeEvent::ItemSelectionChanged(evt) => Some(evt.project),
+            ChangeEvent::ItemMuteChanged(evt) => Some(evt.project),
+            ChangeEvent::ActiveTakeChanged(evt) => Some(evt.project),
+            ChangeEvent::TakeSourceChanged(evt) => Some(evt.project),
+            ChangeEvent::MarkerAdded(evt) => Some(evt.project),
+            ChangeEvent::MarkerRemoved(evt) => Some(evt.project),
+            ChangeEvent::MarkerChanged(evt) => Some(evt.project),
+            ChangeEvent::TempoMapChanged(evt) => Some(evt.project),
+            ChangeEvent::EnvelopeAdded(evt) => Some(evt.envelope.track().project()),
+            ChangeEvent::EnvelopeRemoved(evt) => Some(evt.project),
+            ChangeEvent::EnvelopePointsChanged(evt) => Some(evt.envelope.track().project()),
+            ChangeEvent::EnvelopeActiveChanged(evt) => Some(evt.envelope.track().project()),
+            ChangeEvent::EnvelopeVisibilityChanged(evt) => Some(evt.envelope.track().project()),
+            ChangeEvent::EnvelopeArmChanged(evt) => Some(evt.envelope.track().project()),
         }
     }
 }
@@ -1349,7 +1671,7 @@ pub struct ProjectSwitchedEvent {
 #[derive(Clone, Debug)]
 pub struct TrackVolumeChangedEvent {
     pub touched: bool,
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: ReaperVolumeValue,
     pub new_value: ReaperVolumeValue,
 }
@@ -1357,7 +1679,7 @@ pub struct TrackVolumeChangedEvent {
 #[derive(Clone, Debug)]
 pub struct TrackPanChangedEvent {
     pub touched: bool,
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: Pan,
     pub new_value: AvailablePanValue,
 }
@@ -1371,7 +1693,7 @@ pub enum AvailablePanValue {
 #[derive(Clone, Debug)]
 pub struct TrackRouteVolumeChangedEvent {
     pub touched: bool,
-    pub route: TrackRoute,
+    pub route: TrackRouteRef,
     pub old_value: Option<ReaperVolumeValue>,
     pub new_value: ReaperVolumeValue,
 }
@@ -1379,38 +1701,40 @@ pub struct TrackRouteVolumeChangedEvent {
 #[derive(Clone, Debug)]
 pub struct TrackRoutePanChangedEvent {
     pub touched: bool,
-    pub route: TrackRoute,
+    pub route: TrackRouteRef,
     pub old_value: Option<ReaperPanValue>,
     pub new_value: ReaperPanValue,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackAddedEvent {
-    pub track: Track,
+    pub track: TrackRef,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackRemovedEvent {
-    pub track: Track,
+    /// Not valid anymore, just for identification.
+    pub track: TrackRef,
+    pub guid: Guid,
 }
 
 #[derive(Clone, Debug)]
 pub struct HardwareOutputSendCountChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old: u32,
     pub new: u32,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackSendCountChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old: u32,
     pub new: u32,
 }
 
 #[derive(Clone, Debug)]
 pub struct ReceiveCountChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old: u32,
     pub new: u32,
 }
@@ -1422,26 +1746,26 @@ pub struct TracksReorderedEvent {
 
 #[derive(Clone, Debug)]
 pub struct TrackNameChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackInputChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: Option<RecordingInput>,
     pub new_value: Option<RecordingInput>,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackInputMonitoringChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: InputMonitoringMode,
     pub new_value: InputMonitoringMode,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackArmChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: bool,
     pub new_value: bool,
 }
@@ -1449,33 +1773,34 @@ pub struct TrackArmChangedEvent {
 #[derive(Clone, Debug)]
 pub struct TrackMuteChangedEvent {
     pub touched: bool,
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: bool,
     pub new_value: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackSoloChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: bool,
     pub new_value: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackSelectedChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: bool,
     pub new_value: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct TrackVisibilityChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: TrackVisibility,
     pub new_value: TrackVisibility,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TrackVisibility {
     pub tcp: bool,
     pub mcp: bool,
@@ -1483,57 +1808,57 @@ pub struct TrackVisibility {
 
 #[derive(Clone, Debug)]
 pub struct TrackAutomationModeChangedEvent {
-    pub track: Track,
+    pub track: TrackRef,
     pub old_value: AutomationMode,
     pub new_value: AutomationMode,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxAddedEvent {
-    pub fx: Fx,
+    pub fx: FxRef,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxRemovedEvent {
-    pub fx: Fx,
+    pub fx: FxRef,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxEnabledChangedEvent {
-    pub fx: Fx,
+    pub fx: FxRef,
     pub new_value: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxOpenedEvent {
-    pub fx: Fx,
+    pub fx: FxRef,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxClosedEvent {
-    pub fx: Fx,
+    pub fx: FxRef,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxFocusedEvent {
-    pub fx: Option<Fx>,
+    pub fx: Option<FxRef>,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxReorderedEvent {
-    pub track: Track,
+    pub track: TrackRef,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxParameterValueChangedEvent {
     pub touched: bool,
-    pub parameter: FxParameter,
+    pub parameter: FxParameterRef,
     pub new_value: ReaperNormalizedFxParamValue,
 }
 
 #[derive(Clone, Debug)]
 pub struct FxPresetChangedEvent {
-    pub fx: Fx,
+    pub fx: FxRef,
 }
 
 #[derive(Clone, Debug)]
@@ -1581,6 +1906,313 @@ pub struct BookmarksChangedEvent {
     pub project: Project,
 }
 
+#[derive(Clone, Debug)]
+pub struct ItemAddedEvent {
+    pub project: Project,
+    pub item: Item,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemRemovedEvent {
+    pub project: Project,
+    /// Not valid anymore, just for identification.
+    pub item: Item,
+    pub guid: Guid,
+}
+
+/// The item has been moved in time, resized or moved to another track.
+#[derive(Clone, Debug)]
+pub struct ItemMovedEvent {
+    pub project: Project,
+    pub item: Item,
+    pub old_position: PositionInSeconds,
+    pub new_position: PositionInSeconds,
+    pub old_length: DurationInSeconds,
+    pub new_length: DurationInSeconds,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemSelectionChangedEvent {
+    pub project: Project,
+    pub item: Item,
+    pub new_value: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemMuteChangedEvent {
+    pub project: Project,
+    pub item: Item,
+    pub new_value: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ActiveTakeChangedEvent {
+    pub project: Project,
+    pub item: Item,
+    pub new_value: Option<Take>,
+}
+
+/// The source of the active take of an item has been replaced.
+#[derive(Clone, Debug)]
+pub struct TakeSourceChangedEvent {
+    pub project: Project,
+    pub item: Item,
+    pub take: Take,
+}
+
+#[derive(Clone, Debug)]
+pub struct MarkerAddedEvent {
+    pub project: Project,
+    pub marker: MarkerInfo,
+}
+
+#[derive(Clone, Debug)]
+pub struct MarkerRemovedEvent {
+    pub project: Project,
+    pub marker: MarkerInfo,
+}
+
+/// A marker or region has been moved, resized, renamed or recolored.
+#[derive(Clone, Debug)]
+pub struct MarkerChangedEvent {
+    pub project: Project,
+    pub old: MarkerInfo,
+    pub new: MarkerInfo,
+}
+
+/// A tempo/time signature marker has been added, removed or changed.
+#[derive(Clone, Debug)]
+pub struct TempoMapChangedEvent {
+    pub project: Project,
+    /// Timeline positions before this one are not affected by the change.
+    pub first_affected_position: PositionInSeconds,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnvelopeAddedEvent {
+    pub envelope: EnvelopeRef,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnvelopeRemovedEvent {
+    pub project: Project,
+    /// Not valid anymore, just for identification.
+    ///
+    /// The track might have been removed as well, so don't convert it into a full track. Use
+    /// [`track_guid`](Self::track_guid) instead.
+    pub envelope: EnvelopeRef,
+    pub track_guid: Guid,
+}
+
+/// Envelope points have been added, removed or changed.
+#[derive(Clone, Debug)]
+pub struct EnvelopePointsChangedEvent {
+    pub envelope: EnvelopeRef,
+    /// Start of the time range which contains all changed points.
+    pub start: PositionInSeconds,
+    /// End of the time range which contains all changed points (inclusive).
+    pub end: PositionInSeconds,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnvelopeActiveChangedEvent {
+    pub envelope: EnvelopeRef,
+    pub new_value: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnvelopeVisibilityChangedEvent {
+    pub envelope: EnvelopeRef,
+    pub new_value: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnvelopeArmChangedEvent {
+    pub envelope: EnvelopeRef,
+    pub new_value: bool,
+}
+
+fn take_envelope_snapshots(project: Project) -> EnvelopeSnapshotMap {
+    let reaper = Reaper::get().medium_reaper();
+    let mut snapshots = EnvelopeSnapshotMap::new();
+    for media_track in media_tracks(project) {
+        // Most tracks don't have any envelopes, so they are skipped without further queries
+        let envelope_count = unsafe { reaper.count_track_envelopes(media_track) };
+        if envelope_count == 0 {
+            continue;
+        }
+        let track_guid = get_media_track_guid(media_track);
+        for i in 0..envelope_count {
+            let Some(raw) = (unsafe { reaper.get_track_envelope(media_track, i) }) else {
+                continue;
+            };
+            let snapshot = EnvelopeSnapshot {
+                track: media_track,
+                track_guid,
+                state: get_envelope_state(raw),
+            };
+            snapshots.insert(raw, snapshot);
+        }
+    }
+    snapshots
+}
+
+fn emit_envelope_changes(
+    project: Project,
+    old_snapshots: &EnvelopeSnapshotMap,
+    new_snapshots: &EnvelopeSnapshotMap,
+    handle_change: &mut impl FnMut(ChangeEvent),
+) {
+    let create_envelope = |raw: TrackEnvelope, snapshot: &EnvelopeSnapshot| {
+        EnvelopeRef::new(TrackRef::new(snapshot.track, Some(project.raw())), raw)
+    };
+    for (raw, old) in old_snapshots {
+        if !new_snapshots.contains_key(raw) {
+            handle_change(ChangeEvent::EnvelopeRemoved(EnvelopeRemovedEvent {
+                project,
+                envelope: create_envelope(*raw, old),
+                track_guid: old.track_guid,
+            }));
+        }
+    }
+    for (raw, new) in new_snapshots {
+        let envelope = create_envelope(*raw, new);
+        let Some(old) = old_snapshots.get(raw) else {
+            handle_change(ChangeEvent::EnvelopeAdded(EnvelopeAddedEvent { envelope }));
+            continue;
+        };
+        for change in old.state.changes_to(&new.state) {
+            let event = match change {
+                EnvelopeChange::PointsChanged { start, end } => {
+                    ChangeEvent::EnvelopePointsChanged(EnvelopePointsChangedEvent {
+                        envelope,
+                        start,
+                        end,
+                    })
+                }
+                EnvelopeChange::ActiveChanged(new_value) => {
+                    ChangeEvent::EnvelopeActiveChanged(EnvelopeActiveChangedEvent {
+                        envelope,
+                        new_value,
+                    })
+                }
+                EnvelopeChange::VisibilityChanged(new_value) => {
+                    ChangeEvent::EnvelopeVisibilityChanged(EnvelopeVisibilityChangedEvent {
+                        envelope,
+                        new_value,
+                    })
+                }
+                EnvelopeChange::ArmChanged(new_value) => {
+                    ChangeEvent::EnvelopeArmChanged(EnvelopeArmChangedEvent {
+                        envelope,
+                        new_value,
+                    })
+                }
+            };
+            handle_change(event);
+        }
+    }
+}
+
+fn item_is_valid(project: Project, item: Item) -> bool {
+    Reaper::get()
+        .medium_reaper()
+        .validate_ptr_2(Proj(project.raw()), item.raw())
+}
+
+fn take_item_snapshots(project: Project) -> ItemSnapshotMap {
+    let reaper = Reaper::get().medium_reaper();
+    let mut snapshots = ItemSnapshotMap::new();
+    for track in project.tracks() {
+        let media_track = track.raw_unchecked();
+        for item in track.items() {
+            let mi = item.raw();
+            let snapshot = unsafe {
+                let active_take = reaper.get_active_take(mi);
+                ItemSnapshot {
+                    guid: item.guid(),
+                    track: media_track,
+                    position: item.position(),
+                    length: item.length(),
+                    selected: reaper.get_media_item_info_value(mi, ItemAttributeKey::Selected)
+                        != 0.0,
+                    muted: reaper.get_media_item_info_value(mi, ItemAttributeKey::Mute) != 0.0,
+                    active_take,
+                    active_take_source: active_take
+                        .and_then(|t| reaper.get_media_item_take_source(t)),
+                }
+            };
+            snapshots.insert(mi, snapshot);
+        }
+    }
+    snapshots
+}
+
+fn emit_item_changes(
+    project: Project,
+    old_snapshots: &ItemSnapshotMap,
+    new_snapshots: &ItemSnapshotMap,
+    handle_change: &mut impl FnMut(ChangeEvent),
+) {
+    for (media_item, old) in old_snapshots {
+        if !new_snapshots.contains_key(media_item) {
+            handle_change(ChangeEvent::ItemRemoved(ItemRemovedEvent {
+                project,
+                item: Item::new(*media_item),
+                guid: old.guid,
+            }));
+        }
+    }
+    for (media_item, new) in new_snapshots {
+        let item = Item::new(*media_item);
+        let Some(old) = old_snapshots.get(media_item) else {
+            handle_change(ChangeEvent::ItemAdded(ItemAddedEvent { project, item }));
+            continue;
+        };
+        if new.track != old.track || new.position != old.position || new.length != old.length {
+            handle_change(ChangeEvent::ItemMoved(ItemMovedEvent {
+                project,
+                item,
+                old_position: old.position,
+                new_position: new.position,
+                old_length: old.length,
+                new_length: new.length,
+            }));
+        }
+        if new.selected != old.selected {
+            handle_change(ChangeEvent::ItemSelectionChanged(
+                ItemSelectionChangedEvent {
+                    project,
+                    item,
+                    new_value: new.selected,
+                },
+            ));
+        }
+        if new.muted != old.muted {
+            handle_change(ChangeEvent::ItemMuteChanged(ItemMuteChangedEvent {
+                project,
+                item,
+                new_value: new.muted,
+            }));
+        }
+        if new.active_take != old.active_take {
+            handle_change(ChangeEvent::ActiveTakeChanged(ActiveTakeChangedEvent {
+                project,
+                item,
+                new_value: new.active_take.map(Take::new),
+            }));
+        } else if new.active_take_source != old.active_take_source {
+            if let Some(take) = new.active_take {
+                handle_change(ChangeEvent::TakeSourceChanged(TakeSourceChangedEvent {
+                    project,
+                    item,
+                    take: Take::new(take),
+                }));
+            }
+        }
+    }
+}
+
 unsafe fn get_track_visibility(
     reaper: &reaper_medium::Reaper,
     track: MediaTrack,
@@ -1598,3 +2230,29 @@ unsafe fn get_boolean_track_prop(
 ) -> bool {
     reaper.get_media_track_info_value(track, key) != 0.0
 }
+
+/// Returns the master track and all normal tracks without creating [`Track`] objects.
+fn media_tracks(project: Project) -> impl Iterator<Item = MediaTrack> {
+    let reaper = Reaper::get().medium_reaper();
+    let master_track = reaper.get_master_track(Proj(project.raw()));
+    let tracks = (0..project.track_count()).filter_map(move |i| {
+        Reaper::get()
+            .medium_reaper()
+            .get_track(Proj(project.raw()), i)
+    });
+    std::iter::once(master_track).chain(tracks)
+}
+
+fn is_last_touched_fx_parameter(parameter: FxParameterRef) -> bool {
+    let Some(last_touched) = Reaper::get().last_touched_fx_parameter() else {
+        return false;
+    };
+    let fx = last_touched.fx();
+    last_touched.index() == parameter.index()
+        && fx.is_input_fx() == parameter.fx().is_input_fx()
+        && fx.guid() == Some(parameter.fx().guid())
+}
+
+fn create_fx_ref(track: TrackRef, fx: &Fx) -> FxRef {
+    FxRef::new(track, fx.is_input_fx(), fx.guid().expect("No FX GUID set"))
+}
@@ -0,0 +1,997 @@
+use crate::{
+    AvailablePanValue, BookmarkType, ChangeDetectionMiddleware, ChangeEvent, ControlSurfaceEvent,
+    ControlSurfaceMiddleware, Envelope, EnvelopeRef, Fx, FxParameterRef, FxRef, Guid, Item,
+    MarkerInfo, PanExt, Project, Reaper, Take, Track, TrackRef, TrackRouteRef, TrackVisibility,
+};
+use reaper_medium::ProjectContext::Proj;
+use reaper_medium::{
+    GlobalAutomationModeOverride, ItemAttributeKey, TakeAttributeKey, TrackSendDirection,
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::io::{self, BufRead, Write};
+use tracing::warn;
+
+/// Serializable version of a [`ChangeEvent`].
+///
+/// Objects are identified by GUIDs and indices instead of pointers, so records stay meaningful
+/// outside of the REAPER session in which they were recorded. Values are stored in their raw
+/// form as used by the low-level API.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ChangeEventRecord {
+    ProjectSwitched {
+        old_project: ProjectRecord,
+        new_project: ProjectRecord,
+    },
+    TrackVolumeChanged {
+        touched: bool,
+        track: TrackRecord,
+        old_value: f64,
+        new_value: f64,
+    },
+    /// In case of dual-pan, the values are the left pan values.
+    TrackPanChanged {
+        touched: bool,
+        track: TrackRecord,
+        old_value: f64,
+        new_value: f64,
+    },
+    TrackRouteVolumeChanged {
+        touched: bool,
+        route: TrackRouteRecord,
+        old_value: Option<f64>,
+        new_value: f64,
+    },
+    TrackRoutePanChanged {
+        touched: bool,
+        route: TrackRouteRecord,
+        old_value: Option<f64>,
+        new_value: f64,
+    },
+    TrackAdded {
+        track: TrackRecord,
+    },
+    TrackRemoved {
+        track: TrackRecord,
+    },
+    TracksReordered {
+        project: ProjectRecord,
+    },
+    ReceiveCountChanged {
+        track: TrackRecord,
+        old: u32,
+        new: u32,
+    },
+    HardwareOutputSendCountChanged {
+        track: TrackRecord,
+        old: u32,
+        new: u32,
+    },
+    TrackSendCountChanged {
+        track: TrackRecord,
+        old: u32,
+        new: u32,
+    },
+    TrackNameChanged {
+        track: TrackRecord,
+    },
+    TrackInputChanged {
+        track: TrackRecord,
+        old_value: Option<i32>,
+        new_value: Option<i32>,
+    },
+    TrackInputMonitoringChanged {
+        track: TrackRecord,
+        old_value: i32,
+        new_value: i32,
+    },
+    TrackArmChanged {
+        track: TrackRecord,
+        old_value: bool,
+        new_value: bool,
+    },
+    TrackMuteChanged {
+        touched: bool,
+        track: TrackRecord,
+        old_value: bool,
+        new_value: bool,
+    },
+    TrackSoloChanged {
+        track: TrackRecord,
+        old_value: bool,
+        new_value: bool,
+    },
+    TrackSelectedChanged {
+        track: TrackRecord,
+        old_value: bool,
+        new_value: bool,
+    },
+    TrackAutomationModeChanged {
+        track: TrackRecord,
+        old_value: i32,
+        new_value: i32,
+    },
+    TrackVisibilityChanged {
+        track: TrackRecord,
+        old_value: TrackVisibility,
+        new_value: TrackVisibility,
+    },
+    FxAdded {
+        fx: FxRecord,
+    },
+    FxRemoved {
+        fx: FxRecord,
+    },
+    FxEnabledChanged {
+        fx: FxRecord,
+        new_value: bool,
+    },
+    FxOpened {
+        fx: FxRecord,
+    },
+    FxClosed {
+        fx: FxRecord,
+    },
+    FxFocused {
+        fx: Option<FxRecord>,
+    },
+    FxReordered {
+        track: TrackRecord,
+    },
+    FxParameterValueChanged {
+        touched: bool,
+        parameter: FxParameterRecord,
+        new_value: f64,
+    },
+    FxPresetChanged {
+        fx: FxRecord,
+    },
+    MasterTempoChanged {
+        project: ProjectRecord,
+        touched: bool,
+        new_value: f64,
+    },
+    MasterPlayRateChanged {
+        project: ProjectRecord,
+        touched: bool,
+        new_value: f64,
+    },
+    /// -1 means no override, 6 means bypass, everything else is an automation mode.
+    GlobalAutomationOverrideChanged {
+        project: ProjectRecord,
+        old_value: i32,
+        new_value: i32,
+    },
+    PlayStateChanged {
+        project: ProjectRecord,
+        is_playing: bool,
+        is_paused: bool,
+        is_recording: bool,
+    },
+    RepeatStateChanged {
+        project: ProjectRecord,
+        new_value: bool,
+    },
+    ProjectClosed {
+        project: ProjectRecord,
+    },
+    BookmarksChanged {
+        project: ProjectRecord,
+    },
+    ItemAdded {
+        project: ProjectRecord,
+        item: ItemRecord,
+    },
+    ItemRemoved {
+        project: ProjectRecord,
+        item: ItemRecord,
+    },
+    ItemMoved {
+        project: ProjectRecord,
+        item: ItemRecord,
+        old_position: f64,
+        new_position: f64,
+        old_length: f64,
+        new_length: f64,
+    },
+    ItemSelectionChanged {
+        project: ProjectRecord,
+        item: ItemRecord,
+        new_value: bool,
+    },
+    ItemMuteChanged {
+        project: ProjectRecord,
+        item: ItemRecord,
+        new_value: bool,
+    },
+    ActiveTakeChanged {
+        project: ProjectRecord,
+        item: ItemRecord,
+        new_value: Option<TakeRecord>,
+    },
+    TakeSourceChanged {
+        project: ProjectRecord,
+        item: ItemRecord,
+        take: TakeRecord,
+    },
+    MarkerAdded {
+        project: ProjectRecord,
+        marker: MarkerRecord,
+    },
+    MarkerRemoved {
+        project: ProjectRecord,
+        marker: MarkerRecord,
+    },
+    MarkerChanged {
+        project: ProjectRecord,
+        old: MarkerRecord,
+        new: MarkerRecord,
+    },
+    TempoMapChanged {
+        project: ProjectRecord,
+        first_affected_position: f64,
+    },
+    EnvelopeAdded {
+        envelope: EnvelopeRecord,
+    },
+    EnvelopeRemoved {
+        project: ProjectRecord,
+        envelope: EnvelopeRecord,
+    },
+    EnvelopePointsChanged {
+        envelope: EnvelopeRecord,
+        start: f64,
+        end: f64,
+    },
+    EnvelopeActiveChanged {
+        envelope: EnvelopeRecord,
+        new_value: bool,
+    },
+    EnvelopeVisibilityChanged {
+        envelope: EnvelopeRecord,
+        new_value: bool,
+    },
+    EnvelopeArmChanged {
+        envelope: EnvelopeRecord,
+        new_value: bool,
+    },
+}
+
+/// Identifies a project by its position in the open project tabs.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct ProjectRecord {
+    /// `None` if the project is not open anymore.
+    pub index: Option<u32>,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct TrackRecord {
+    pub project: ProjectRecord,
+    /// GUID with braces. Empty if the track doesn't exist anymore and its GUID is not known.
+    pub guid: String,
+    /// `None` for the master track or if the track doesn't exist anymore.
+    pub index: Option<u32>,
+    pub is_master: bool,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct TrackRouteRecord {
+    pub track: TrackRecord,
+    pub is_receive: bool,
+    /// For sends, this counts both hardware output sends and track sends.
+    pub index: u32,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct FxRecord {
+    /// `None` for monitoring FX.
+    pub track: Option<TrackRecord>,
+    pub is_input_fx: bool,
+    /// GUID with braces. `None` if it couldn't be determined.
+    pub guid: Option<String>,
+    /// `None` if the FX doesn't exist anymore.
+    pub index: Option<u32>,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct FxParameterRecord {
+    pub fx: FxRecord,
+    pub index: u32,
+}
+
+/// Identifies an item by its GUID and by its track and its index within that track.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct ItemRecord {
+    /// GUID with braces. Empty if the item doesn't exist anymore and its GUID is not known.
+    pub guid: String,
+    /// `None` if the item doesn't exist anymore.
+    pub track: Option<TrackRecord>,
+    /// `None` if the item doesn't exist anymore.
+    pub index: Option<u32>,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct TakeRecord {
+    pub item: ItemRecord,
+    /// GUID with braces. Empty if the take doesn't exist anymore.
+    pub guid: String,
+    /// `None` if the take doesn't exist anymore.
+    pub index: Option<u32>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MarkerRecord {
+    pub is_region: bool,
+    pub id: u32,
+    pub position: f64,
+    pub region_end_position: Option<f64>,
+    pub name: String,
+    pub color: i32,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct EnvelopeRecord {
+    pub track: TrackRecord,
+    /// `None` if the envelope doesn't exist anymore.
+    pub index: Option<u32>,
+    /// `None` if the envelope doesn't exist anymore.
+    pub name: Option<String>,
+}
+
+impl From<&ChangeEvent> for ChangeEventRecord {
+    fn from(event: &ChangeEvent) -> Self {
+        use ChangeEvent as E;
+        use ChangeEventRecord as R;
+        match event {
+            E::ProjectSwitched(e) => R::ProjectSwitched {
+                old_project: e.old_project.into(),
+                new_project: e.new_project.into(),
+            },
+            E::TrackVolumeChanged(e) => R::TrackVolumeChanged {
+                touched: e.touched,
+                track: e.track.into(),
+                old_value: e.old_value.get(),
+                new_value: e.new_value.get(),
+            },
+            E::TrackPanChanged(e) => R::TrackPanChanged {
+                touched: e.touched,
+                track: e.track.into(),
+                old_value: e.old_value.main_pan().get(),
+                new_value: match e.new_value {
+                    AvailablePanValue::Complete(pan) => pan.main_pan().get(),
+                    AvailablePanValue::Incomplete(pan) => pan.get(),
+                },
+            },
+            E::TrackRouteVolumeChanged(e) => R::TrackRouteVolumeChanged {
+                touched: e.touched,
+                route: e.route.into(),
+                old_value: e.old_value.map(|v| v.get()),
+                new_value: e.new_value.get(),
+            },
+            E::TrackRoutePanChanged(e) => R::TrackRoutePanChanged {
+                touched: e.touched,
+                route: e.route.into(),
+                old_value: e.old_value.map(|v| v.get()),
+                new_value: e.new_value.get(),
+            },
+            E::TrackAdded(e) => R::TrackAdded {
+                track: e.track.into(),
+            },
+            E::TrackRemoved(e) => R::TrackRemoved {
+                track: TrackRecord::removed(e.track.project(), e.guid),
+            },
+            E::TracksReordered(e) => R::TracksReordered {
+                project: e.project.into(),
+            },
+            E::ReceiveCountChanged(e) => R::ReceiveCountChanged {
+                track: e.track.into(),
+                old: e.old,
+                new: e.new,
+            },
+            E::HardwareOutputSendCountChanged(e) => R::HardwareOutputSendCountChanged {
+                track: e.track.into(),
+                old: e.old,
+                new: e.new,
+            },
+            E::TrackSendCountChanged(e) => R::TrackSendCountChanged {
+                track: e.track.into(),
+                old: e.old,
+                new: e.new,
+            },
+            E::TrackNameChanged(e) => R::TrackNameChanged {
+                track: e.track.into(),
+            },
+            E::TrackInputChanged(e) => R::TrackInputChanged {
+                track: e.track.into(),
+                old_value: e.old_value.map(|v| v.to_raw()),
+                new_value: e.new_value.map(|v| v.to_raw()),
+            },
+            E::TrackInputMonitoringChanged(e) => R::TrackInputMonitoringChanged {
+                track: e.track.into(),
+                old_value: e.old_value.to_raw(),
+                new_value: e.new_value.to_raw(),
+            },
+            E::TrackArmChanged(e) => R::TrackArmChanged {
+                track: e.track.into(),
+                old_value: e.old_value,
+                new_value: e.new_value,
+            },
+            E::TrackMuteChanged(e) => R::TrackMuteChanged {
+                touched: e.touched,
+                track: e.track.into(),
+                old_value: e.old_value,
+                new_value: e.new_value,
+            },
+            E::TrackSoloChanged(e) => R::TrackSoloChanged {
+                track: e.track.into(),
+                old_value: e.old_value,
+                new_value: e.new_value,
+            },
+            E::TrackSelectedChanged(e) => R::TrackSelectedChanged {
+                track: e.track.into(),
+                old_value: e.old_value,
+                new_value: e.new_value,
+            },
+            E::TrackAutomationModeChanged(e) => R::TrackAutomationModeChanged {
+                track: e.track.into(),
+                old_value: e.old_value.to_raw(),
+                new_value: e.new_value.to_raw(),
+            },
+            E::TrackVisibilityChanged(e) => R::TrackVisibilityChanged {
+                track: e.track.into(),
+                old_value: e.old_value,
+                new_value: e.new_value,
+            },
+            E::FxAdded(e) => R::FxAdded { fx: e.fx.into() },
+            E::FxRemoved(e) => R::FxRemoved { fx: e.fx.into() },
+            E::FxEnabledChanged(e) => R::FxEnabledChanged {
+                fx: e.fx.into(),
+                new_value: e.new_value,
+            },
+            E::FxOpened(e) => R::FxOpened { fx: e.fx.into() },
+            E::FxClosed(e) => R::FxClosed { fx: e.fx.into() },
+            E::FxFocused(e) => R::FxFocused {
+                fx: e.fx.map(|fx| fx.into()),
+            },
+            E::FxReordered(e) => R::FxReordered {
+                track: e.track.into(),
+            },
+            E::FxParameterValueChanged(e) => R::FxParameterValueChanged {
+                touched: e.touched,
+                parameter: e.parameter.into(),
+                new_value: e.new_value.get(),
+            },
+            E::FxPresetChanged(e) => R::FxPresetChanged { fx: e.fx.into() },
+            E::MasterTempoChanged(e) => R::MasterTempoChanged {
+                project: e.project.into(),
+                touched: e.touched,
+                new_value: e.new_value.get(),
+            },
+            E::MasterPlayRateChanged(e) => R::MasterPlayRateChanged {
+                project: e.project.into(),
+                touched: e.touched,
+                new_value: e.new_value.get(),
+            },
+            E::GlobalAutomationOverrideChanged(e) => R::GlobalAutomationOverrideChanged {
+                project: e.project.into(),
+                old_value: global_automation_override_to_raw(e.old_value),
+                new_value: global_automation_override_to_raw(e.new_value),
+            },
+            E::PlayStateChanged(e) => R::PlayStateChanged {
+                project: e.project.into(),
+                is_playing: e.new_value.is_playing,
+                is_paused: e.new_value.is_paused,
+                is_recording: e.new_value.is_recording,
+            },
+            E::RepeatStateChanged(e) => R::RepeatStateChanged {
+                project: e.project.into(),
+                new_value: e.new_value,
+            },
+            E::ProjectClosed(e) => R::ProjectClosed {
+                project: e.project.into(),
+            },
+            E::BookmarksChanged(e) => R::BookmarksChanged {
+                project: e.project.into(),
+            },
+            E::ItemAdded(e) => R::ItemAdded {
+                project: e.project.into(),
+                item: ItemRecord::new(e.project, e.item),
+            },
+            E::ItemRemoved(e) => R::ItemRemoved {
+                project: e.project.into(),
+                item: ItemRecord::removed(e.guid),
+            },
+            E::ItemMoved(e) => R::ItemMoved {
+                project: e.project.into(),
+                item: ItemRecord::new(e.project, e.item),
+                old_position: e.old_position.get(),
+                new_position: e.new_position.get(),
+                old_length: e.old_length.get(),
+                new_length: e.new_length.get(),
+            },
+            E::ItemSelectionChanged(e) => R::ItemSelectionChanged {
+                project: e.project.into(),
+                item: ItemRecord::new(e.project, e.item),
+                new_value: e.new_value,
+            },
+            E::ItemMuteChanged(e) => R::ItemMuteChanged {
+                project: e.project.into(),
+                item: ItemRecord::new(e.project, e.item),
+                new_value: e.new_value,
+            },
+            E::ActiveTakeChanged(e) => R::ActiveTakeChanged {
+                project: e.project.into(),
+                item: ItemRecord::new(e.project, e.item),
+                new_value: e
+                    .new_value
+                    .as_ref()
+                    .map(|take| TakeRecord::new(e.project, e.item, take)),
+            },
+            E::TakeSourceChanged(e) => R::TakeSourceChanged {
+                project: e.project.into(),
+                item: ItemRecord::new(e.project, e.item),
+                take: TakeRecord::new(e.project, e.item, &e.take),
+            },
+            E::MarkerAdded(e) => R::MarkerAdded {
+                project: e.project.into(),
+                marker: (&e.marker).into(),
+            },
+            E::MarkerRemoved(e) => R::MarkerRemoved {
+                project: e.project.into(),
+                marker: (&e.marker).into(),
+            },
+            E::MarkerChanged(e) => R::MarkerChanged {
+                project: e.project.into(),
+                old: (&e.old).into(),
+                new: (&e.new).into(),
+            },
+            E::TempoMapChanged(e) => R::TempoMapChanged {
+                project: e.project.into(),
+                first_affected_position: e.first_affected_position.get(),
+            },
+            E::EnvelopeAdded(e) => R::EnvelopeAdded {
+                envelope: e.envelope.into(),
+            },
+            E::EnvelopeRemoved(e) => R::EnvelopeRemoved {
+                project: e.project.into(),
+                envelope: EnvelopeRecord {
+                    // The track might have been removed as well, so only look it up by GUID
+                    track: match e.project.track_by_guid(&e.track_guid) {
+                        Ok(track) if track.is_available() => (&track).into(),
+                        _ => TrackRecord::removed(e.project, e.track_guid),
+                    },
+                    index: None,
+                    name: None,
+                },
+            },
+            E::EnvelopePointsChanged(e) => R::EnvelopePointsChanged {
+                envelope: e.envelope.into(),
+                start: e.start.get(),
+                end: e.end.get(),
+            },
+            E::EnvelopeActiveChanged(e) => R::EnvelopeActiveChanged {
+                envelope: e.envelope.into(),
+                new_value: e.new_value,
+            },
+            E::EnvelopeVisibilityChanged(e) => R::EnvelopeVisibilityChanged {
+                envelope: e.envelope.into(),
+                new_value: e.new_value,
+            },
+            E::EnvelopeArmChanged(e) => R::EnvelopeArmChanged {
+                envelope: e.envelope.into(),
+                new_value: e.new_value,
+            },
+        }
+    }
+}
+
+impl From<Project> for ProjectRecord {
+    fn from(project: Project) -> Self {
+        Self {
+            index: project.index().ok(),
+        }
+    }
+}
+
+impl From<&Track> for TrackRecord {
+    fn from(track: &Track) -> Self {
+        Self {
+            project: track.project().into(),
+            guid: track.guid().to_string_with_braces(),
+            index: track.index(),
+            is_master: track.is_master_track(),
+        }
+    }
+}
+
+impl TrackRecord {
+    fn removed(project: Project, guid: Guid) -> Self {
+        Self {
+            project: project.into(),
+            guid: guid.to_string_with_braces(),
+            index: None,
+            is_master: false,
+        }
+    }
+}
+
+impl From<TrackRef> for TrackRecord {
+    fn from(track: TrackRef) -> Self {
+        match track.to_track() {
+            Some(t) => (&t).into(),
+            None => Self {
+                project: track.project().into(),
+                guid: String::new(),
+                index: None,
+                is_master: false,
+            },
+        }
+    }
+}
+
+impl From<TrackRouteRef> for TrackRouteRecord {
+    fn from(route: TrackRouteRef) -> Self {
+        Self {
+            track: route.track().into(),
+            is_receive: route.direction() == TrackSendDirection::Receive,
+            index: route.index(),
+        }
+    }
+}
+
+impl From<&Fx> for FxRecord {
+    fn from(fx: &Fx) -> Self {
+        let is_available = fx.is_available();
+        let guid = match fx.guid() {
+            Some(guid) => Some(guid),
+            None if is_available => fx.get_or_query_guid().ok(),
+            None => None,
+        };
+        Self {
+            track: fx.track().map(|t| t.into()),
+            is_input_fx: fx.is_input_fx(),
+            guid: guid.map(|g| g.to_string_with_braces()),
+            index: if is_available { Some(fx.index()) } else { None },
+        }
+    }
+}
+
+impl From<FxRef> for FxRecord {
+    fn from(fx: FxRef) -> Self {
+        match fx.to_fx() {
+            Some(f) => (&f).into(),
+            None => Self {
+                track: Some(fx.track().into()),
+                is_input_fx: fx.is_input_fx(),
+                guid: Some(fx.guid().to_string_with_braces()),
+                index: None,
+            },
+        }
+    }
+}
+
+impl From<FxParameterRef> for FxParameterRecord {
+    fn from(parameter: FxParameterRef) -> Self {
+        Self {
+            fx: parameter.fx().into(),
+            index: parameter.index(),
+        }
+    }
+}
+
+impl ItemRecord {
+    fn new(project: Project, item: Item) -> Self {
+        if !item_is_valid(project, item) {
+            return Self {
+                guid: String::new(),
+                track: None,
+                index: None,
+            };
+        }
+        let index = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_media_item_info_value(item.raw(), ItemAttributeKey::custom("IP_ITEMNUMBER"))
+        };
+        Self {
+            guid: item.guid().to_string_with_braces(),
+            track: item.track().as_ref().map(|t| t.into()),
+            index: Some(index as u32),
+        }
+    }
+
+    fn removed(guid: Guid) -> Self {
+        Self {
+            guid: guid.to_string_with_braces(),
+            track: None,
+            index: None,
+        }
+    }
+}
+
+impl TakeRecord {
+    fn new(project: Project, item: Item, take: &Take) -> Self {
+        let is_valid = item_is_valid(project, item)
+            && Reaper::get()
+                .medium_reaper()
+                .validate_ptr_2(Proj(project.raw()), take.raw());
+        if !is_valid {
+            return Self {
+                item: ItemRecord::new(project, item),
+                guid: String::new(),
+                index: None,
+            };
+        }
+        let index = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_media_item_take_info_value(
+                    take.raw(),
+                    TakeAttributeKey::custom("IP_TAKENUMBER"),
+                )
+        };
+        Self {
+            item: ItemRecord::new(project, item),
+            guid: take.guid().to_string_with_braces(),
+            index: Some(index as u32),
+        }
+    }
+}
+
+impl From<&MarkerInfo> for MarkerRecord {
+    fn from(marker: &MarkerInfo) -> Self {
+        Self {
+            is_region: marker.bookmark_type == BookmarkType::Region,
+            id: marker.id.get(),
+            position: marker.position.get(),
+            region_end_position: marker.region_end_position.map(|p| p.get()),
+            name: marker.name.clone(),
+            color: marker.color.to_raw(),
+        }
+    }
+}
+
+impl From<&Envelope> for EnvelopeRecord {
+    fn from(envelope: &Envelope) -> Self {
+        let track = envelope.track();
+        let index = if track.is_available() {
+            track
+                .envelopes()
+                .position(|e| e.raw() == envelope.raw())
+                .map(|i| i as u32)
+        } else {
+            None
+        };
+        Self {
+            track: track.into(),
+            index,
+            name: index.map(|_| envelope.name()),
+        }
+    }
+}
+
+impl From<EnvelopeRef> for EnvelopeRecord {
+    fn from(envelope: EnvelopeRef) -> Self {
+        match envelope.to_envelope() {
+            Some(e) => (&e).into(),
+            None => Self {
+                track: envelope.track().into(),
+                index: None,
+                name: None,
+            },
+        }
+    }
+}
+
+fn item_is_valid(project: Project, item: Item) -> bool {
+    project.is_available()
+        && Reaper::get()
+            .medium_reaper()
+            .validate_ptr_2(Proj(project.raw()), item.raw())
+}
+
+fn global_automation_override_to_raw(value: Option<GlobalAutomationModeOverride>) -> i32 {
+    match value {
+        None => -1,
+        Some(GlobalAutomationModeOverride::Bypass) => 6,
+        Some(GlobalAutomationModeOverride::Mode(mode)) => mode.to_raw(),
+    }
+}
+
+/// Writes change event records as JSON lines, one [`ChangeEventRecord`] per line.
+///
+/// Doesn't need a running REAPER. Use [`ChangeEventRecorder`] to record a live session.
+#[derive(Debug)]
+pub struct ChangeEventWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> ChangeEventWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn write_record(&mut self, record: &ChangeEventRecord) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n")
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Records change events of a live session as JSON lines, see [`ChangeEventWriter`].
+///
+/// As a [`ControlSurfaceMiddleware`], it detects changes on its own by delegating to a
+/// [`ChangeDetectionMiddleware`]. Write errors are logged and don't interrupt the session. If
+/// change detection already happens in another middleware, call
+/// [`handle_change()`](Self::handle_change) for each of its change events instead.
+///
+/// Recorded sessions can be read again with [`ChangeEventReplayer`].
+#[derive(Debug)]
+pub struct ChangeEventRecorder<W: Write> {
+    change_detection: ChangeDetectionMiddleware,
+    writer: RefCell<ChangeEventWriter<W>>,
+}
+
+impl<W: Write> ChangeEventRecorder<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            change_detection: ChangeDetectionMiddleware::new(),
+            writer: RefCell::new(ChangeEventWriter::new(writer)),
+        }
+    }
+
+    /// Captures the initial state, see [`ChangeDetectionMiddleware::reset()`].
+    ///
+    /// Should be called when the control surface is registered.
+    pub fn reset(&self) {
+        self.change_detection
+            .reset(|event| record_change(&self.writer, &event));
+    }
+
+    /// Records the given event.
+    pub fn handle_change(&self, event: &ChangeEvent) -> io::Result<()> {
+        self.writer.borrow_mut().write_record(&event.into())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().into_inner()
+    }
+}
+
+impl<W: Write> ControlSurfaceMiddleware for ChangeEventRecorder<W> {
+    fn run(&mut self) {
+        let writer = &self.writer;
+        self.change_detection
+            .run(&mut |event| record_change(writer, &event));
+    }
+
+    fn handle_event(&self, event: ControlSurfaceEvent) -> bool {
+        self.change_detection
+            .process(&event, |event| record_change(&self.writer, &event))
+    }
+}
+
+fn record_change(writer: &RefCell<ChangeEventWriter<impl Write>>, event: &ChangeEvent) {
+    if let Err(e) = writer.borrow_mut().write_record(&event.into()) {
+        warn!(msg = "Couldn't record change event", %e);
+    }
+}
+
+/// Reads change event records written by [`ChangeEventRecorder`] or [`ChangeEventWriter`].
+///
+/// Doesn't need a running REAPER, so it can be used to test consumers of change events against
+/// recorded sessions. Such consumers must be written against [`ChangeEventRecord`] because
+/// objects such as [`Track`] can't be restored without REAPER. The `reaper-rx` crate can feed
+/// replayed records into the record stream of its `ControlSurfaceRx`.
+#[derive(Debug)]
+pub struct ChangeEventReplayer<R: BufRead> {
+    lines: io::Lines<R>,
+}
+
+impl<R: BufRead> ChangeEventReplayer<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+        }
+    }
+
+    /// Feeds all remaining records into the given handler.
+    ///
+    /// Stops at the first record which can't be read.
+    pub fn replay(self, mut handle_record: impl FnMut(ChangeEventRecord)) -> io::Result<()> {
+        for record in self {
+            handle_record(record?);
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for ChangeEventReplayer<R> {
+    type Item = io::Result<ChangeEventRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(l) => l,
+                Err(e) => return Some(Err(e)),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(serde_json::from_str(&line).map_err(io::Error::from));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(index: u32) -> TrackRecord {
+        TrackRecord {
+            project: ProjectRecord { index: Some(0) },
+            guid: "{C7E9A8E1-0B4C-4B5B-8F2D-2E0E6F9C1A3B}".to_string(),
+            index: Some(index),
+            is_master: false,
+        }
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let records = vec![
+            ChangeEventRecord::TrackVolumeChanged {
+                touched: true,
+                track: track(2),
+                old_value: 1.0,
+                new_value: 0.5,
+            },
+            ChangeEventRecord::FxFocused { fx: None },
+            ChangeEventRecord::TrackVisibilityChanged {
+                track: track(0),
+                old_value: TrackVisibility {
+                    tcp: true,
+                    mcp: true,
+                },
+                new_value: TrackVisibility {
+                    tcp: false,
+                    mcp: true,
+                },
+            },
+            ChangeEventRecord::ItemRemoved {
+                project: ProjectRecord { index: Some(0) },
+                item: ItemRecord {
+                    guid: "{5B1E3A0C-7D2F-4E8A-9C61-0F3B2D4A6E85}".to_string(),
+                    track: None,
+                    index: None,
+                },
+            },
+        ];
+        let mut writer = ChangeEventWriter::new(Vec::new());
+        for r in &records {
+            writer.write_record(r).unwrap();
+        }
+        let output = writer.into_inner();
+        assert_eq!(output.iter().filter(|b| **b == b'\n').count(), 4);
+        let mut replayed = vec![];
+        ChangeEventReplayer::new(output.as_slice())
+            .replay(|r| replayed.push(r))
+            .unwrap();
+        assert_eq!(replayed, records);
+    }
+
+    #[test]
+    fn replay_skips_empty_lines_and_reports_invalid_ones() {
+        let input = "{\"type\":\"FxFocused\",\"fx\":null}\n\n{\"type\":\"Unknown\"}\n";
+        let mut replayer = ChangeEventReplayer::new(input.as_bytes());
+        assert_eq!(
+            replayer.next().unwrap().unwrap(),
+            ChangeEventRecord::FxFocused { fx: None }
+        );
+        assert!(replayer.next().unwrap().is_err());
+        assert!(replayer.next().is_none());
+    }
+}
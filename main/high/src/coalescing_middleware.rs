@@ -0,0 +1,383 @@
+use crate::{
+    ChangeEvent, FxParameterRef, FxParameterValueChangedEvent, MasterPlayRateChangedEvent,
+    MasterTempoChangedEvent, Project, TrackPanChangedEvent, TrackRef, TrackRoutePanChangedEvent,
+    TrackRouteRef, TrackRouteVolumeChangedEvent, TrackVolumeChangedEvent,
+};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// Merges high-frequency change events and optionally rate-limits them.
+///
+/// Sits between [`ChangeDetectionMiddleware`](crate::ChangeDetectionMiddleware) and consumers.
+/// Feed all events into [`process()`](Self::process) and call [`run()`](Self::run) once per
+/// main loop cycle in order to emit them. Events with the same kind and target which arrive
+/// within one cycle are merged into one, keeping the old value of the first and the new value of
+/// the last event. Events are emitted in the order in which they first arrived.
+///
+/// If a rate limit is set for an event kind, events of that kind are emitted at most once per
+/// interval and target. Held-back events are merged with subsequent ones and emitted in a later
+/// cycle, so the latest value never gets lost.
+#[derive(Debug)]
+pub struct CoalescingMiddleware<E: CoalescableEvent = ChangeEvent> {
+    queue: VecDeque<E>,
+    queue_index_by_key: HashMap<CoalescingKey<E>, usize>,
+    rate_limits: HashMap<E::Kind, Duration>,
+    last_emissions: HashMap<CoalescingKey<E>, Instant>,
+}
+
+type CoalescingKey<E> = (
+    <E as CoalescableEvent>::Kind,
+    <E as CoalescableEvent>::Target,
+);
+
+/// An event which can be merged with subsequent events of the same kind and target.
+pub trait CoalescableEvent {
+    type Kind: Copy + Eq + Hash + Debug;
+    type Target: Eq + Hash + Debug;
+
+    /// Returns `None` if this event must not be merged with other events.
+    fn coalescing_key(&self) -> Option<(Self::Kind, Self::Target)>;
+
+    /// Merges the given subsequent event of the same kind and target into this one.
+    fn merge(&mut self, later: Self);
+}
+
+impl<E: CoalescableEvent> Default for CoalescingMiddleware<E> {
+    fn default() -> Self {
+        Self {
+            queue: Default::default(),
+            queue_index_by_key: Default::default(),
+            rate_limits: Default::default(),
+            last_emissions: Default::default(),
+        }
+    }
+}
+
+impl<E: CoalescableEvent> CoalescingMiddleware<E> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Emits events of the given kind at most once per interval and target.
+    pub fn set_rate_limit(&mut self, kind: E::Kind, min_interval: Duration) {
+        self.rate_limits.insert(kind, min_interval);
+    }
+
+    pub fn remove_rate_limit(&mut self, kind: E::Kind) {
+        self.rate_limits.remove(&kind);
+    }
+
+    /// Queues the given event, merging it with a queued event of the same kind and target.
+    pub fn process(&mut self, event: E) {
+        if let Some(key) = event.coalescing_key() {
+            if let Some(&index) = self.queue_index_by_key.get(&key) {
+                self.queue[index].merge(event);
+                return;
+            }
+            self.queue_index_by_key.insert(key, self.queue.len());
+        }
+        self.queue.push_back(event);
+    }
+
+    /// Emits all queued events which are not held back by a rate limit.
+    pub fn run(&mut self, handle_event: impl FnMut(E)) {
+        self.run_at(Instant::now(), handle_event);
+    }
+
+    fn run_at(&mut self, now: Instant, mut handle_event: impl FnMut(E)) {
+        let queue = std::mem::take(&mut self.queue);
+        self.queue_index_by_key.clear();
+        for event in queue {
+            let Some(key) = event.coalescing_key() else {
+                handle_event(event);
+                continue;
+            };
+            let Some(&min_interval) = self.rate_limits.get(&key.0) else {
+                handle_event(event);
+                continue;
+            };
+            let is_held_back = self
+                .last_emissions
+                .get(&key)
+                .map(|last| now.duration_since(*last) < min_interval)
+                .unwrap_or(false);
+            if is_held_back {
+                self.queue_index_by_key.insert(key, self.queue.len());
+                self.queue.push_back(event);
+            } else {
+                self.last_emissions.insert(key, now);
+                handle_event(event);
+            }
+        }
+        // Forget emissions which can't hold back anything anymore
+        let rate_limits = &self.rate_limits;
+        self.last_emissions.retain(|(kind, _), last| {
+            rate_limits
+                .get(kind)
+                .map(|min_interval| now.duration_since(*last) < *min_interval)
+                .unwrap_or(false)
+        });
+    }
+}
+
+/// Kinds of change events which can be coalesced.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum CoalescableChangeEventKind {
+    TrackVolumeChanged,
+    TrackPanChanged,
+    TrackRouteVolumeChanged,
+    TrackRoutePanChanged,
+    FxParameterValueChanged,
+    MasterTempoChanged,
+    MasterPlayRateChanged,
+}
+
+/// Target of a change event which can be coalesced.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ChangeEventTarget {
+    Track(TrackRef),
+    TrackRoute(TrackRouteRef),
+    FxParameter(FxParameterRef),
+    Project(Project),
+}
+
+impl CoalescableEvent for ChangeEvent {
+    type Kind = CoalescableChangeEventKind;
+    type Target = ChangeEventTarget;
+
+    fn coalescing_key(&self) -> Option<(Self::Kind, Self::Target)> {
+        use ChangeEventTarget as T;
+        use CoalescableChangeEventKind as K;
+        let key = match self {
+            ChangeEvent::TrackVolumeChanged(e) => (K::TrackVolumeChanged, T::Track(e.track)),
+            ChangeEvent::TrackPanChanged(e) => (K::TrackPanChanged, T::Track(e.track)),
+            ChangeEvent::TrackRouteVolumeChanged(e) => {
+                (K::TrackRouteVolumeChanged, T::TrackRoute(e.route))
+            }
+            ChangeEvent::TrackRoutePanChanged(e) => {
+                (K::TrackRoutePanChanged, T::TrackRoute(e.route))
+            }
+            ChangeEvent::FxParameterValueChanged(e) => {
+                (K::FxParameterValueChanged, T::FxParameter(e.parameter))
+            }
+            ChangeEvent::MasterTempoChanged(e) => (K::MasterTempoChanged, T::Project(e.project)),
+            ChangeEvent::MasterPlayRateChanged(e) => {
+                (K::MasterPlayRateChanged, T::Project(e.project))
+            }
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    fn merge(&mut self, later: Self) {
+        use ChangeEvent as E;
+        match (self, later) {
+            (E::TrackVolumeChanged(e), E::TrackVolumeChanged(later)) => {
+                *e = TrackVolumeChangedEvent {
+                    touched: e.touched || later.touched,
+                    old_value: e.old_value,
+                    ..later
+                };
+            }
+            (E::TrackPanChanged(e), E::TrackPanChanged(later)) => {
+                *e = TrackPanChangedEvent {
+                    touched: e.touched || later.touched,
+                    old_value: e.old_value,
+                    ..later
+                };
+            }
+            (E::TrackRouteVolumeChanged(e), E::TrackRouteVolumeChanged(later)) => {
+                *e = TrackRouteVolumeChangedEvent {
+                    touched: e.touched || later.touched,
+                    old_value: e.old_value,
+                    ..later
+                };
+            }
+            (E::TrackRoutePanChanged(e), E::TrackRoutePanChanged(later)) => {
+                *e = TrackRoutePanChangedEvent {
+                    touched: e.touched || later.touched,
+                    old_value: e.old_value,
+                    ..later
+                };
+            }
+            (E::FxParameterValueChanged(e), E::FxParameterValueChanged(later)) => {
+                *e = FxParameterValueChangedEvent {
+                    touched: e.touched || later.touched,
+                    ..later
+                };
+            }
+            (E::MasterTempoChanged(e), E::MasterTempoChanged(later)) => {
+                *e = MasterTempoChangedEvent {
+                    touched: e.touched || later.touched,
+                    ..later
+                };
+            }
+            (E::MasterPlayRateChanged(e), E::MasterPlayRateChanged(later)) => {
+                *e = MasterPlayRateChangedEvent {
+                    touched: e.touched || later.touched,
+                    ..later
+                };
+            }
+            // Different keys are never merged
+            (e, later) => *e = later,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reaper_medium::{MediaTrack, ReaProject, ReaperVolumeValue, TrackSendDirection};
+    use std::ptr::NonNull;
+
+    #[derive(Clone, PartialEq, Debug)]
+    enum TestEvent {
+        Value { target: u32, old: u32, new: u32 },
+        Other(u32),
+    }
+
+    impl CoalescableEvent for TestEvent {
+        type Kind = ();
+        type Target = u32;
+
+        fn coalescing_key(&self) -> Option<((), u32)> {
+            match self {
+                TestEvent::Value { target, .. } => Some(((), *target)),
+                TestEvent::Other(_) => None,
+            }
+        }
+
+        fn merge(&mut self, later: Self) {
+            if let (TestEvent::Value { new, .. }, TestEvent::Value { new: later_new, .. }) =
+                (self, later)
+            {
+                *new = later_new;
+            }
+        }
+    }
+
+    fn value(target: u32, old: u32, new: u32) -> TestEvent {
+        TestEvent::Value { target, old, new }
+    }
+
+    fn run_at(middleware: &mut CoalescingMiddleware<TestEvent>, now: Instant) -> Vec<TestEvent> {
+        let mut events = vec![];
+        middleware.run_at(now, |e| events.push(e));
+        events
+    }
+
+    #[test]
+    fn merges_events_per_target_within_one_cycle() {
+        let mut middleware = CoalescingMiddleware::new();
+        middleware.process(value(1, 0, 1));
+        middleware.process(TestEvent::Other(5));
+        middleware.process(value(2, 0, 7));
+        middleware.process(value(1, 1, 2));
+        middleware.process(value(1, 2, 3));
+        middleware.process(TestEvent::Other(5));
+        let now = Instant::now();
+        assert_eq!(
+            run_at(&mut middleware, now),
+            vec![
+                value(1, 0, 3),
+                TestEvent::Other(5),
+                value(2, 0, 7),
+                TestEvent::Other(5)
+            ]
+        );
+        assert!(run_at(&mut middleware, now).is_empty());
+        // Next cycle starts from scratch
+        middleware.process(value(1, 3, 4));
+        assert_eq!(run_at(&mut middleware, now), vec![value(1, 3, 4)]);
+    }
+
+    #[test]
+    fn rate_limit_holds_back_and_merges_events() {
+        let mut middleware = CoalescingMiddleware::new();
+        middleware.set_rate_limit((), Duration::from_millis(100));
+        let start = Instant::now();
+        middleware.process(value(1, 0, 1));
+        assert_eq!(run_at(&mut middleware, start), vec![value(1, 0, 1)]);
+        // Within interval: held back and merged
+        middleware.process(value(1, 1, 2));
+        middleware.process(value(2, 0, 1));
+        let t1 = start + Duration::from_millis(50);
+        assert_eq!(run_at(&mut middleware, t1), vec![value(2, 0, 1)]);
+        middleware.process(value(1, 2, 3));
+        assert!(run_at(&mut middleware, t1).is_empty());
+        // Interval elapsed: latest value is emitted
+        let t2 = start + Duration::from_millis(100);
+        assert_eq!(run_at(&mut middleware, t2), vec![value(1, 1, 3)]);
+        assert!(run_at(&mut middleware, t2).is_empty());
+        // Without rate limit, events are emitted immediately again
+        middleware.remove_rate_limit(());
+        middleware.process(value(1, 3, 4));
+        assert_eq!(run_at(&mut middleware, t2), vec![value(1, 3, 4)]);
+    }
+
+    fn track() -> TrackRef {
+        TrackRef::new(
+            MediaTrack::new(NonNull::dangling().as_ptr()).unwrap(),
+            Some(ReaProject::new(NonNull::dangling().as_ptr()).unwrap()),
+        )
+    }
+
+    fn volume(value: f64) -> ReaperVolumeValue {
+        ReaperVolumeValue::new_panic(value)
+    }
+
+    #[test]
+    fn merges_track_volume_changes() {
+        let mut event = ChangeEvent::TrackVolumeChanged(TrackVolumeChangedEvent {
+            touched: true,
+            track: track(),
+            old_value: volume(1.0),
+            new_value: volume(0.5),
+        });
+        event.merge(ChangeEvent::TrackVolumeChanged(TrackVolumeChangedEvent {
+            touched: false,
+            track: track(),
+            old_value: volume(0.5),
+            new_value: volume(0.25),
+        }));
+        let ChangeEvent::TrackVolumeChanged(e) = event else {
+            panic!("unexpected event kind");
+        };
+        assert!(e.touched);
+        assert_eq!(e.old_value.get(), 1.0);
+        assert_eq!(e.new_value.get(), 0.25);
+    }
+
+    #[test]
+    fn merges_track_route_volume_changes() {
+        let route = TrackRouteRef::new(track(), TrackSendDirection::Send, 0);
+        let mut middleware = CoalescingMiddleware::<ChangeEvent>::new();
+        middleware.process(ChangeEvent::TrackRouteVolumeChanged(
+            TrackRouteVolumeChangedEvent {
+                touched: false,
+                route,
+                old_value: None,
+                new_value: volume(0.5),
+            },
+        ));
+        middleware.process(ChangeEvent::TrackRouteVolumeChanged(
+            TrackRouteVolumeChangedEvent {
+                touched: true,
+                route,
+                old_value: Some(volume(0.5)),
+                new_value: volume(0.25),
+            },
+        ));
+        let mut events = vec![];
+        middleware.run_at(Instant::now(), |e| events.push(e));
+        let [ChangeEvent::TrackRouteVolumeChanged(e)] = events.as_slice() else {
+            panic!("unexpected events {events:?}");
+        };
+        assert!(e.touched);
+        assert_eq!(e.route, route);
+        assert_eq!(e.old_value.map(|v| v.get()), None);
+        assert_eq!(e.new_value.get(), 0.25);
+    }
+}
@@ -0,0 +1,340 @@
+use crate::error::ReaperResult;
+use crate::{Reaper, Track};
+use reaper_medium::{reaper_str, ChunkCacheHint, PositionInSeconds, ReaperStr, TrackEnvelope};
+
+/// Envelope chunks of envelopes with lots of points can get big.
+const MAX_ENVELOPE_CHUNK_SIZE: u32 = 10_000_000;
+/// Enough for the boolean info strings, e.g. `ACTIVE`.
+const MAX_ENVELOPE_INFO_STRING_SIZE: u32 = 16;
+
+/// An envelope of a track, e.g. volume, pan or an FX parameter envelope.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Envelope {
+    track: Track,
+    raw: TrackEnvelope,
+}
+
+impl Envelope {
+    pub fn new(track: Track, raw: TrackEnvelope) -> Self {
+        Self { track, raw }
+    }
+
+    pub fn track(&self) -> &Track {
+        &self.track
+    }
+
+    pub fn raw(&self) -> TrackEnvelope {
+        self.raw
+    }
+
+    /// Returns the display name, e.g. "Volume".
+    pub fn name(&self) -> String {
+        let name = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_envelope_name(self.raw, 256)
+        };
+        name.into_string()
+    }
+
+    pub fn is_active(&self) -> bool {
+        get_bool_info(self.raw, reaper_str!("ACTIVE"))
+    }
+
+    pub fn is_visible(&self) -> bool {
+        get_bool_info(self.raw, reaper_str!("VISIBLE"))
+    }
+
+    pub fn is_armed(&self) -> bool {
+        get_bool_info(self.raw, reaper_str!("ARM"))
+    }
+
+    /// Returns the points of this envelope, ordered by position.
+    ///
+    /// Doesn't contain the points of automation items.
+    pub fn points(&self) -> Vec<EnvelopePoint> {
+        get_points(self.raw)
+    }
+
+    /// Returns the RPPXML state chunk of this envelope.
+    ///
+    /// This allocates a big buffer, so prefer [`state()`](Self::state) if you don't need the
+    /// complete chunk.
+    pub fn state_chunk(&self) -> ReaperResult<String> {
+        let chunk = unsafe {
+            Reaper::get().medium_reaper().get_envelope_state_chunk(
+                self.raw,
+                MAX_ENVELOPE_CHUNK_SIZE,
+                ChunkCacheHint::UndoMode,
+            )?
+        };
+        Ok(chunk.into_string())
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> EnvelopeState {
+        get_envelope_state(self.raw)
+    }
+}
+
+/// Reads the state via the envelope API, which is much cheaper than parsing the state chunk.
+pub(crate) fn get_envelope_state(raw: TrackEnvelope) -> EnvelopeState {
+    EnvelopeState {
+        is_active: get_bool_info(raw, reaper_str!("ACTIVE")),
+        is_visible: get_bool_info(raw, reaper_str!("VISIBLE")),
+        is_armed: get_bool_info(raw, reaper_str!("ARM")),
+        points: get_points(raw),
+    }
+}
+
+fn get_points(raw: TrackEnvelope) -> Vec<EnvelopePoint> {
+    let reaper = Reaper::get().medium_reaper();
+    let count = unsafe { reaper.count_envelope_points(raw) };
+    (0..count)
+        .filter_map(|i| {
+            let point = unsafe { reaper.get_envelope_point_ex(raw, None, i) }?;
+            let point = EnvelopePoint {
+                position: point.position,
+                value: point.value,
+                shape: point.shape,
+                tension: point.tension,
+            };
+            Some(point)
+        })
+        .collect()
+}
+
+fn get_bool_info(raw: TrackEnvelope, key: &ReaperStr) -> bool {
+    let value = unsafe {
+        Reaper::get()
+            .medium_reaper()
+            .get_set_envelope_info_string_get(raw, key, MAX_ENVELOPE_INFO_STRING_SIZE)
+    };
+    value.map(|v| v.to_str().trim() == "1").unwrap_or(false)
+}
+
+/// State of an envelope at a certain point in time.
+///
+/// Doesn't contain the points of automation items.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct EnvelopeState {
+    pub is_active: bool,
+    pub is_visible: bool,
+    pub is_armed: bool,
+    pub points: Vec<EnvelopePoint>,
+}
+
+/// A point of an envelope as stored in the state chunk.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EnvelopePoint {
+    pub position: PositionInSeconds,
+    pub value: f64,
+    pub shape: u32,
+    pub tension: f64,
+}
+
+/// A change of an envelope.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EnvelopeChange {
+    /// Points have been added, removed or changed within the given time range (inclusive).
+    PointsChanged {
+        start: PositionInSeconds,
+        end: PositionInSeconds,
+    },
+    ActiveChanged(bool),
+    VisibilityChanged(bool),
+    ArmChanged(bool),
+}
+
+impl EnvelopeState {
+    /// Parses the relevant parts of the given envelope state chunk.
+    ///
+    /// Ignores nested chunks and lines that can't be parsed.
+    pub fn from_chunk(chunk: &str) -> Self {
+        let mut state = EnvelopeState::default();
+        let mut depth = 0;
+        for line in chunk.lines().map(str::trim) {
+            if line.starts_with('<') {
+                depth += 1;
+                continue;
+            }
+            if line == ">" {
+                depth -= 1;
+                continue;
+            }
+            if depth != 1 {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let first_number = |f: &mut std::str::SplitWhitespace| {
+                f.next().and_then(|v| v.parse::<i32>().ok()).unwrap_or(0)
+            };
+            match fields.next() {
+                Some("ACT") => state.is_active = first_number(&mut fields) != 0,
+                Some("VIS") => state.is_visible = first_number(&mut fields) != 0,
+                Some("ARM") => state.is_armed = first_number(&mut fields) != 0,
+                Some("PT") => {
+                    if let Some(point) = parse_point(fields) {
+                        state.points.push(point);
+                    }
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+
+    /// Returns the changes which lead from this state to the given newer one.
+    pub fn changes_to(&self, newer: &EnvelopeState) -> Vec<EnvelopeChange> {
+        let mut changes = vec![];
+        if let Some((start, end)) = affected_range(&self.points, &newer.points) {
+            changes.push(EnvelopeChange::PointsChanged { start, end });
+        }
+        if self.is_active != newer.is_active {
+            changes.push(EnvelopeChange::ActiveChanged(newer.is_active));
+        }
+        if self.is_visible != newer.is_visible {
+            changes.push(EnvelopeChange::VisibilityChanged(newer.is_visible));
+        }
+        if self.is_armed != newer.is_armed {
+            changes.push(EnvelopeChange::ArmChanged(newer.is_armed));
+        }
+        changes
+    }
+}
+
+/// Line format: `PT position value [shape] [time signature] [selected] [tension]`
+fn parse_point<'a>(mut fields: impl Iterator<Item = &'a str>) -> Option<EnvelopePoint> {
+    let position = fields.next()?.parse().ok()?;
+    let value = fields.next()?.parse().ok()?;
+    let shape = fields.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+    let tension = fields.nth(2).and_then(|v| v.parse().ok()).unwrap_or(0.0);
+    let point = EnvelopePoint {
+        position: PositionInSeconds::new(position).ok()?,
+        value,
+        shape,
+        tension,
+    };
+    Some(point)
+}
+
+/// Returns the time range which contains all differing points of the given point lists.
+///
+/// Both lists must be sorted by position.
+fn affected_range(
+    old: &[EnvelopePoint],
+    new: &[EnvelopePoint],
+) -> Option<(PositionInSeconds, PositionInSeconds)> {
+    let common_prefix_len = old.iter().zip(new).take_while(|(o, n)| o == n).count();
+    if common_prefix_len == old.len() && common_prefix_len == new.len() {
+        return None;
+    }
+    let max_suffix_len = old.len().min(new.len()) - common_prefix_len;
+    let common_suffix_len = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix_len)
+        .take_while(|(o, n)| o == n)
+        .count();
+    let old_diff = &old[common_prefix_len..old.len() - common_suffix_len];
+    let new_diff = &new[common_prefix_len..new.len() - common_suffix_len];
+    let mut positions = old_diff.iter().chain(new_diff).map(|p| p.position);
+    let first = positions.next()?;
+    let range = positions.fold((first, first), |(start, end), pos| {
+        (start.min(pos), end.max(pos))
+    });
+    Some(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(value: f64) -> PositionInSeconds {
+        PositionInSeconds::new_panic(value)
+    }
+
+    fn point(position: f64, value: f64) -> EnvelopePoint {
+        EnvelopePoint {
+            position: pos(position),
+            value,
+            shape: 0,
+            tension: 0.0,
+        }
+    }
+
+    #[test]
+    fn from_chunk() {
+        let chunk = "<VOLENV2
+EGUID {A4E6E6F4-4F0A-4D6B-9C3E-2C8C0D1E4E6B}
+ACT 1 -1
+VIS 1 1 1
+LANEHEIGHT 0 0
+ARM 0
+DEFSHAPE 0 -1 -1
+VOLTYPE 1
+PT 0 1 0
+PT 2.5 0.5 5 0 1 -0.25
+<POOLEDENVINST
+PT 1 0 0
+>
+>";
+        let state = EnvelopeState::from_chunk(chunk);
+        assert_eq!(
+            state,
+            EnvelopeState {
+                is_active: true,
+                is_visible: true,
+                is_armed: false,
+                points: vec![
+                    point(0.0, 1.0),
+                    EnvelopePoint {
+                        position: pos(2.5),
+                        value: 0.5,
+                        shape: 5,
+                        tension: -0.25
+                    }
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn changes_to() {
+        let old = EnvelopeState {
+            is_active: true,
+            is_visible: true,
+            is_armed: false,
+            points: vec![point(0.0, 1.0), point(1.0, 0.5), point(4.0, 0.0)],
+        };
+        assert!(old.changes_to(&old).is_empty());
+        let new = EnvelopeState {
+            is_armed: true,
+            points: vec![point(0.0, 1.0), point(2.0, 0.5), point(4.0, 0.0)],
+            ..old.clone()
+        };
+        assert_eq!(
+            old.changes_to(&new),
+            vec![
+                EnvelopeChange::PointsChanged {
+                    start: pos(1.0),
+                    end: pos(2.0)
+                },
+                EnvelopeChange::ArmChanged(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn affected_range_of_added_and_removed_points() {
+        let old = vec![point(0.0, 1.0), point(4.0, 0.0)];
+        let new = vec![point(0.0, 1.0), point(2.0, 0.5), point(4.0, 0.0)];
+        assert_eq!(affected_range(&old, &new), Some((pos(2.0), pos(2.0))));
+        assert_eq!(affected_range(&new, &old), Some((pos(2.0), pos(2.0))));
+        assert_eq!(affected_range(&[], &new), Some((pos(0.0), pos(4.0))));
+        // Same point duplicated: only one of them counts as added
+        let dup = vec![point(0.0, 1.0), point(0.0, 1.0), point(4.0, 0.0)];
+        assert_eq!(affected_range(&old, &dup), Some((pos(0.0), pos(0.0))));
+    }
+}
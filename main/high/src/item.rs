@@ -1,4 +1,4 @@
-use crate::{Project, Reaper, Take, Track};
+use crate::{Guid, Project, Reaper, Take, Track};
 use reaper_medium::{
     BeatAttachMode, DurationInSeconds, FadeCurvature, FadeShape, ItemAttributeKey, ItemGroupId,
     MediaItem, NativeColorValue, PositionInSeconds, ProjectContext, ReaperFunctionError,
@@ -95,6 +95,15 @@ impl Item {
         }
     }
 
+    pub fn is_selected(&self) -> bool {
+        unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_media_item_info_value(self.raw, ItemAttributeKey::Selected)
+                != 0.0
+        }
+    }
+
     pub fn set_selected(&self, selected: bool) {
         unsafe {
             Reaper::get()
@@ -103,6 +112,24 @@ impl Item {
         }
     }
 
+    pub fn guid(&self) -> Guid {
+        let raw = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_set_media_item_info_get_guid(self.raw)
+        };
+        Guid::new(raw)
+    }
+
+    pub fn is_muted(&self) -> bool {
+        unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_media_item_info_value(self.raw, ItemAttributeKey::Mute)
+                != 0.0
+        }
+    }
+
     pub fn set_mute(&self, mute: bool) -> Result<(), ReaperFunctionError> {
         unsafe {
             Reaper::get().medium_reaper.set_media_item_info_value(
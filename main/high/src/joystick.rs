@@ -0,0 +1,164 @@
+use crate::error::ReaperResult;
+use crate::{Guid, Reaper};
+use reaper_medium::{JoystickDeviceInfo, OwnedJoystickDevice};
+
+/// An installed joystick or game controller as returned by [`Reaper::joysticks()`].
+///
+/// [`Reaper::joysticks()`]: crate::Reaper::joysticks
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct JoystickDescriptor {
+    pub guid: Guid,
+    pub name: String,
+}
+
+impl JoystickDescriptor {
+    /// Opens this device.
+    pub fn open(&self) -> ReaperResult<Joystick> {
+        let device = Reaper::get()
+            .medium_reaper()
+            .joystick_create(&self.guid.to_raw())?;
+        let info = device.info();
+        let joystick = Joystick {
+            descriptor: self.clone(),
+            device,
+            info,
+        };
+        Ok(joystick)
+    }
+}
+
+/// An opened joystick or game controller device.
+///
+/// Call [`update()`](Self::update) to read the current state from the hardware before querying
+/// it. The device is closed when dropped.
+#[derive(Debug)]
+pub struct Joystick {
+    descriptor: JoystickDescriptor,
+    device: OwnedJoystickDevice,
+    info: JoystickDeviceInfo,
+}
+
+impl Joystick {
+    pub fn descriptor(&self) -> &JoystickDescriptor {
+        &self.descriptor
+    }
+
+    /// Reads the current state from the hardware.
+    pub fn update(&self) -> ReaperResult<()> {
+        if !self.device.update() {
+            return Err("couldn't read joystick state".into());
+        }
+        Ok(())
+    }
+
+    /// Returns the values of all axes, usually between -1.0 and 1.0.
+    pub fn axes(&self) -> Vec<f64> {
+        (0..self.info.axis_count)
+            .map(|i| self.device.axis(i))
+            .collect()
+    }
+
+    /// Returns for each button whether it's pressed (at most 32 buttons).
+    pub fn buttons(&self) -> Vec<bool> {
+        let mask = self.device.button_mask();
+        (0..self.info.button_count.min(32))
+            .map(|i| mask & (1 << i) != 0)
+            .collect()
+    }
+
+    /// Returns the direction of all POVs (hat switches) in degrees, `None` if centered.
+    pub fn povs(&self) -> Vec<Option<f64>> {
+        (0..self.info.pov_count)
+            .map(|i| self.device.pov(i))
+            .collect()
+    }
+
+    /// Returns the complete current state.
+    pub fn state(&self) -> JoystickState {
+        JoystickState {
+            axes: self.axes(),
+            buttons: self.buttons(),
+            povs: self.povs(),
+        }
+    }
+}
+
+/// State of a joystick at a certain point in time.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct JoystickState {
+    pub axes: Vec<f64>,
+    pub buttons: Vec<bool>,
+    pub povs: Vec<Option<f64>>,
+}
+
+/// A change of a single joystick control.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum JoystickChange {
+    Axis { index: u32, value: f64 },
+    Button { index: u32, is_pressed: bool },
+    Pov { index: u32, value: Option<f64> },
+}
+
+impl JoystickState {
+    /// Returns the changes which lead from this state to the given newer one.
+    ///
+    /// Controls which only exist in the newer state count as changed.
+    pub fn changes_to(&self, newer: &JoystickState) -> Vec<JoystickChange> {
+        let axes = changed(&self.axes, &newer.axes)
+            .map(|(index, value)| JoystickChange::Axis { index, value });
+        let buttons = changed(&self.buttons, &newer.buttons)
+            .map(|(index, is_pressed)| JoystickChange::Button { index, is_pressed });
+        let povs = changed(&self.povs, &newer.povs)
+            .map(|(index, value)| JoystickChange::Pov { index, value });
+        axes.chain(buttons).chain(povs).collect()
+    }
+}
+
+fn changed<'a, T: Copy + PartialEq>(
+    older: &'a [T],
+    newer: &'a [T],
+) -> impl Iterator<Item = (u32, T)> + 'a {
+    newer
+        .iter()
+        .enumerate()
+        .filter(move |(i, v)| older.get(*i) != Some(*v))
+        .map(|(i, v)| (i as u32, *v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_to() {
+        let older = JoystickState {
+            axes: vec![0.0, 0.5],
+            buttons: vec![false, true],
+            povs: vec![None],
+        };
+        let newer = JoystickState {
+            axes: vec![0.0, -0.5],
+            buttons: vec![true, true],
+            povs: vec![Some(90.0)],
+        };
+        assert_eq!(
+            older.changes_to(&newer),
+            vec![
+                JoystickChange::Axis {
+                    index: 1,
+                    value: -0.5
+                },
+                JoystickChange::Button {
+                    index: 0,
+                    is_pressed: true
+                },
+                JoystickChange::Pov {
+                    index: 0,
+                    value: Some(90.0)
+                },
+            ]
+        );
+        assert!(newer.changes_to(&newer).is_empty());
+        assert_eq!(JoystickState::default().changes_to(&older).len(), 5);
+    }
+}
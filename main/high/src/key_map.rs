@@ -0,0 +1,383 @@
+use crate::error::ReaperResult;
+use crate::ReaperError;
+use enumflags2::BitFlags;
+use reaper_medium::{AcceleratorBehavior, AcceleratorKeyCode, CommandId, SectionId};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// All shortcut assignments as stored in the `KEY` lines of `reaper-kb.ini`.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct KeyMap {
+    pub entries: Vec<KeyMapEntry>,
+}
+
+/// A shortcut assigned to an action (one `KEY` line).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct KeyMapEntry {
+    pub trigger: ShortcutTrigger,
+    pub command: KeyMapCommand,
+    pub section_id: SectionId,
+}
+
+/// What triggers a shortcut.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ShortcutTrigger {
+    /// A computer keyboard key.
+    Key {
+        behavior: BitFlags<AcceleratorBehavior>,
+        key_code: AcceleratorKeyCode,
+    },
+    /// A MIDI message, identified by its status byte and first data byte.
+    Midi { status_byte: u8, data_byte_1: u8 },
+    /// Anything else (e.g. mousewheel), preserved as is.
+    Other { modifiers: u32, key: u32 },
+}
+
+/// The action which is triggered by a shortcut.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum KeyMapCommand {
+    /// A built-in action.
+    Id(CommandId),
+    /// A custom action, script or action registered by an extension (without leading
+    /// underscore).
+    Named(String),
+}
+
+/// An error which can occur when loading or saving a key map.
+#[derive(Debug)]
+pub enum KeyMapError {
+    /// The file couldn't be read or written.
+    Io(io::Error),
+    /// The file contains an invalid `KEY` line.
+    InvalidKeyLine(ReaperError),
+}
+
+impl Error for KeyMapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            KeyMapError::Io(e) => Some(e),
+            KeyMapError::InvalidKeyLine(e) => Some(e),
+        }
+    }
+}
+
+impl Display for KeyMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyMapError::Io(e) => write!(f, "couldn't access key map: {e}"),
+            KeyMapError::InvalidKeyLine(e) => write!(f, "invalid key map: {e}"),
+        }
+    }
+}
+
+impl From<KeyMapError> for ReaperError {
+    fn from(e: KeyMapError) -> Self {
+        match e {
+            KeyMapError::Io(_) => Self::new("couldn't access key map"),
+            KeyMapError::InvalidKeyLine(e) => e,
+        }
+    }
+}
+
+impl KeyMap {
+    /// Parses the `KEY` lines of the given `reaper-kb.ini` content, ignoring all other lines.
+    pub fn parse(ini: &str) -> ReaperResult<KeyMap> {
+        let entries = ini
+            .lines()
+            .filter(|line| is_key_line(line))
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
+        Ok(KeyMap { entries })
+    }
+
+    /// Loads the key map from the given `reaper-kb.ini` file.
+    ///
+    /// The file doesn't need to be valid UTF-8 (names of custom actions and scripts are written
+    /// in the system encoding on some systems), only the `KEY` lines are interpreted.
+    pub fn load(path: &Path) -> Result<KeyMap, KeyMapError> {
+        let ini = std::fs::read(path).map_err(KeyMapError::Io)?;
+        Self::parse(&String::from_utf8_lossy(&ini)).map_err(KeyMapError::InvalidKeyLine)
+    }
+
+    /// Returns the given `reaper-kb.ini` content with all `KEY` lines replaced by the entries of
+    /// this key map. All other lines are kept.
+    pub fn merge_into(&self, ini: &str) -> String {
+        String::from_utf8(self.merge_into_bytes(ini.as_bytes()))
+            .expect("merging UTF-8 content should result in UTF-8")
+    }
+
+    /// Like [`merge_into()`](Self::merge_into) but keeps all other lines byte by byte, even if
+    /// they are not valid UTF-8.
+    pub fn merge_into_bytes(&self, ini: &[u8]) -> Vec<u8> {
+        let mut result = Vec::with_capacity(ini.len());
+        for line in ini.split_inclusive(|b| *b == b'\n') {
+            if is_key_line(&String::from_utf8_lossy(line)) {
+                continue;
+            }
+            result.extend_from_slice(line);
+            if !line.ends_with(b"\n") {
+                result.push(b'\n');
+            }
+        }
+        for entry in &self.entries {
+            result.extend_from_slice(entry.to_string().as_bytes());
+            result.push(b'\n');
+        }
+        result
+    }
+
+    /// Replaces the `KEY` lines of the given `reaper-kb.ini` file with the entries of this key map.
+    ///
+    /// Creates the file if it doesn't exist. Fails without touching the file if it exists but
+    /// can't be read. REAPER reads this file at startup only, so the changes take effect after a
+    /// restart.
+    pub fn save(&self, path: &Path) -> Result<(), KeyMapError> {
+        let existing = match std::fs::read(path) {
+            Ok(ini) => ini,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(KeyMapError::Io(e)),
+        };
+        std::fs::write(path, self.merge_into_bytes(&existing)).map_err(KeyMapError::Io)
+    }
+
+    /// Returns all entries which belong to the given section.
+    pub fn entries_in_section(
+        &self,
+        section_id: SectionId,
+    ) -> impl Iterator<Item = &KeyMapEntry> + '_ {
+        self.entries
+            .iter()
+            .filter(move |e| e.section_id == section_id)
+    }
+}
+
+impl ShortcutTrigger {
+    fn from_raw(modifiers: u32, key: u32) -> Self {
+        if modifiers < 0x80 {
+            if let (Ok(behavior), Ok(key_code)) = (
+                BitFlags::<AcceleratorBehavior>::from_bits(modifiers as u8),
+                u16::try_from(key),
+            ) {
+                return Self::Key {
+                    behavior,
+                    key_code: AcceleratorKeyCode::new(key_code),
+                };
+            }
+        } else if modifiers < 0xF0 && key < 0x80 {
+            return Self::Midi {
+                status_byte: modifiers as u8,
+                data_byte_1: key as u8,
+            };
+        }
+        Self::Other { modifiers, key }
+    }
+
+    fn to_raw(self) -> (u32, u32) {
+        use ShortcutTrigger::*;
+        match self {
+            Key { behavior, key_code } => (behavior.bits() as u32, key_code.get() as u32),
+            Midi {
+                status_byte,
+                data_byte_1,
+            } => (status_byte as u32, data_byte_1 as u32),
+            Other { modifiers, key } => (modifiers, key),
+        }
+    }
+}
+
+impl Display for KeyMapCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyMapCommand::Id(id) => write!(f, "{}", id.get()),
+            KeyMapCommand::Named(name) => write!(f, "_{name}"),
+        }
+    }
+}
+
+impl FromStr for KeyMapCommand {
+    type Err = ReaperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(name) = s.strip_prefix('_') {
+            return Ok(KeyMapCommand::Named(name.to_string()));
+        }
+        // Custom actions without underscore are stored as 32-digit hash
+        if s.len() == 32 {
+            return Ok(KeyMapCommand::Named(s.to_string()));
+        }
+        let id: u32 = s
+            .parse()
+            .map_err(|_| ReaperError::new("invalid command in key map"))?;
+        let id =
+            CommandId::try_from(id).map_err(|_| ReaperError::new("command ID must not be 0"))?;
+        Ok(KeyMapCommand::Id(id))
+    }
+}
+
+impl Display for KeyMapEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (modifiers, key) = self.trigger.to_raw();
+        write!(
+            f,
+            "KEY {modifiers} {key} {} {}",
+            self.command,
+            self.section_id.get()
+        )
+    }
+}
+
+impl FromStr for KeyMapEntry {
+    type Err = ReaperError;
+
+    /// Parses a `KEY` line. Trailing comments are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        if tokens.next() != Some(KEY_LINE_PREFIX) {
+            return Err(ReaperError::new("not a KEY line"));
+        }
+        let mut next_number = || -> Result<u32, ReaperError> {
+            tokens
+                .next()
+                .and_then(|t| t.parse().ok())
+                .ok_or(ReaperError::new("invalid number in KEY line"))
+        };
+        let modifiers = next_number()?;
+        let key = next_number()?;
+        let command = tokens
+            .next()
+            .ok_or(ReaperError::new("KEY line without command"))?
+            .parse()?;
+        let section_id = tokens
+            .next()
+            .and_then(|t| t.parse().ok())
+            .ok_or(ReaperError::new("KEY line without valid section"))?;
+        let entry = KeyMapEntry {
+            trigger: ShortcutTrigger::from_raw(modifiers, key),
+            command,
+            section_id: SectionId::new(section_id),
+        };
+        Ok(entry)
+    }
+}
+
+fn is_key_line(line: &str) -> bool {
+    line.split_whitespace().next() == Some(KEY_LINE_PREFIX)
+}
+
+const KEY_LINE_PREFIX: &str = "KEY";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_key_lines() {
+        let ini = "ACT 0 0 \"abc\" \"Custom: Test\" 40044\n\
+                   KEY 13 65 40044 0\t\t # Main : Ctrl+Shift+A\n\
+                   KEY 144 60 _SWS_ABOUT 0\n\
+                   KEY 255 6 40001 32060\n\
+                   SCR 4 0 RS123 \"Script\" test.lua\n";
+        let key_map = KeyMap::parse(ini).unwrap();
+        assert_eq!(
+            key_map.entries,
+            vec![
+                KeyMapEntry {
+                    trigger: ShortcutTrigger::Key {
+                        behavior: AcceleratorBehavior::VirtKey
+                            | AcceleratorBehavior::Shift
+                            | AcceleratorBehavior::Control,
+                        key_code: AcceleratorKeyCode::new(65),
+                    },
+                    command: KeyMapCommand::Id(CommandId::new(40044)),
+                    section_id: SectionId::new(0),
+                },
+                KeyMapEntry {
+                    trigger: ShortcutTrigger::Midi {
+                        status_byte: 144,
+                        data_byte_1: 60
+                    },
+                    command: KeyMapCommand::Named("SWS_ABOUT".to_string()),
+                    section_id: SectionId::new(0),
+                },
+                KeyMapEntry {
+                    trigger: ShortcutTrigger::Other {
+                        modifiers: 255,
+                        key: 6
+                    },
+                    command: KeyMapCommand::Id(CommandId::new(40001)),
+                    section_id: SectionId::new(32060),
+                },
+            ]
+        );
+        assert!(KeyMap::parse("KEY 1 65").is_err());
+        assert!(KeyMap::parse("KEY 1 65 0 0").is_err());
+    }
+
+    #[test]
+    fn serialize_key_lines() {
+        let ini = "KEY 13 65 40044 0\nKEY 144 60 _SWS_ABOUT 0\nKEY 255 6 40001 32060\n";
+        let key_map = KeyMap::parse(ini).unwrap();
+        let lines: Vec<_> = key_map.entries.iter().map(|e| e.to_string()).collect();
+        assert_eq!(lines, ini.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn merge() {
+        let existing = "ACT 0 0 \"abc\" \"Custom: Test\" 40044\nKEY 1 65 40044 0\n";
+        let key_map = KeyMap {
+            entries: vec![KeyMapEntry {
+                trigger: ShortcutTrigger::Key {
+                    behavior: AcceleratorBehavior::VirtKey.into(),
+                    key_code: AcceleratorKeyCode::new(66),
+                },
+                command: KeyMapCommand::Named("abc".to_string()),
+                section_id: SectionId::new(0),
+            }],
+        };
+        assert_eq!(
+            key_map.merge_into(existing),
+            "ACT 0 0 \"abc\" \"Custom: Test\" 40044\nKEY 1 66 _abc 0\n"
+        );
+    }
+
+    #[test]
+    fn merge_keeps_non_utf8_lines() {
+        let existing =
+            b"ACT 0 0 \"abc\" \"Custom: Caf\xe9\" 40044\r\nKEY 1 65 40044 0\r\nSCR 4 0 RS1";
+        let key_map = KeyMap::default();
+        assert_eq!(
+            key_map.merge_into_bytes(existing),
+            b"ACT 0 0 \"abc\" \"Custom: Caf\xe9\" 40044\r\nSCR 4 0 RS1\n"
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dir = std::env::temp_dir().join(format!("reaper-rs-key-map-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("reaper-kb.ini");
+        let _ = std::fs::remove_file(&path);
+        let key_map = KeyMap::parse("KEY 1 65 40044 0\n").unwrap();
+        // Missing file
+        key_map.save(&path).unwrap();
+        assert_eq!(KeyMap::load(&path).unwrap(), key_map);
+        // Existing file which is not valid UTF-8
+        std::fs::write(
+            &path,
+            b"ACT 0 0 \"abc\" \"Caf\xe9\" 40044\nKEY 1 66 40001 0\n",
+        )
+        .unwrap();
+        key_map.save(&path).unwrap();
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            b"ACT 0 0 \"abc\" \"Caf\xe9\" 40044\nKEY 1 65 40044 0\n"
+        );
+        // Unreadable file (a directory) is not overwritten
+        assert!(matches!(key_map.save(&dir), Err(KeyMapError::Io(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
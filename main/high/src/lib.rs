@@ -45,16 +45,32 @@ pub use reaper_simple::*;
 
 mod project;
 pub use project::*;
+mod project_snapshot;
+pub use project_snapshot::*;
+mod project_diff;
+pub use project_diff::*;
 
 mod track;
 pub use track::*;
 
+mod track_ref;
+pub use track_ref::*;
+
 mod take;
 pub use take::*;
 
 mod track_route;
 pub use track_route::*;
 
+mod track_meter;
+pub use track_meter::*;
+
+mod track_lane;
+pub use track_lane::*;
+
+mod thing_at_point;
+pub use thing_at_point::*;
+
 mod fx;
 pub use fx::*;
 
@@ -67,6 +83,27 @@ pub use section::*;
 mod action;
 pub use action::*;
 
+mod action_interceptor;
+pub use action_interceptor::*;
+
+mod key_map;
+pub use key_map::*;
+
+mod macro_action;
+pub use macro_action::*;
+
+mod menu;
+pub use menu::*;
+
+mod joystick;
+pub use joystick::*;
+
+mod marker_diff;
+pub use marker_diff::*;
+
+mod envelope;
+pub use envelope::*;
+
 mod guid;
 pub use guid::*;
 
@@ -119,6 +156,17 @@ pub use middleware_control_surface::*;
 mod change_detection_middleware;
 pub use change_detection_middleware::*;
 
+#[cfg(feature = "serde")]
+mod change_event_record;
+#[cfg(feature = "serde")]
+pub use change_event_record::*;
+
+mod coalescing_middleware;
+pub use coalescing_middleware::*;
+
+mod touch_manager;
+pub use touch_manager::*;
+
 mod bookmark;
 pub use bookmark::*;
 
@@ -0,0 +1,494 @@
+use crate::error::ReaperResult;
+use crate::{Action, GroupingBehavior, Reaper, ReaperError, Track};
+use reaper_medium::{
+    CommandId, Db, GangBehavior, MasterTrackBehavior, ReaperNormalizedFxParamValue, ReaperPanValue,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A composite action built from other actions, see [`Reaper::register_macro_action()`].
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MacroDefinition {
+    /// Description shown in the action list.
+    pub description: String,
+    pub steps: Vec<MacroStep>,
+}
+
+/// A single step of a macro.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MacroStep {
+    /// Invokes a main section action as trigger.
+    InvokeAction {
+        /// Command ID of a built-in action (e.g. `40044`) or command name of a custom action,
+        /// script or action registered by an extension (e.g. `_SWS_ABOUT`).
+        command_name: String,
+    },
+    /// Waits the given number of milliseconds before executing the next step.
+    Wait { millis: u64 },
+    /// Executes one of the given step lists depending on the toggle state of an action.
+    ///
+    /// Actions which don't report on/off states are treated as off.
+    IfToggleState {
+        command_name: String,
+        is_on: bool,
+        then_steps: Vec<MacroStep>,
+        else_steps: Vec<MacroStep>,
+    },
+    /// Changes a track property.
+    SetTrackProperty {
+        track: MacroTrack,
+        property: MacroTrackProperty,
+    },
+    /// Sets an FX parameter of a track's normal FX chain to the given REAPER-normalized value.
+    SetFxParameter {
+        track: MacroTrack,
+        fx_index: u32,
+        param_index: u32,
+        value: f64,
+    },
+}
+
+/// The track which a macro step refers to, resolved in the current project.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MacroTrack {
+    Master,
+    FirstSelected,
+    ByIndex(u32),
+}
+
+/// A track property which can be changed by a macro step.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MacroTrackProperty {
+    Mute(bool),
+    Solo(bool),
+    Arm(bool),
+    Selected(bool),
+    VolumeDb(f64),
+    /// Pan between -1.0 (left) and 1.0 (right).
+    Pan(f64),
+}
+
+impl MacroDefinition {
+    /// Executes all steps of this macro in order.
+    ///
+    /// Must be polled in the main thread. Stops at the first failing step.
+    pub async fn execute(&self) -> ReaperResult<()> {
+        execute_steps(&self.steps).await
+    }
+
+    /// Loads the macro definition that has been saved for the given command name.
+    ///
+    /// Returns `None` if there's no such definition.
+    pub fn load(command_name: &str) -> ReaperResult<Option<MacroDefinition>> {
+        let reaper = Reaper::get().medium_reaper();
+        let Some(steps) =
+            reaper.get_ext_state(EXT_STATE_SECTION, command_name, |s| s.to_str().to_owned())
+        else {
+            return Ok(None);
+        };
+        let description = reaper
+            .get_ext_state(EXT_STATE_SECTION, description_key(command_name), |s| {
+                s.to_str().to_owned()
+            })
+            .unwrap_or_default();
+        let definition = MacroDefinition {
+            description,
+            steps: parse_steps(&steps)?,
+        };
+        Ok(Some(definition))
+    }
+
+    /// Saves this macro definition persistently (in `reaper-extstate.ini`).
+    pub fn save(&self, command_name: &str) {
+        let reaper = Reaper::get().medium_reaper();
+        reaper.set_ext_state(
+            EXT_STATE_SECTION,
+            command_name,
+            format_steps(&self.steps),
+            true,
+        );
+        reaper.set_ext_state(
+            EXT_STATE_SECTION,
+            description_key(command_name),
+            self.description.as_str(),
+            true,
+        );
+    }
+
+    /// Removes the macro definition saved for the given command name.
+    pub fn delete(command_name: &str) {
+        let reaper = Reaper::get().medium_reaper();
+        reaper.delete_ext_state(EXT_STATE_SECTION, command_name, true);
+        reaper.delete_ext_state(EXT_STATE_SECTION, description_key(command_name), true);
+    }
+}
+
+impl MacroTrack {
+    fn resolve(self) -> ReaperResult<Track> {
+        let project = Reaper::get().current_project();
+        match self {
+            MacroTrack::Master => project.master_track(),
+            MacroTrack::FirstSelected => project
+                .first_selected_track(MasterTrackBehavior::IncludeMasterTrack)
+                .ok_or(ReaperError::new("no track selected")),
+            MacroTrack::ByIndex(index) => project
+                .track_by_index(index)
+                .ok_or(ReaperError::new("track doesn't exist")),
+        }
+    }
+}
+
+fn execute_steps(steps: &[MacroStep]) -> Pin<Box<dyn Future<Output = ReaperResult<()>> + '_>> {
+    // Boxed because of recursion (conditional steps)
+    Box::pin(async move {
+        for step in steps {
+            execute_step(step).await?;
+        }
+        Ok(())
+    })
+}
+
+async fn execute_step(step: &MacroStep) -> ReaperResult<()> {
+    use MacroStep::*;
+    match step {
+        InvokeAction { command_name } => {
+            resolve_action(command_name).invoke_as_trigger(None, None)?;
+        }
+        Wait { millis } => {
+            futures_timer::Delay::new(Duration::from_millis(*millis)).await;
+        }
+        IfToggleState {
+            command_name,
+            is_on,
+            then_steps,
+            else_steps,
+        } => {
+            let actual_is_on = resolve_action(command_name).is_on()?.unwrap_or(false);
+            let steps = if actual_is_on == *is_on {
+                then_steps
+            } else {
+                else_steps
+            };
+            execute_steps(steps).await?;
+        }
+        SetTrackProperty { track, property } => {
+            set_track_property(&track.resolve()?, *property)?;
+        }
+        SetFxParameter {
+            track,
+            fx_index,
+            param_index,
+            value,
+        } => {
+            let fx = track
+                .resolve()?
+                .normal_fx_chain()
+                .fx_by_index(*fx_index)
+                .ok_or(ReaperError::new("FX doesn't exist"))?;
+            fx.parameter_by_index(*param_index)
+                .set_reaper_normalized_value(ReaperNormalizedFxParamValue::new(*value))?;
+        }
+    }
+    Ok(())
+}
+
+fn set_track_property(track: &Track, property: MacroTrackProperty) -> ReaperResult<()> {
+    use MacroTrackProperty::*;
+    let gang = GangBehavior::DenyGang;
+    let grouping = GroupingBehavior::PreventGrouping;
+    match property {
+        Mute(on) => track.set_mute(on, gang, grouping),
+        Solo(true) => track.solo(gang, grouping),
+        Solo(false) => track.unsolo(gang, grouping),
+        Arm(true) => track.arm(true, gang, grouping),
+        Arm(false) => track.disarm(true, gang, grouping),
+        Selected(true) => track.select(),
+        Selected(false) => track.unselect(),
+        VolumeDb(db) => {
+            let db = Db::new(db).map_err(|_| ReaperError::new("invalid dB value"))?;
+            track.set_volume_smart(db.to_linear_volume_value(), Default::default())?;
+        }
+        Pan(pan) => {
+            let pan =
+                ReaperPanValue::new(pan).map_err(|_| ReaperError::new("invalid pan value"))?;
+            track.set_pan_smart(pan, Default::default())?;
+        }
+    }
+    Ok(())
+}
+
+fn resolve_action(command_name: &str) -> Action {
+    let reaper = Reaper::get();
+    match command_name.parse::<u32>() {
+        Ok(id) if id > 0 => reaper
+            .main_section()
+            .action_by_command_id(CommandId::new(id)),
+        _ => reaper.action_by_command_name(command_name),
+    }
+}
+
+fn description_key(command_name: &str) -> String {
+    format!("{command_name}.description")
+}
+
+const EXT_STATE_SECTION: &str = "reaper-rs.macros";
+
+// The textual format is a single line (so it can be stored in ExtState) in which steps are
+// separated by semicolons, e.g. `action 40044; wait 500; if 40001 on; action 1007; else; end`.
+
+/// Formats the given steps in the textual macro format.
+pub fn format_steps(steps: &[MacroStep]) -> String {
+    let mut tokens = Vec::new();
+    push_step_tokens(steps, &mut tokens);
+    tokens.join("; ")
+}
+
+/// Parses steps from the textual macro format.
+pub fn parse_steps(text: &str) -> ReaperResult<Vec<MacroStep>> {
+    let mut lines = text.split(';').map(str::trim).filter(|l| !l.is_empty());
+    let (steps, terminator) = parse_block(&mut lines)?;
+    if terminator.is_some() {
+        return Err(ReaperError::new("unexpected else or end in macro"));
+    }
+    Ok(steps)
+}
+
+fn push_step_tokens(steps: &[MacroStep], tokens: &mut Vec<String>) {
+    for step in steps {
+        match step {
+            MacroStep::IfToggleState {
+                command_name,
+                is_on,
+                then_steps,
+                else_steps,
+            } => {
+                tokens.push(format!("if {command_name} {}", format_on_off(*is_on)));
+                push_step_tokens(then_steps, tokens);
+                tokens.push("else".to_string());
+                push_step_tokens(else_steps, tokens);
+                tokens.push("end".to_string());
+            }
+            _ => tokens.push(step.to_string()),
+        }
+    }
+}
+
+/// Parses steps until `else`, `end` or the end of the input and returns the terminator.
+fn parse_block<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+) -> ReaperResult<(Vec<MacroStep>, Option<&'a str>)> {
+    let mut steps = Vec::new();
+    while let Some(line) = lines.next() {
+        if line == "else" || line == "end" {
+            return Ok((steps, Some(line)));
+        }
+        let mut words = line.split_whitespace();
+        if words.next() == Some("if") {
+            let command_name = words.next().ok_or(ReaperError::new("if without action"))?;
+            let is_on = parse_on_off(words.next())?;
+            let (then_steps, terminator) = parse_block(lines)?;
+            let else_steps = match terminator {
+                Some("else") => match parse_block(lines)? {
+                    (steps, Some("end")) => steps,
+                    _ => return Err(ReaperError::new("if without end")),
+                },
+                Some("end") => Vec::new(),
+                _ => return Err(ReaperError::new("if without end")),
+            };
+            steps.push(MacroStep::IfToggleState {
+                command_name: command_name.to_string(),
+                is_on,
+                then_steps,
+                else_steps,
+            });
+        } else {
+            steps.push(line.parse()?);
+        }
+    }
+    Ok((steps, None))
+}
+
+impl Display for MacroStep {
+    /// Formats simple steps. Conditional steps are formatted by [`format_steps()`].
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        use MacroStep::*;
+        match self {
+            InvokeAction { command_name } => write!(f, "action {command_name}"),
+            Wait { millis } => write!(f, "wait {millis}"),
+            IfToggleState { .. } => f.write_str(&format_steps(std::slice::from_ref(self))),
+            SetTrackProperty { track, property } => {
+                use MacroTrackProperty::*;
+                write!(f, "track {track} ")?;
+                match property {
+                    Mute(on) => write!(f, "mute {}", format_on_off(*on)),
+                    Solo(on) => write!(f, "solo {}", format_on_off(*on)),
+                    Arm(on) => write!(f, "arm {}", format_on_off(*on)),
+                    Selected(on) => write!(f, "select {}", format_on_off(*on)),
+                    VolumeDb(db) => write!(f, "volume {db}"),
+                    Pan(pan) => write!(f, "pan {pan}"),
+                }
+            }
+            SetFxParameter {
+                track,
+                fx_index,
+                param_index,
+                value,
+            } => write!(f, "fx {track} {fx_index} {param_index} {value}"),
+        }
+    }
+}
+
+impl FromStr for MacroStep {
+    type Err = ReaperError;
+
+    /// Parses simple steps. Conditional steps are parsed by [`parse_steps()`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let keyword = words.next().ok_or(ReaperError::new("empty macro step"))?;
+        let mut next = || {
+            words
+                .next()
+                .ok_or(ReaperError::new("incomplete macro step"))
+        };
+        let step = match keyword {
+            "action" => MacroStep::InvokeAction {
+                command_name: next()?.to_string(),
+            },
+            "wait" => MacroStep::Wait {
+                millis: parse_number(next()?)?,
+            },
+            "track" => {
+                use MacroTrackProperty::*;
+                let track = next()?.parse()?;
+                let property = match next()? {
+                    "mute" => Mute(parse_on_off(Some(next()?))?),
+                    "solo" => Solo(parse_on_off(Some(next()?))?),
+                    "arm" => Arm(parse_on_off(Some(next()?))?),
+                    "select" => Selected(parse_on_off(Some(next()?))?),
+                    "volume" => VolumeDb(parse_number(next()?)?),
+                    "pan" => Pan(parse_number(next()?)?),
+                    _ => return Err(ReaperError::new("unknown track property in macro")),
+                };
+                MacroStep::SetTrackProperty { track, property }
+            }
+            "fx" => MacroStep::SetFxParameter {
+                track: next()?.parse()?,
+                fx_index: parse_number(next()?)?,
+                param_index: parse_number(next()?)?,
+                value: parse_number(next()?)?,
+            },
+            _ => return Err(ReaperError::new("unknown macro step")),
+        };
+        Ok(step)
+    }
+}
+
+impl Display for MacroTrack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MacroTrack::Master => f.write_str("master"),
+            MacroTrack::FirstSelected => f.write_str("selected"),
+            MacroTrack::ByIndex(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+impl FromStr for MacroTrack {
+    type Err = ReaperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let track = match s {
+            "master" => MacroTrack::Master,
+            "selected" => MacroTrack::FirstSelected,
+            _ => MacroTrack::ByIndex(parse_number(s)?),
+        };
+        Ok(track)
+    }
+}
+
+fn format_on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn parse_on_off(word: Option<&str>) -> ReaperResult<bool> {
+    match word {
+        Some("on") => Ok(true),
+        Some("off") => Ok(false),
+        _ => Err(ReaperError::new("expected on or off in macro")),
+    }
+}
+
+fn parse_number<T: FromStr>(word: &str) -> ReaperResult<T> {
+    word.parse()
+        .map_err(|_| ReaperError::new("invalid number in macro"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_and_parse_steps() {
+        let steps = vec![
+            MacroStep::InvokeAction {
+                command_name: "40044".to_string(),
+            },
+            MacroStep::Wait { millis: 500 },
+            MacroStep::IfToggleState {
+                command_name: "_SWS_TOGGLE".to_string(),
+                is_on: true,
+                then_steps: vec![MacroStep::SetTrackProperty {
+                    track: MacroTrack::FirstSelected,
+                    property: MacroTrackProperty::Mute(true),
+                }],
+                else_steps: vec![MacroStep::IfToggleState {
+                    command_name: "1007".to_string(),
+                    is_on: false,
+                    then_steps: vec![],
+                    else_steps: vec![MacroStep::SetTrackProperty {
+                        track: MacroTrack::Master,
+                        property: MacroTrackProperty::VolumeDb(-6.5),
+                    }],
+                }],
+            },
+            MacroStep::SetFxParameter {
+                track: MacroTrack::ByIndex(2),
+                fx_index: 0,
+                param_index: 3,
+                value: 0.25,
+            },
+        ];
+        let text = format_steps(&steps);
+        assert_eq!(
+            text,
+            "action 40044; wait 500; if _SWS_TOGGLE on; track selected mute on; else; \
+             if 1007 off; else; track master volume -6.5; end; end; fx 2 0 3 0.25"
+        );
+        assert_eq!(parse_steps(&text).unwrap(), steps);
+    }
+
+    #[test]
+    fn parse_invalid_steps() {
+        assert!(parse_steps("").unwrap().is_empty());
+        assert!(parse_steps("if 1007 on; action 1").is_err());
+        assert!(parse_steps("if 1007 maybe; end").is_err());
+        assert!(parse_steps("action 1; end").is_err());
+        assert!(parse_steps("wait soon").is_err());
+        assert!(parse_steps("track 1 color red").is_err());
+        assert!(parse_steps("jump 1").is_err());
+    }
+}
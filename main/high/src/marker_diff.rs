@@ -0,0 +1,223 @@
+use crate::{BookmarkType, Project, Reaper};
+use reaper_medium::{
+    BookmarkId, Bpm, GetTempoTimeSigMarkerResult, NativeColor, PositionInSeconds, TimeSignature,
+};
+use std::collections::HashMap;
+
+/// A marker or region at a certain point in time.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MarkerInfo {
+    pub bookmark_type: BookmarkType,
+    /// Markers and regions have separate ID spaces.
+    pub id: BookmarkId,
+    pub position: PositionInSeconds,
+    pub region_end_position: Option<PositionInSeconds>,
+    pub name: String,
+    pub color: NativeColor,
+}
+
+/// A tempo/time signature marker at a certain point in time.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TempoMarkerInfo {
+    pub position: PositionInSeconds,
+    pub tempo: Bpm,
+    pub time_signature: Option<TimeSignature>,
+    pub is_linear: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MarkerChange {
+    Added(MarkerInfo),
+    Removed(MarkerInfo),
+    Changed { old: MarkerInfo, new: MarkerInfo },
+}
+
+/// Returns the changes which lead from the old markers and regions to the new ones.
+///
+/// Markers and regions are matched by type and ID. Removals are returned first, followed by
+/// additions and changes in the order of the new markers.
+pub fn diff_markers(old: &[MarkerInfo], new: &[MarkerInfo]) -> Vec<MarkerChange> {
+    let old_by_key: HashMap<_, _> = old.iter().map(|m| (marker_key(m), m)).collect();
+    let new_by_key: HashMap<_, _> = new.iter().map(|m| (marker_key(m), m)).collect();
+    let removed = old
+        .iter()
+        .filter(|m| !new_by_key.contains_key(&marker_key(m)))
+        .map(|m| MarkerChange::Removed(m.clone()));
+    let added_or_changed = new
+        .iter()
+        .filter_map(|m| match old_by_key.get(&marker_key(m)) {
+            None => Some(MarkerChange::Added(m.clone())),
+            Some(&old) if old != m => Some(MarkerChange::Changed {
+                old: old.clone(),
+                new: m.clone(),
+            }),
+            Some(_) => None,
+        });
+    removed.chain(added_or_changed).collect()
+}
+
+/// Returns the earliest position from which on the tempo map has changed or `None` if it hasn't
+/// changed at all.
+///
+/// Both tempo maps must be sorted by position.
+pub fn first_affected_tempo_position(
+    old: &[TempoMarkerInfo],
+    new: &[TempoMarkerInfo],
+) -> Option<PositionInSeconds> {
+    let first_difference = old.iter().zip(new).position(|(o, n)| o != n);
+    match first_difference {
+        Some(i) => {
+            let (old_pos, new_pos) = (old[i].position, new[i].position);
+            Some(if old_pos <= new_pos { old_pos } else { new_pos })
+        }
+        None => {
+            // Common part is equal. Changed if one of them has additional markers.
+            let common_len = old.len().min(new.len());
+            old.get(common_len)
+                .or_else(|| new.get(common_len))
+                .map(|m| m.position)
+        }
+    }
+}
+
+pub(crate) fn read_markers(project: Project) -> Vec<MarkerInfo> {
+    let reaper = Reaper::get().medium_reaper();
+    (0..)
+        .map_while(|i| {
+            reaper.enum_project_markers_3(project.context(), i, |res| {
+                let res = res?;
+                let info = MarkerInfo {
+                    bookmark_type: if res.region_end_position.is_some() {
+                        BookmarkType::Region
+                    } else {
+                        BookmarkType::Marker
+                    },
+                    id: res.id,
+                    position: res.position,
+                    region_end_position: res.region_end_position,
+                    name: res.name.to_str().to_owned(),
+                    color: res.color,
+                };
+                Some(info)
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn read_tempo_markers(project: Project) -> Vec<TempoMarkerInfo> {
+    let reaper = Reaper::get().medium_reaper();
+    (0..)
+        .map_while(|i| reaper.get_tempo_time_sig_marker(project.context(), i))
+        .map(|res: GetTempoTimeSigMarkerResult| TempoMarkerInfo {
+            position: res.position,
+            tempo: res.tempo,
+            time_signature: res.time_signature,
+            is_linear: res.is_linear,
+        })
+        .collect()
+}
+
+fn marker_key(marker: &MarkerInfo) -> (bool, BookmarkId) {
+    (marker.bookmark_type == BookmarkType::Region, marker.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(id: u32, pos: f64, name: &str) -> MarkerInfo {
+        MarkerInfo {
+            bookmark_type: BookmarkType::Marker,
+            id: BookmarkId::new(id),
+            position: PositionInSeconds::new_panic(pos),
+            region_end_position: None,
+            name: name.to_string(),
+            color: NativeColor::default(),
+        }
+    }
+
+    fn region(id: u32, pos: f64, end: f64) -> MarkerInfo {
+        MarkerInfo {
+            bookmark_type: BookmarkType::Region,
+            region_end_position: Some(PositionInSeconds::new_panic(end)),
+            ..marker(id, pos, "")
+        }
+    }
+
+    fn tempo(pos: f64, bpm: f64) -> TempoMarkerInfo {
+        TempoMarkerInfo {
+            position: PositionInSeconds::new_panic(pos),
+            tempo: Bpm::new_panic(bpm),
+            time_signature: None,
+            is_linear: false,
+        }
+    }
+
+    #[test]
+    fn diff_markers_detects_changes() {
+        let old = vec![
+            marker(1, 0.0, "Verse"),
+            marker(2, 10.0, "Chorus"),
+            region(1, 0.0, 5.0),
+        ];
+        let new = vec![
+            marker(1, 0.0, "Verse"),
+            region(1, 0.0, 6.0),
+            marker(3, 20.0, "Bridge"),
+        ];
+        assert_eq!(
+            diff_markers(&old, &new),
+            vec![
+                MarkerChange::Removed(marker(2, 10.0, "Chorus")),
+                MarkerChange::Changed {
+                    old: region(1, 0.0, 5.0),
+                    new: region(1, 0.0, 6.0)
+                },
+                MarkerChange::Added(marker(3, 20.0, "Bridge")),
+            ]
+        );
+        assert!(diff_markers(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn marker_and_region_with_same_id_are_different() {
+        let old = vec![marker(1, 0.0, "")];
+        let new = vec![region(1, 0.0, 5.0)];
+        assert_eq!(
+            diff_markers(&old, &new),
+            vec![
+                MarkerChange::Removed(marker(1, 0.0, "")),
+                MarkerChange::Added(region(1, 0.0, 5.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn first_affected_tempo_position_finds_earliest_change() {
+        let old = vec![tempo(0.0, 120.0), tempo(10.0, 90.0), tempo(20.0, 100.0)];
+        assert_eq!(first_affected_tempo_position(&old, &old), None);
+        // Changed tempo
+        let new = vec![tempo(0.0, 120.0), tempo(10.0, 95.0), tempo(20.0, 100.0)];
+        assert_eq!(
+            first_affected_tempo_position(&old, &new),
+            Some(PositionInSeconds::new_panic(10.0))
+        );
+        // Moved marker
+        let new = vec![tempo(0.0, 120.0), tempo(8.0, 90.0), tempo(20.0, 100.0)];
+        assert_eq!(
+            first_affected_tempo_position(&old, &new),
+            Some(PositionInSeconds::new_panic(8.0))
+        );
+        // Removed marker
+        let new = vec![tempo(0.0, 120.0), tempo(10.0, 90.0)];
+        assert_eq!(
+            first_affected_tempo_position(&old, &new),
+            Some(PositionInSeconds::new_panic(20.0))
+        );
+        // Added marker
+        assert_eq!(
+            first_affected_tempo_position(&new, &old),
+            Some(PositionInSeconds::new_panic(20.0))
+        );
+    }
+}
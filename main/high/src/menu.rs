@@ -0,0 +1,370 @@
+use crate::error::ReaperResult;
+use crate::Reaper;
+use camino::Utf8Path;
+use reaper_low::{raw, Swell};
+use reaper_medium::{
+    CommandId, CommandItem, Hmenu, MenuOrToolbarItem, PositionDescriptor, SectionId, SubMenuStart,
+    UiRefreshBehavior,
+};
+use std::ffi::CString;
+
+/// A menu consisting of actions, separators and submenus.
+///
+/// Can be registered in the "Extensions" main menu or as customizable menu (see
+/// [`Reaper::register_menu()`]) or added to a toolbar. Use [`MenuBuilder`] to create one.
+///
+/// [`Reaper::register_menu()`]: crate::Reaper::register_menu
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Menu {
+    pub items: Vec<MenuItem>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum MenuItem {
+    Action(MenuAction),
+    Separator,
+    SubMenu(SubMenu),
+}
+
+/// A menu entry which invokes an action of the main section.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MenuAction {
+    pub label: String,
+    pub command_id: CommandId,
+    /// Whether to show a checkmark while the action is on.
+    pub shows_toggle_state: bool,
+    /// Name of the icon file (e.g. `toolbar_add.png`), only relevant for toolbars.
+    pub icon_file_name: Option<String>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SubMenu {
+    pub label: String,
+    pub menu: Menu,
+}
+
+/// Where a menu registered via [`Reaper::register_menu()`] shows up.
+///
+/// [`Reaper::register_menu()`]: crate::Reaper::register_menu
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum MenuLocation {
+    /// Appended to the "Extensions" main menu.
+    Extensions,
+    /// A menu which the user can customize in REAPER's "Customize menus/toolbars" dialog and
+    /// which can be shown via [`Reaper::show_popup_menu()`] using its ID.
+    ///
+    /// [`Reaper::show_popup_menu()`]: crate::Reaper::show_popup_menu
+    Customizable {
+        /// Unique ID, e.g. `MyExtension context`.
+        id: String,
+        /// Name shown in the customization dialog and in the menu bar.
+        name: String,
+        add_to_main_menu: bool,
+    },
+}
+
+impl MenuLocation {
+    /// The ID which REAPER passes to the custom menu hook when initializing this menu.
+    pub(crate) fn menu_id(&self) -> &str {
+        match self {
+            MenuLocation::Extensions => "Main extensions",
+            MenuLocation::Customizable { id, .. } => id,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MenuBuilder {
+    items: Vec<MenuItem>,
+}
+
+impl MenuBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds an action.
+    pub fn action(self, label: impl Into<String>, command_id: CommandId) -> Self {
+        self.item(MenuAction::new(label, command_id))
+    }
+
+    /// Adds an action which shows a checkmark while it's on.
+    pub fn toggle_action(self, label: impl Into<String>, command_id: CommandId) -> Self {
+        self.item(MenuAction::new(label, command_id).with_toggle_state())
+    }
+
+    pub fn separator(self) -> Self {
+        self.item(MenuItem::Separator)
+    }
+
+    /// Adds a submenu whose items are added by the given function.
+    pub fn sub_menu(
+        self,
+        label: impl Into<String>,
+        build: impl FnOnce(MenuBuilder) -> MenuBuilder,
+    ) -> Self {
+        let sub_menu = SubMenu {
+            label: label.into(),
+            menu: build(MenuBuilder::new()).build(),
+        };
+        self.item(MenuItem::SubMenu(sub_menu))
+    }
+
+    pub fn item(mut self, item: impl Into<MenuItem>) -> Self {
+        self.items.push(item.into());
+        self
+    }
+
+    pub fn build(self) -> Menu {
+        Menu { items: self.items }
+    }
+}
+
+impl MenuAction {
+    pub fn new(label: impl Into<String>, command_id: CommandId) -> Self {
+        Self {
+            label: label.into(),
+            command_id,
+            shows_toggle_state: false,
+            icon_file_name: None,
+        }
+    }
+
+    pub fn with_toggle_state(mut self) -> Self {
+        self.shows_toggle_state = true;
+        self
+    }
+
+    pub fn with_icon(mut self, icon_file_name: impl Into<String>) -> Self {
+        self.icon_file_name = Some(icon_file_name.into());
+        self
+    }
+}
+
+impl From<MenuAction> for MenuItem {
+    fn from(value: MenuAction) -> Self {
+        MenuItem::Action(value)
+    }
+}
+
+impl Menu {
+    /// Returns the items in the flat representation used by REAPER's menu and toolbar
+    /// customization, with submenus enclosed in start and end markers.
+    pub fn to_menu_or_toolbar_items(&self) -> Vec<MenuOrToolbarItem<&str, &str>> {
+        let mut result = Vec::new();
+        self.collect_menu_or_toolbar_items(&mut result);
+        result
+    }
+
+    /// Returns the IDs of all actions (including those in submenus) which show their toggle state.
+    pub fn toggle_command_ids(&self) -> Vec<CommandId> {
+        let mut result = Vec::new();
+        self.collect_toggle_command_ids(&mut result);
+        result
+    }
+
+    /// Appends the items of this menu to the given native menu.
+    ///
+    /// Checkmarks are not set here, see [`Self::update_checkmarks()`].
+    pub fn populate(&self, menu: Hmenu) {
+        let swell = Swell::get();
+        for item in &self.items {
+            let mut info = raw::MENUITEMINFO {
+                fMask: raw::MIIM_TYPE,
+                ..Default::default()
+            };
+            // Must stay alive until the item is inserted
+            let label = match item {
+                MenuItem::Action(a) => {
+                    info.fMask |= raw::MIIM_ID | raw::MIIM_DATA;
+                    info.fType = raw::MF_STRING;
+                    info.wID = a.command_id.get();
+                    Some(menu_label(&a.label))
+                }
+                MenuItem::Separator => {
+                    info.fType = raw::MF_SEPARATOR;
+                    None
+                }
+                MenuItem::SubMenu(s) => {
+                    let sub_menu = swell.CreatePopupMenu();
+                    if let Some(sub_menu) = Hmenu::new(sub_menu) {
+                        s.menu.populate(sub_menu);
+                    }
+                    info.fMask |= raw::MIIM_SUBMENU | raw::MIIM_DATA;
+                    info.fType = raw::MF_STRING;
+                    info.hSubMenu = sub_menu;
+                    Some(menu_label(&s.label))
+                }
+            };
+            if let Some(label) = &label {
+                info.dwTypeData = label.as_ptr() as *mut _;
+                info.cch = label.as_bytes().len() as _;
+            }
+            unsafe {
+                let count = swell.GetMenuItemCount(menu.as_ptr());
+                swell.InsertMenuItem(menu.as_ptr(), count, 1, &mut info as *mut _);
+            }
+        }
+    }
+
+    /// Sets the checkmarks of the given native menu, previously populated with this menu,
+    /// according to the current toggle states.
+    pub fn update_checkmarks(&self, menu: Hmenu) {
+        let medium = Reaper::get().medium_reaper();
+        let swell = Swell::get();
+        for command_id in self.toggle_command_ids() {
+            let is_on = medium
+                .get_toggle_command_state_ex(SectionId::MAIN, command_id)
+                .unwrap_or(false);
+            let state = if is_on {
+                raw::MF_CHECKED
+            } else {
+                raw::MF_UNCHECKED
+            };
+            unsafe {
+                swell.CheckMenuItem(
+                    menu.as_ptr(),
+                    command_id.get() as _,
+                    (raw::MF_BYCOMMAND | state) as _,
+                );
+            }
+        }
+    }
+
+    /// Appends the items of this menu to the given toolbar (e.g. "Main toolbar" or
+    /// "Floating toolbar 1"), including icons.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this menu contains submenus (toolbars can't show them) or if an item
+    /// couldn't be added.
+    pub fn add_to_toolbar(&self, toolbar_name: &str) -> ReaperResult<()> {
+        let items = self.to_menu_or_toolbar_items();
+        if items
+            .iter()
+            .any(|item| matches!(item, MenuOrToolbarItem::SubMenuStart(_)))
+        {
+            return Err("toolbars can't contain submenus".into());
+        }
+        let medium = Reaper::get().medium_reaper();
+        for item in items {
+            match item {
+                MenuOrToolbarItem::Separator => {
+                    medium.add_custom_menu_or_toolbar_item_separator(
+                        toolbar_name,
+                        PositionDescriptor::Append,
+                        UiRefreshBehavior::NoRefresh,
+                    )?;
+                }
+                MenuOrToolbarItem::Command(c) => {
+                    medium.add_custom_menu_or_toolbar_item_command(
+                        toolbar_name,
+                        PositionDescriptor::Append,
+                        c.command_id,
+                        c.toolbar_flags,
+                        c.label,
+                        c.icon_file_name.map(Utf8Path::new),
+                        UiRefreshBehavior::NoRefresh,
+                    )?;
+                }
+                MenuOrToolbarItem::SubMenuStart(_) | MenuOrToolbarItem::SubMenuEnd => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_menu_or_toolbar_items<'a>(
+        &'a self,
+        result: &mut Vec<MenuOrToolbarItem<&'a str, &'a str>>,
+    ) {
+        for item in &self.items {
+            match item {
+                MenuItem::Action(a) => {
+                    let item = CommandItem {
+                        command_id: a.command_id,
+                        toolbar_flags: 0,
+                        label: a.label.as_str(),
+                        icon_file_name: a.icon_file_name.as_deref(),
+                    };
+                    result.push(MenuOrToolbarItem::Command(item));
+                }
+                MenuItem::Separator => result.push(MenuOrToolbarItem::Separator),
+                MenuItem::SubMenu(s) => {
+                    let start = SubMenuStart {
+                        label: s.label.as_str(),
+                    };
+                    result.push(MenuOrToolbarItem::SubMenuStart(start));
+                    s.menu.collect_menu_or_toolbar_items(result);
+                    result.push(MenuOrToolbarItem::SubMenuEnd);
+                }
+            }
+        }
+    }
+
+    fn collect_toggle_command_ids(&self, result: &mut Vec<CommandId>) {
+        for item in &self.items {
+            match item {
+                MenuItem::Action(a) if a.shows_toggle_state => result.push(a.command_id),
+                MenuItem::SubMenu(s) => s.menu.collect_toggle_command_ids(result),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn menu_label(label: &str) -> CString {
+    CString::new(label.replace('\0', "")).expect("no nul bytes left")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu() -> Menu {
+        MenuBuilder::new()
+            .action("Play", CommandId::new(1007))
+            .toggle_action("Repeat", CommandId::new(1068))
+            .separator()
+            .sub_menu("More", |m| {
+                m.toggle_action("Metronome", CommandId::new(40364)).item(
+                    MenuAction::new("Add track", CommandId::new(40001))
+                        .with_icon("toolbar_add.png"),
+                )
+            })
+            .build()
+    }
+
+    #[test]
+    fn flatten() {
+        use MenuOrToolbarItem::*;
+        let command = |id: u32, label, icon_file_name| {
+            Command(CommandItem {
+                command_id: CommandId::new(id),
+                toolbar_flags: 0,
+                label,
+                icon_file_name,
+            })
+        };
+        assert_eq!(
+            menu().to_menu_or_toolbar_items(),
+            vec![
+                command(1007, "Play", None),
+                command(1068, "Repeat", None),
+                Separator,
+                SubMenuStart(super::SubMenuStart { label: "More" }),
+                command(40364, "Metronome", None),
+                command(40001, "Add track", Some("toolbar_add.png")),
+                SubMenuEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn toggle_command_ids() {
+        assert_eq!(
+            menu().toggle_command_ids(),
+            vec![CommandId::new(1068), CommandId::new(40364)]
+        );
+        assert!(Menu::default().toggle_command_ids().is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::guid::Guid;
+use crate::project_snapshot::take_project_snapshot;
 use crate::{
-    BasicBookmarkInfo, BookmarkType, IndexBasedBookmark, Item, PlayRate, Reaper, ReaperResult,
-    Tempo, Track,
+    BasicBookmarkInfo, BookmarkType, IndexBasedBookmark, Item, PlayRate, ProjectSnapshot, Reaper,
+    ReaperResult, SnapshotOptions, Tempo, Track,
 };
 use std::fmt::Debug;
 use std::{iter, mem};
@@ -12,7 +13,7 @@ use reaper_medium::ProjectContext::{CurrentProject, Proj};
 use reaper_medium::{
     AutoSeekBehavior, BookmarkId, BookmarkRef, CountProjectMarkersResult, DurationInSeconds,
     GetLastMarkerAndCurRegionResult, GetLoopTimeRange2Result, MasterTrackBehavior, PanMode,
-    PlayState, PositionInSeconds, ProjectContext, ProjectRef, ReaProject, ReaperString,
+    PlayState, PositionInSeconds, ProjectContext, ProjectRef, RazorEdit, ReaProject, ReaperString,
     ReaperStringArg, SetEditCurPosOptions, TimeMap2TimeToBeatsResult, TimeMode, TimeModeOverride,
     TimeRangeType, TimeSignature, TrackDefaultsBehavior, TrackLocation, UndoBehavior,
 };
@@ -127,6 +128,16 @@ impl Project {
         Either::Right(iter)
     }
 
+    /// Returns the razor edit areas of all tracks in this project, including the master track.
+    pub fn razor_edits(self) -> ReaperResult<Vec<(Track, RazorEdit)>> {
+        let mut result = Vec::new();
+        for track in iter::once(self.master_track()?).chain(self.tracks()) {
+            let edits = track.razor_edits()?;
+            result.extend(edits.into_iter().map(|e| (track.clone(), e)));
+        }
+        Ok(result)
+    }
+
     pub fn items(self) -> impl ExactSizeIterator<Item = Item> + DoubleEndedIterator + 'static {
         if self.complain_if_not_available().is_err() {
             return Either::Left(iter::empty());
@@ -279,6 +290,13 @@ impl Project {
         Ok(Track::new(mt, Some(self.rea_project)))
     }
 
+    /// Takes a plain-data snapshot of this project which can be serialized and compared with
+    /// other snapshots.
+    pub fn snapshot(self, options: SnapshotOptions) -> ReaperResult<ProjectSnapshot> {
+        self.complain_if_not_available()?;
+        Ok(take_project_snapshot(self, options))
+    }
+
     pub fn undoable<'a, F, R>(self, label: impl Into<ReaperStringArg<'a>>, operation: F) -> R
     where
         F: FnOnce() -> R,
@@ -321,6 +339,13 @@ impl Project {
             .mark_project_dirty(Proj(self.rea_project));
     }
 
+    /// Recalculates the lane arrangement of all fixed lane tracks in this project.
+    pub fn update_item_lanes(self) {
+        Reaper::get()
+            .medium_reaper()
+            .update_item_lanes(Proj(self.rea_project));
+    }
+
     pub fn is_dirty(self) -> bool {
         Reaper::get()
             .medium_reaper()
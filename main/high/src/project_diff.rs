@@ -0,0 +1,501 @@
+use crate::{FxSnapshot, ItemSnapshot, ProjectSnapshot, SendSnapshot, TrackSnapshot};
+use std::collections::{HashMap, HashSet};
+
+/// A structural change between two [`ProjectSnapshot`]s, as returned by
+/// [`ProjectSnapshot::diff()`].
+///
+/// Objects are referred to by their GUIDs. An item which has been moved to another track shows up
+/// as removed from the old and added to the new track.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum ProjectChange {
+    TrackAdded {
+        /// Index within the tracks of the newer snapshot.
+        index: u32,
+        track: TrackSnapshot,
+    },
+    TrackRemoved {
+        guid: String,
+    },
+    /// The order of tracks which exist in both snapshots has changed.
+    TracksReordered,
+    TrackNameChanged {
+        guid: String,
+        old: String,
+        new: String,
+    },
+    TrackVolumeChanged {
+        guid: String,
+        old: f64,
+        new: f64,
+    },
+    TrackPanChanged {
+        guid: String,
+        old: f64,
+        new: f64,
+    },
+    TrackMuteChanged {
+        guid: String,
+        new: bool,
+    },
+    TrackSoloChanged {
+        guid: String,
+        new: bool,
+    },
+    FxAdded {
+        track_guid: String,
+        /// Index within the FX of the track in the newer snapshot.
+        index: u32,
+        fx: FxSnapshot,
+    },
+    FxRemoved {
+        track_guid: String,
+        fx_guid: String,
+    },
+    /// The order of FX which exist in both snapshots has changed.
+    FxReordered {
+        track_guid: String,
+    },
+    FxEnabledChanged {
+        track_guid: String,
+        fx_guid: String,
+        new: bool,
+    },
+    FxParameterValueChanged {
+        track_guid: String,
+        fx_guid: String,
+        index: u32,
+        old: f64,
+        new: f64,
+    },
+    SendAdded {
+        track_guid: String,
+        send: SendSnapshot,
+    },
+    SendRemoved {
+        track_guid: String,
+        target_track_guid: String,
+    },
+    SendChanged {
+        track_guid: String,
+        old: SendSnapshot,
+        new: SendSnapshot,
+    },
+    ItemAdded {
+        track_guid: String,
+        item: ItemSnapshot,
+    },
+    ItemRemoved {
+        track_guid: String,
+        item_guid: String,
+    },
+    /// The position, length or mute state of the item has changed.
+    ItemChanged {
+        track_guid: String,
+        old: ItemSnapshot,
+        new: ItemSnapshot,
+    },
+}
+
+impl ProjectSnapshot {
+    /// Returns the changes which lead from this snapshot to the given newer one.
+    ///
+    /// Removals come first, then additions, then changes of objects which exist in both
+    /// snapshots.
+    pub fn diff(&self, newer: &ProjectSnapshot) -> Vec<ProjectChange> {
+        let mut changes = vec![];
+        let tracks = diff_by_key(&self.tracks, &newer.tracks, |t| &t.guid);
+        for track in tracks.removed {
+            changes.push(ProjectChange::TrackRemoved {
+                guid: track.guid.clone(),
+            });
+        }
+        for (index, track) in tracks.added {
+            changes.push(ProjectChange::TrackAdded {
+                index,
+                track: track.clone(),
+            });
+        }
+        if tracks.reordered {
+            changes.push(ProjectChange::TracksReordered);
+        }
+        for (old, new) in tracks.common {
+            diff_tracks(old, new, &mut changes);
+        }
+        changes
+    }
+}
+
+fn diff_tracks(old: &TrackSnapshot, new: &TrackSnapshot, changes: &mut Vec<ProjectChange>) {
+    let guid = || new.guid.clone();
+    if old.name != new.name {
+        changes.push(ProjectChange::TrackNameChanged {
+            guid: guid(),
+            old: old.name.clone(),
+            new: new.name.clone(),
+        });
+    }
+    if old.volume != new.volume {
+        changes.push(ProjectChange::TrackVolumeChanged {
+            guid: guid(),
+            old: old.volume,
+            new: new.volume,
+        });
+    }
+    if old.pan != new.pan {
+        changes.push(ProjectChange::TrackPanChanged {
+            guid: guid(),
+            old: old.pan,
+            new: new.pan,
+        });
+    }
+    if old.is_muted != new.is_muted {
+        changes.push(ProjectChange::TrackMuteChanged {
+            guid: guid(),
+            new: new.is_muted,
+        });
+    }
+    if old.is_solo != new.is_solo {
+        changes.push(ProjectChange::TrackSoloChanged {
+            guid: guid(),
+            new: new.is_solo,
+        });
+    }
+    diff_fxs(new, &old.fxs, &new.fxs, changes);
+    let sends = diff_by_key(&old.sends, &new.sends, |s| &s.target_track_guid);
+    for send in sends.removed {
+        changes.push(ProjectChange::SendRemoved {
+            track_guid: guid(),
+            target_track_guid: send.target_track_guid.clone(),
+        });
+    }
+    for (_, send) in sends.added {
+        changes.push(ProjectChange::SendAdded {
+            track_guid: guid(),
+            send: send.clone(),
+        });
+    }
+    for (old_send, new_send) in sends.common {
+        if old_send != new_send {
+            changes.push(ProjectChange::SendChanged {
+                track_guid: guid(),
+                old: old_send.clone(),
+                new: new_send.clone(),
+            });
+        }
+    }
+    let items = diff_by_key(&old.items, &new.items, |i| &i.guid);
+    for item in items.removed {
+        changes.push(ProjectChange::ItemRemoved {
+            track_guid: guid(),
+            item_guid: item.guid.clone(),
+        });
+    }
+    for (_, item) in items.added {
+        changes.push(ProjectChange::ItemAdded {
+            track_guid: guid(),
+            item: item.clone(),
+        });
+    }
+    for (old_item, new_item) in items.common {
+        if old_item != new_item {
+            changes.push(ProjectChange::ItemChanged {
+                track_guid: guid(),
+                old: old_item.clone(),
+                new: new_item.clone(),
+            });
+        }
+    }
+}
+
+fn diff_fxs(
+    track: &TrackSnapshot,
+    old: &[FxSnapshot],
+    new: &[FxSnapshot],
+    changes: &mut Vec<ProjectChange>,
+) {
+    let track_guid = || track.guid.clone();
+    let fxs = diff_by_key(old, new, |fx| &fx.guid);
+    for fx in fxs.removed {
+        changes.push(ProjectChange::FxRemoved {
+            track_guid: track_guid(),
+            fx_guid: fx.guid.clone(),
+        });
+    }
+    for (index, fx) in fxs.added {
+        changes.push(ProjectChange::FxAdded {
+            track_guid: track_guid(),
+            index,
+            fx: fx.clone(),
+        });
+    }
+    if fxs.reordered {
+        changes.push(ProjectChange::FxReordered {
+            track_guid: track_guid(),
+        });
+    }
+    for (old_fx, new_fx) in fxs.common {
+        if old_fx.is_enabled != new_fx.is_enabled {
+            changes.push(ProjectChange::FxEnabledChanged {
+                track_guid: track_guid(),
+                fx_guid: new_fx.guid.clone(),
+                new: new_fx.is_enabled,
+            });
+        }
+        // Parameters which exist in one snapshot only (e.g. because parameters were excluded)
+        // are not compared.
+        let values = old_fx.parameter_values.iter().zip(&new_fx.parameter_values);
+        for (index, (old_value, new_value)) in values.enumerate() {
+            if old_value != new_value {
+                changes.push(ProjectChange::FxParameterValueChanged {
+                    track_guid: track_guid(),
+                    fx_guid: new_fx.guid.clone(),
+                    index: index as u32,
+                    old: *old_value,
+                    new: *new_value,
+                });
+            }
+        }
+    }
+}
+
+struct KeyedDiff<'a, T> {
+    /// Elements only contained in the new list, with their index in the new list.
+    added: Vec<(u32, &'a T)>,
+    /// Elements only contained in the old list.
+    removed: Vec<&'a T>,
+    /// Old and new version of elements contained in both lists, in new order.
+    common: Vec<(&'a T, &'a T)>,
+    /// Whether the common elements appear in a different order.
+    reordered: bool,
+}
+
+/// Matches the elements of both lists by key.
+///
+/// Elements with the same key are matched in order of appearance.
+fn diff_by_key<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    key: impl Fn(&'a T) -> &'a String,
+) -> KeyedDiff<'a, T> {
+    let old_keys = occurrence_keys(old, &key);
+    let new_keys = occurrence_keys(new, &key);
+    let old_index_by_key: HashMap<_, _> =
+        old_keys.iter().enumerate().map(|(i, k)| (k, i)).collect();
+    let new_key_set: HashSet<_> = new_keys.iter().collect();
+    let removed = old
+        .iter()
+        .zip(&old_keys)
+        .filter(|(_, k)| !new_key_set.contains(k))
+        .map(|(element, _)| element)
+        .collect();
+    let mut added = vec![];
+    let mut common = vec![];
+    let mut common_old_indexes = vec![];
+    for (index, (element, k)) in new.iter().zip(&new_keys).enumerate() {
+        match old_index_by_key.get(k) {
+            None => added.push((index as u32, element)),
+            Some(&old_index) => {
+                common.push((&old[old_index], element));
+                common_old_indexes.push(old_index);
+            }
+        }
+    }
+    KeyedDiff {
+        added,
+        removed,
+        common,
+        reordered: common_old_indexes.windows(2).any(|w| w[0] > w[1]),
+    }
+}
+
+/// Makes keys unique by adding the number of previous occurrences.
+fn occurrence_keys<'a, T>(
+    elements: &'a [T],
+    key: impl Fn(&'a T) -> &'a String,
+) -> Vec<(&'a str, u32)> {
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    elements
+        .iter()
+        .map(|element| {
+            let k = key(element).as_str();
+            let count = counts.entry(k).or_default();
+            let occurrence_key = (k, *count);
+            *count += 1;
+            occurrence_key
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(guid: &str) -> TrackSnapshot {
+        TrackSnapshot {
+            guid: guid.to_string(),
+            name: guid.to_string(),
+            volume: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn fx(guid: &str, parameter_values: Vec<f64>) -> FxSnapshot {
+        FxSnapshot {
+            guid: guid.to_string(),
+            name: guid.to_string(),
+            is_enabled: true,
+            parameter_values,
+            ..Default::default()
+        }
+    }
+
+    fn send(target: &str, volume: f64) -> SendSnapshot {
+        SendSnapshot {
+            target_track_guid: target.to_string(),
+            volume,
+            ..Default::default()
+        }
+    }
+
+    fn item(guid: &str, position: f64) -> ItemSnapshot {
+        ItemSnapshot {
+            guid: guid.to_string(),
+            position,
+            length: 1.0,
+            is_muted: false,
+        }
+    }
+
+    fn project(tracks: Vec<TrackSnapshot>) -> ProjectSnapshot {
+        ProjectSnapshot { tracks }
+    }
+
+    #[test]
+    fn no_changes() {
+        let snapshot = project(vec![track("a"), track("b")]);
+        assert!(snapshot.diff(&snapshot.clone()).is_empty());
+    }
+
+    #[test]
+    fn tracks_added_removed_and_reordered() {
+        let old = project(vec![track("a"), track("b"), track("c")]);
+        let new = project(vec![track("c"), track("d"), track("a")]);
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                ProjectChange::TrackRemoved {
+                    guid: "b".to_string()
+                },
+                ProjectChange::TrackAdded {
+                    index: 1,
+                    track: track("d")
+                },
+                ProjectChange::TracksReordered,
+            ]
+        );
+        // Removing a track alone is not a reordering
+        let new = project(vec![track("a"), track("c")]);
+        assert_eq!(
+            old.diff(&new),
+            vec![ProjectChange::TrackRemoved {
+                guid: "b".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn track_contents_changed() {
+        let old = project(vec![TrackSnapshot {
+            fxs: vec![fx("f1", vec![0.0, 0.5]), fx("f2", vec![])],
+            sends: vec![send("b", 1.0)],
+            items: vec![item("i1", 0.0), item("i2", 4.0)],
+            ..track("a")
+        }]);
+        let new = project(vec![TrackSnapshot {
+            name: "Lead".to_string(),
+            is_muted: true,
+            fxs: vec![
+                fx("f1", vec![0.0, 0.75]),
+                FxSnapshot {
+                    is_enabled: false,
+                    ..fx("f2", vec![])
+                },
+                fx("f3", vec![]),
+            ],
+            sends: vec![send("b", 0.5)],
+            items: vec![item("i1", 2.0), item("i3", 8.0)],
+            ..track("a")
+        }]);
+        let track_guid = || "a".to_string();
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                ProjectChange::TrackNameChanged {
+                    guid: track_guid(),
+                    old: "a".to_string(),
+                    new: "Lead".to_string()
+                },
+                ProjectChange::TrackMuteChanged {
+                    guid: track_guid(),
+                    new: true
+                },
+                ProjectChange::FxAdded {
+                    track_guid: track_guid(),
+                    index: 2,
+                    fx: fx("f3", vec![])
+                },
+                ProjectChange::FxParameterValueChanged {
+                    track_guid: track_guid(),
+                    fx_guid: "f1".to_string(),
+                    index: 1,
+                    old: 0.5,
+                    new: 0.75
+                },
+                ProjectChange::FxEnabledChanged {
+                    track_guid: track_guid(),
+                    fx_guid: "f2".to_string(),
+                    new: false
+                },
+                ProjectChange::SendChanged {
+                    track_guid: track_guid(),
+                    old: send("b", 1.0),
+                    new: send("b", 0.5)
+                },
+                ProjectChange::ItemRemoved {
+                    track_guid: track_guid(),
+                    item_guid: "i2".to_string()
+                },
+                ProjectChange::ItemAdded {
+                    track_guid: track_guid(),
+                    item: item("i3", 8.0)
+                },
+                ProjectChange::ItemChanged {
+                    track_guid: track_guid(),
+                    old: item("i1", 0.0),
+                    new: item("i1", 2.0)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn multiple_sends_to_same_track() {
+        let old = project(vec![TrackSnapshot {
+            sends: vec![send("b", 1.0), send("b", 0.5)],
+            ..track("a")
+        }]);
+        let new = project(vec![TrackSnapshot {
+            sends: vec![send("b", 1.0)],
+            ..track("a")
+        }]);
+        assert_eq!(
+            old.diff(&new),
+            vec![ProjectChange::SendRemoved {
+                track_guid: "a".to_string(),
+                target_track_guid: "b".to_string()
+            }]
+        );
+    }
+}
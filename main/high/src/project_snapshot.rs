@@ -0,0 +1,177 @@
+use crate::{Fx, Item, Project, SendPartnerType, Track, TrackRoute, TrackRoutePartner};
+
+/// Decides which parts of a project end up in a [`ProjectSnapshot`].
+///
+/// By default, everything is included.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SnapshotOptions {
+    pub include_fx: bool,
+    /// Only has an effect if FX are included. Can take a while for FX with many parameters.
+    pub include_fx_parameters: bool,
+    pub include_sends: bool,
+    pub include_items: bool,
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        Self {
+            include_fx: true,
+            include_fx_parameters: true,
+            include_sends: true,
+            include_items: true,
+        }
+    }
+}
+
+/// Plain-data state of a project at a certain point in time.
+///
+/// Created via [`Project::snapshot()`]. Objects are identified by their GUIDs (with braces), so
+/// snapshots of different sessions or REAPER instances can be compared with
+/// [`diff()`](Self::diff).
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectSnapshot {
+    /// Master track first, then all normal tracks in project order.
+    pub tracks: Vec<TrackSnapshot>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackSnapshot {
+    pub guid: String,
+    pub is_master: bool,
+    pub name: String,
+    pub volume: f64,
+    pub pan: f64,
+    pub is_muted: bool,
+    pub is_solo: bool,
+    /// Input FX first, then normal FX.
+    pub fxs: Vec<FxSnapshot>,
+    /// Sends to other tracks (no hardware outputs).
+    pub sends: Vec<SendSnapshot>,
+    pub items: Vec<ItemSnapshot>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FxSnapshot {
+    pub guid: String,
+    pub name: String,
+    pub is_input_fx: bool,
+    pub is_enabled: bool,
+    /// Normalized values, ordered by parameter index.
+    pub parameter_values: Vec<f64>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SendSnapshot {
+    pub target_track_guid: String,
+    pub volume: f64,
+    pub pan: f64,
+    pub is_muted: bool,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemSnapshot {
+    pub guid: String,
+    pub position: f64,
+    pub length: f64,
+    pub is_muted: bool,
+}
+
+pub(crate) fn take_project_snapshot(project: Project, options: SnapshotOptions) -> ProjectSnapshot {
+    let tracks = project.master_track().into_iter().chain(project.tracks());
+    ProjectSnapshot {
+        tracks: tracks
+            .map(|track| take_track_snapshot(&track, options))
+            .collect(),
+    }
+}
+
+fn take_track_snapshot(track: &Track, options: SnapshotOptions) -> TrackSnapshot {
+    let fxs = if options.include_fx {
+        let chains = [track.input_fx_chain(), track.normal_fx_chain()];
+        chains
+            .iter()
+            .flat_map(|chain| chain.fxs())
+            .map(|fx| take_fx_snapshot(&fx, options))
+            .collect()
+    } else {
+        vec![]
+    };
+    let sends = if options.include_sends {
+        track
+            .typed_sends(SendPartnerType::Track)
+            .filter_map(|route| take_send_snapshot(&route))
+            .collect()
+    } else {
+        vec![]
+    };
+    let items = if options.include_items {
+        track
+            .items()
+            .map(|item| take_item_snapshot(&item))
+            .collect()
+    } else {
+        vec![]
+    };
+    TrackSnapshot {
+        guid: track.guid().to_string_with_braces(),
+        is_master: track.is_master_track(),
+        name: track
+            .name()
+            .map(|name| name.into_string())
+            .unwrap_or_default(),
+        volume: track.volume().get(),
+        pan: track.pan().reaper_value().get(),
+        is_muted: track.is_muted(),
+        is_solo: track.is_solo(),
+        fxs,
+        sends,
+        items,
+    }
+}
+
+fn take_fx_snapshot(fx: &Fx, options: SnapshotOptions) -> FxSnapshot {
+    let parameter_values = if options.include_fx_parameters {
+        fx.parameters()
+            .map(|p| p.reaper_normalized_value().get())
+            .collect()
+    } else {
+        vec![]
+    };
+    FxSnapshot {
+        guid: fx
+            .guid()
+            .map(|guid| guid.to_string_with_braces())
+            .unwrap_or_default(),
+        name: fx.name().into_string(),
+        is_input_fx: fx.is_input_fx(),
+        is_enabled: fx.is_enabled(),
+        parameter_values,
+    }
+}
+
+fn take_send_snapshot(route: &TrackRoute) -> Option<SendSnapshot> {
+    let Some(TrackRoutePartner::Track(target_track)) = route.partner() else {
+        return None;
+    };
+    let snapshot = SendSnapshot {
+        target_track_guid: target_track.guid().to_string_with_braces(),
+        volume: route.volume().ok()?.get(),
+        pan: route.pan().ok()?.reaper_value().get(),
+        is_muted: route.is_muted().ok()?,
+    };
+    Some(snapshot)
+}
+
+fn take_item_snapshot(item: &Item) -> ItemSnapshot {
+    ItemSnapshot {
+        guid: item.guid().to_string_with_braces(),
+        position: item.position().get(),
+        length: item.length().get(),
+        is_muted: item.is_muted(),
+    }
+}
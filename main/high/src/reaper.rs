@@ -10,7 +10,11 @@ use std::sync::{Arc, OnceLock, Weak};
 
 use crate::undo_block::UndoBlock;
 use crate::ActionKind::Toggleable;
-use crate::{DefaultConsoleMessageFormatter, Project};
+use crate::{
+    ActionInterceptor, ActionInterceptorId, ActionInterceptorRegistry, ActionInvocation,
+    DefaultConsoleMessageFormatter, FutureSupport, Interception, MacroDefinition, Menu,
+    MenuLocation, Project,
+};
 use once_cell::sync::Lazy;
 use reaper_low::{raw, register_plugin_destroy_hook, PluginDestroyHook};
 
@@ -22,15 +26,16 @@ use fragile::Fragile;
 use reaper_medium::ProjectContext::Proj;
 use reaper_medium::UndoScope::All;
 use reaper_medium::{
-    ActionValueChange, CommandId, Handle, HookCommand, HookPostCommand2, OwnedGaccelRegister,
-    ReaProject, RealTimeAudioThreadScope, ReaperSession, ReaperStr, ReaperString, ReaperStringArg,
-    SectionContext, ToggleAction, ToggleActionResult, WindowContext,
+    ActionValueChange, CommandId, Handle, Hmenu, HookCommand, HookCommand2, HookCustomMenu,
+    HookPostCommand2, MenuHookFlag, OwnedGaccelRegister, ProjectContext, ReaProject,
+    RealTimeAudioThreadScope, ReaperSession, ReaperStr, ReaperString, ReaperStringArg,
+    SectionContext, SectionId, ToggleAction, ToggleActionResult, WindowContext,
 };
 use std::fmt::{Debug, Formatter};
 use std::ops::{Deref, DerefMut};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
-use tracing::debug;
+use tracing::{debug, warn};
 
 /// How many tasks to process at a maximum in one main loop iteration.
 pub const DEFAULT_MAIN_THREAD_TASK_BULK_SIZE: usize = 100;
@@ -75,6 +80,8 @@ impl ReaperBuilder {
             medium_session: RefCell::new(self.medium),
             command_by_id: RefCell::new(HashMap::new()),
             action_value_change_history: RefCell::new(Default::default()),
+            action_interceptors: Default::default(),
+            menu_by_id: RefCell::new(HashMap::new()),
             undo_block_is_active: Cell::new(false),
             session_status: RefCell::new(SessionStatus::Sleeping),
         };
@@ -146,6 +153,8 @@ struct ReaperMain {
     // reference???  Look into that!!!
     command_by_id: RefCell<HashMap<CommandId, Command>>,
     action_value_change_history: RefCell<HashMap<CommandId, ActionValueChange>>,
+    action_interceptors: ActionInterceptorRegistry,
+    menu_by_id: RefCell<HashMap<String, Menu>>,
     undo_block_is_active: Cell<bool>,
     session_status: RefCell<SessionStatus>,
 }
@@ -310,7 +319,8 @@ impl Reaper {
     ///
     /// In particular, it does the following:
     ///
-    /// - Registers command hooks (to actually execute invoked custom actions or menu entries)
+    /// - Registers command hooks (to actually execute invoked custom actions or menu entries and
+    ///   to run action interceptors)
     /// - Registers post command hooks (to inform listeners of executed actions)
     /// - Registers toggle actions (to report action on/off states)
     /// - Registers all previously defined actions
@@ -326,6 +336,10 @@ impl Reaper {
         medium
             .plugin_register_add_hook_command::<HighLevelHookCommand>()
             .map_err(|_| "couldn't register hook command")?;
+        // Only needed for action interceptors, so we allow it to fail.
+        let _ = medium.plugin_register_add_hook_command_2::<HighLevelHookCommand2>();
+        // Only needed for registered menus, so we allow it to fail.
+        let _ = medium.plugin_register_add_hook_custom_menu::<HighLevelHookCustomMenu>();
         medium
             .plugin_register_add_toggle_action::<HighLevelToggleAction>()
             .map_err(|_| "couldn't register toggle command")?;
@@ -376,6 +390,8 @@ impl Reaper {
         // Remove functions
         medium.plugin_register_remove_hook_post_command_2::<HighLevelHookPostCommand2>();
         medium.plugin_register_remove_toggle_action::<HighLevelToggleAction>();
+        medium.plugin_register_remove_hook_custom_menu::<HighLevelHookCustomMenu>();
+        medium.plugin_register_remove_hook_command_2::<HighLevelHookCommand2>();
         medium.plugin_register_remove_hook_command::<HighLevelHookCommand>();
         *session_status = SessionStatus::Sleeping;
         debug!("Sleeping");
@@ -450,6 +466,87 @@ impl Reaper {
         registered_action
     }
 
+    /// Registers a macro action in the main section and saves its definition persistently.
+    ///
+    /// When invoked, the steps of the macro are executed one after another on the main thread,
+    /// using the given future support for waiting.
+    pub fn register_macro_action(
+        &self,
+        command_name: &str,
+        definition: MacroDefinition,
+        future_support: &FutureSupport,
+    ) -> RegisteredAction {
+        definition.save(command_name);
+        let description = definition.description.clone();
+        let definition = Rc::new(definition);
+        let future_support = future_support.clone();
+        self.register_action(
+            command_name.to_string(),
+            description,
+            None,
+            move || {
+                let definition = definition.clone();
+                future_support.spawn_in_main_thread_from_main_thread(async move {
+                    definition.execute().await?;
+                    Ok(())
+                });
+            },
+            ActionKind::NotToggleable,
+        )
+    }
+
+    /// Registers a macro action whose definition has been saved before via
+    /// [`Self::register_macro_action`] or [`MacroDefinition::save`].
+    pub fn register_saved_macro_action(
+        &self,
+        command_name: &str,
+        future_support: &FutureSupport,
+    ) -> ReaperResult<RegisteredAction> {
+        let definition = MacroDefinition::load(command_name)?
+            .ok_or(ReaperError::new("no saved macro definition"))?;
+        Ok(self.register_macro_action(command_name, definition, future_support))
+    }
+
+    /// Registers the given menu at the given location.
+    ///
+    /// REAPER populates the menu when it initializes it, so this should be called early (e.g. when
+    /// the extension is loaded). Checkmarks of toggle actions are updated whenever the menu is
+    /// shown. Registering a menu at the same location again replaces it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the customizable menu couldn't be added.
+    pub fn register_menu(&self, location: MenuLocation, menu: Menu) -> ReaperResult<()> {
+        match &location {
+            MenuLocation::Extensions => {
+                self.medium_reaper().add_extensions_main_menu();
+            }
+            MenuLocation::Customizable {
+                id,
+                name,
+                add_to_main_menu,
+            } => {
+                let menu_is_known = self.reaper_main.get().menu_by_id.borrow().contains_key(id);
+                if !menu_is_known
+                    && !self.medium_reaper().add_customizable_menu(
+                        id.as_str(),
+                        name.as_str(),
+                        None,
+                        *add_to_main_menu,
+                    )
+                {
+                    return Err("couldn't add customizable menu".into());
+                }
+            }
+        }
+        self.reaper_main
+            .get()
+            .menu_by_id
+            .borrow_mut()
+            .insert(location.menu_id().to_string(), menu);
+        Ok(())
+    }
+
     /// Registers an action in a specific section (e.g. MIDI editor, media explorer, etc.)
     /// 
     /// # Arguments
@@ -562,6 +659,30 @@ impl Reaper {
             .copied()
     }
 
+    /// Adds an interceptor which gets to see all action invocations in all sections before they
+    /// are executed.
+    ///
+    /// Interceptors with higher priority see invocations first. Takes effect while reaper-rs is
+    /// awake. Can also be called from within an interceptor, in which case the new interceptor
+    /// takes part from the next invocation on.
+    pub fn add_action_interceptor(
+        &self,
+        priority: i32,
+        interceptor: impl ActionInterceptor + 'static,
+    ) -> ActionInterceptorId {
+        self.reaper_main
+            .get()
+            .action_interceptors
+            .add(priority, interceptor)
+    }
+
+    /// Removes the given interceptor and returns whether it was present.
+    ///
+    /// Can also be called from within an interceptor, e.g. for removing itself.
+    pub fn remove_action_interceptor(&self, id: ActionInterceptorId) -> bool {
+        self.reaper_main.get().action_interceptors.remove(id)
+    }
+
     pub fn undoable_action_is_running(&self) -> bool {
         self.reaper_main.get().undo_block_is_active.get()
     }
@@ -710,6 +831,74 @@ impl HookCommand for HighLevelHookCommand {
     }
 }
 
+// Called by REAPER (using a delegate function)!
+// Runs the action interceptors, for all sections
+struct HighLevelHookCommand2 {}
+
+impl HookCommand2 for HighLevelHookCommand2 {
+    fn call(
+        section: SectionContext,
+        command_id: CommandId,
+        value_change: ActionValueChange,
+        window: WindowContext,
+    ) -> bool {
+        let interceptors = &Reaper::get().reaper_main.get().action_interceptors;
+        if interceptors.is_empty() {
+            return false;
+        }
+        let section_id = match section {
+            SectionContext::MainSection => SectionId::MAIN,
+            SectionContext::Sec(s) => s.unique_id(),
+        };
+        let invocation = ActionInvocation {
+            section_id,
+            command_id,
+            value_change,
+            window,
+        };
+        // If entered already, this is an invocation triggered by an interceptor (e.g. a
+        // replacement), which must not be intercepted again.
+        interceptors
+            .enter(|interceptors| match interceptors.intercept(&invocation) {
+                Interception::Proceed => false,
+                Interception::Veto => true,
+                Interception::Replace(replacement_id) => {
+                    let window = match window {
+                        WindowContext::Win(hwnd) => Some(hwnd),
+                        WindowContext::NoWindow => None,
+                    };
+                    let result = Reaper::get()
+                        .section_by_id(section_id)
+                        .action_by_command_id(replacement_id)
+                        .invoke_directly(value_change, window, ProjectContext::CurrentProject);
+                    if let Err(e) = result {
+                        warn!(msg = "Couldn't invoke replacement action", %e);
+                    }
+                    true
+                }
+            })
+            .unwrap_or(false)
+    }
+}
+
+// Called by REAPER (using a delegate function)!
+// Populates registered menus and updates their checkmarks
+struct HighLevelHookCustomMenu {}
+
+impl HookCustomMenu for HighLevelHookCustomMenu {
+    fn call(menu_id: &ReaperStr, menu: Hmenu, flag: MenuHookFlag) {
+        let menu_by_id = Reaper::get().reaper_main.get().menu_by_id.borrow();
+        let Some(registered_menu) = menu_by_id.get(menu_id.to_str()) else {
+            return;
+        };
+        match flag {
+            MenuHookFlag::Init => registered_menu.populate(menu),
+            MenuHookFlag::Show => registered_menu.update_checkmarks(menu),
+            MenuHookFlag::Unknown(_) => {}
+        }
+    }
+}
+
 // Called by REAPER directly (using a delegate function)!
 // Processes main section only.
 struct HighLevelHookPostCommand2 {}
@@ -822,6 +1011,7 @@ fn register_action(
 }
 
 use crate::error::ReaperResult;
+use crate::ReaperError;
 #[cfg(feature = "sentry")]
 pub use sentry_impl::SentryConfig;
 
@@ -2,17 +2,22 @@
 //! just access to `reaper_medium::Reaper` - without all the advanced stuff like subjects,
 //! channels etc. Although they end up in the same struct, this gives a little bit of structure.
 use crate::error::ReaperResult;
+use crate::section::find_actions_in;
 use crate::{
-    Action, Fx, FxChain, FxParameter, Guid, MidiInputDevice, MidiOutputDevice, Project, Reaper,
-    ReaperError, Section,
+    Action, ActionInfo, Fx, FxChain, FxParameter, Guid, Item, JoystickDescriptor, KeyMap,
+    KeyMapError, MidiInputDevice, MidiOutputDevice, Project, Reaper, ReaperError, Section,
+    ThingAtPoint, Track,
 };
 use camino::Utf8PathBuf;
+use enumflags2::BitFlags;
 use helgoboss_midi::ShortMessage;
 use reaper_medium::{
-    AudioDeviceAttributeKey, CommandId, EnumPitchShiftModesResult, GetLastTouchedFxResult,
-    GlobalAutomationModeOverride, Hwnd, Hz, MidiInputDeviceId, MidiOutputDeviceId, PitchShiftMode,
-    PitchShiftSubMode, ProjectRef, ReaperStr, ReaperString, ReaperStringArg, ReaperVersion,
-    ResampleMode, SectionId, StuffMidiMessageTarget, TrackLocation,
+    AudioDeviceAttributeKey, CommandId, CursorContext, EnumPitchShiftModesResult,
+    GetLastTouchedFxResult, GlobalAutomationModeOverride, Hwnd, Hz, MidiInputDeviceId,
+    MidiOutputDeviceId, PitchShiftMode, PitchShiftSubMode, Point, ProjectRef, ReaperStr,
+    ReaperString, ReaperStringArg, ReaperVersion, ReaperVolumeValue, RecordingInput, ResampleMode,
+    RgbColor, SectionId, StuffMidiMessageTarget, ThemeColorKey, ThemeLayoutIndex,
+    ThemeLayoutParameter, TrackLocation,
 };
 use std::fmt::Debug;
 use std::path::PathBuf;
@@ -55,14 +60,57 @@ impl Reaper {
             .map(|r| r.unwrap())
     }
 
+    pub fn output_channels(&self) -> impl Iterator<Item = String> + '_ {
+        (0..)
+            .map(move |i| self.output_channel_name(i))
+            .take_while(|r| !r.is_none())
+            .map(|r| r.unwrap())
+    }
+
+    pub fn output_channel_name(&self, index: u32) -> Option<String> {
+        self.medium_reaper()
+            .get_output_channel_name(index, |name| name.map(|n| n.to_string_lossy().to_string()))
+    }
+
+    /// Returns the approximate current level of the given hardware input.
+    ///
+    /// Returns `None` if not available.
+    pub fn input_level(&self, input: RecordingInput) -> Option<ReaperVolumeValue> {
+        self.medium_reaper().get_input_activity_level(input)
+    }
+
     pub fn main_section(&self) -> Section {
-        self.section_by_id(SectionId::new(0))
+        self.section_by_id(SectionId::MAIN)
     }
 
     pub fn section_by_id(&self, id: SectionId) -> Section {
         Section::new(id)
     }
 
+    /// Returns the built-in action sections which are available.
+    pub fn sections(&self) -> impl Iterator<Item = Section> + 'static {
+        [
+            SectionId::MAIN,
+            SectionId::MAIN_ALT_RECORDING,
+            SectionId::MIDI_EDITOR,
+            SectionId::MIDI_EVENT_LIST_EDITOR,
+            SectionId::MIDI_INLINE_EDITOR,
+            SectionId::MEDIA_EXPLORER,
+        ]
+        .into_iter()
+        .map(Section::new)
+        .filter(|s| s.is_available())
+    }
+
+    /// Searches the actions of all sections by fuzzy-matching the given query against their
+    /// descriptions and command names.
+    ///
+    /// The best matches come first.
+    pub fn find_actions(&self, query: &str) -> Vec<ActionInfo> {
+        let actions = self.sections().flat_map(|s| s.actions());
+        find_actions_in(actions, query)
+    }
+
     pub fn monitoring_fx_chain(&self) -> FxChain {
         FxChain::from_monitoring()
     }
@@ -111,6 +159,26 @@ impl Reaper {
         self.medium_reaper.get_resource_path(|p| p.to_owned())
     }
 
+    /// Returns the path of the `reaper-kb.ini` file which contains all shortcut assignments.
+    pub fn key_map_file_path(&self) -> Utf8PathBuf {
+        self.resource_path().join("reaper-kb.ini")
+    }
+
+    /// Reads all shortcut assignments from `reaper-kb.ini`.
+    ///
+    /// REAPER writes this file whenever shortcuts change, so it reflects the current state.
+    pub fn export_key_map(&self) -> Result<KeyMap, KeyMapError> {
+        KeyMap::load(self.key_map_file_path().as_std_path())
+    }
+
+    /// Replaces all shortcut assignments in `reaper-kb.ini` with the given ones.
+    ///
+    /// Custom actions and scripts defined in the file are kept. REAPER reads this file at startup
+    /// only, so the new shortcuts take effect after a restart.
+    pub fn import_key_map(&self, key_map: &KeyMap) -> Result<(), KeyMapError> {
+        key_map.save(self.key_map_file_path().as_std_path())
+    }
+
     // Attention: Returns normal fx only, not input fx!
     // This is not reliable! After REAPER start no focused Fx can be found!
     #[allow(deprecated)]
@@ -174,6 +242,97 @@ impl Reaper {
         self.medium_reaper().get_main_hwnd()
     }
 
+    /// Shows a built-in context menu (e.g. `track_panel` or `item`) or a menu registered via
+    /// [`Self::register_menu()`] at the given screen location.
+    pub fn show_popup_menu(&self, name: &str, location: Point) {
+        unsafe {
+            self.medium_reaper()
+                .show_popup_menu(name, location, Some(self.main_window()));
+        }
+    }
+
+    /// Returns the current color of the given theme element.
+    pub fn theme_color(&self, key: ThemeColorKey) -> ReaperResult<RgbColor> {
+        let reaper = self.medium_reaper();
+        let color = reaper.get_theme_color(key, BitFlags::empty())?;
+        Ok(reaper.color_from_native(color))
+    }
+
+    /// Temporarily changes the color of the given theme element (until the theme is reloaded) or
+    /// resets it to the theme default if `color` is `None`. Updates the arrange view.
+    pub fn set_theme_color(
+        &self,
+        key: ThemeColorKey,
+        color: Option<RgbColor>,
+    ) -> ReaperResult<RgbColor> {
+        let reaper = self.medium_reaper();
+        let applied_color = reaper.set_theme_color(key, color, BitFlags::empty())?;
+        reaper.update_arrange();
+        Ok(applied_color)
+    }
+
+    /// Returns all parameters of the current theme.
+    pub fn theme_parameters(&self) -> impl Iterator<Item = ThemeLayoutParameter> + '_ {
+        (0..).map_while(move |i| self.medium_reaper().theme_layout_get_parameter(i))
+    }
+
+    /// Sets the value of the theme parameter at the given index and refreshes all layouts, so the
+    /// change is visible immediately.
+    ///
+    /// If `persist` is `true`, the value is also used the next time the theme is loaded.
+    pub fn set_theme_parameter(&self, index: u32, value: i32, persist: bool) -> ReaperResult<()> {
+        let reaper = self.medium_reaper();
+        reaper.theme_layout_set_parameter(index, value, persist)?;
+        reaper.theme_layout_refresh_all();
+        Ok(())
+    }
+
+    /// Returns the names of all layouts which the current theme offers in the given section (e.g.
+    /// `tcp` or `mcp`).
+    pub fn theme_layouts(&self, section: &str) -> Vec<ReaperString> {
+        (0..)
+            .map_while(|i| {
+                self.medium_reaper().theme_layout_get_layout(
+                    section,
+                    ThemeLayoutIndex::Layout(i),
+                    256,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the currently active layout of the given section.
+    pub fn active_theme_layout(&self, section: &str) -> Option<ReaperString> {
+        self.medium_reaper()
+            .theme_layout_get_layout(section, ThemeLayoutIndex::Current, 256)
+    }
+
+    /// Sets the active layout of the given section. Pass `None` in order to clear it.
+    pub fn set_active_theme_layout(&self, section: &str, layout: Option<&str>) -> ReaperResult<()> {
+        self.medium_reaper()
+            .theme_layout_set_layout(section, layout.unwrap_or_default())?;
+        Ok(())
+    }
+
+    /// Returns the path of the most recently loaded theme file.
+    pub fn last_theme_file(&self) -> Option<Utf8PathBuf> {
+        self.medium_reaper().get_last_color_theme_file()
+    }
+
+    /// Returns all installed joysticks and game controllers.
+    pub fn joysticks(&self) -> Vec<JoystickDescriptor> {
+        (0..)
+            .map_while(|i| self.medium_reaper().joystick_enum(i))
+            .filter_map(|r| {
+                let descriptor = JoystickDescriptor {
+                    guid: Guid::from_string_with_braces(r.guid.as_reaper_str()).ok()?,
+                    name: r.name.map(|n| n.into_string()).unwrap_or_default(),
+                };
+                Some(descriptor)
+            })
+            .collect()
+    }
+
     pub fn resample_modes(&self) -> impl Iterator<Item = &'static ReaperStr> + '_ {
         (0..)
             .map(move |i| {
@@ -465,6 +624,51 @@ impl Reaper {
         Ok(result)
     }
 
+    /// Returns the current cursor context.
+    ///
+    /// If there's no valid context at the moment, returns the last valid one.
+    pub fn cursor_context(&self) -> CursorContext {
+        self.medium_reaper.get_cursor_context_2(true)
+    }
+
+    /// Focuses the track panels or the arrange window.
+    ///
+    /// Use [`Reaper::medium_reaper`] for focusing a particular envelope.
+    pub fn set_cursor_context(&self, context: CursorContext) {
+        unsafe {
+            self.medium_reaper.set_cursor_context(context, None);
+        }
+    }
+
+    /// Returns the current mouse position in screen coordinates.
+    pub fn mouse_position(&self) -> Point {
+        self.medium_reaper.get_mouse_position()
+    }
+
+    /// Returns what's located at the given point in screen coordinates.
+    pub fn thing_at_point(&self, point: Point) -> Option<ThingAtPoint> {
+        ThingAtPoint::at(point)
+    }
+
+    /// Returns what's located under the mouse cursor.
+    pub fn thing_under_mouse(&self) -> Option<ThingAtPoint> {
+        self.thing_at_point(self.mouse_position())
+    }
+
+    /// Returns the item located at the given point in screen coordinates.
+    pub fn item_at_point(&self, point: Point, allow_locked: bool) -> Option<Item> {
+        let result = self
+            .medium_reaper
+            .get_item_from_point(point, allow_locked)?;
+        Some(Item::new(result.item))
+    }
+
+    /// Returns the track located at the given point in screen coordinates.
+    pub fn track_at_point(&self, point: Point) -> Option<Track> {
+        let result = self.medium_reaper.get_track_from_point(point)?;
+        Some(Track::new(result.track, None))
+    }
+
     pub fn get_preference_ref<'a, T>(
         &self,
         name: impl Into<ReaperStringArg<'a>>,
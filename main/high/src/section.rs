@@ -1,11 +1,33 @@
 use crate::{Action, Reaper};
-use reaper_medium::{CommandId, KbdSectionInfo, SectionId};
+use reaper_medium::{CommandId, KbdSectionInfo, SectionContext, SectionId};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Section {
     id: SectionId,
 }
 
+/// Information about an action as returned by [`Section::actions()`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ActionInfo {
+    pub section: Section,
+    /// Index of the action within its section.
+    pub index: u32,
+    pub command_id: CommandId,
+    /// Command name (without leading underscore).
+    ///
+    /// Only available for custom actions, scripts and actions registered by extensions.
+    pub command_name: Option<String>,
+    pub description: String,
+    /// `None` if the action doesn't report on/off states.
+    pub toggle_state: Option<bool>,
+}
+
+impl ActionInfo {
+    pub fn action(&self) -> Action {
+        Action::new(self.section, self.command_id, Some(self.index))
+    }
+}
+
 impl Section {
     pub(super) fn new(id: SectionId) -> Section {
         Section { id }
@@ -15,6 +37,11 @@ impl Section {
         self.id
     }
 
+    /// Returns whether this section exists.
+    pub fn is_available(self) -> bool {
+        self.with_raw(|_| ()).is_some()
+    }
+
     pub fn with_raw<R>(self, f: impl FnOnce(&KbdSectionInfo) -> R) -> Option<R> {
         Reaper::get()
             .medium_reaper()
@@ -35,33 +62,158 @@ impl Section {
         Action::new(self, command_id, None)
     }
 
+    /// Returns the action at the given index.
+    ///
+    /// The index covers custom actions, scripts and actions registered by extensions as well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no action at the given index.
     pub fn action_by_index(self, index: u32) -> Action {
-        self.with_raw(|s| {
-            assert!(
-                index < s.action_list_cnt(),
-                "No such action index in section"
-            );
-            let kbd_cmd = s.get_action_by_index(index).unwrap();
-            Action::new(self, kbd_cmd.cmd(), Some(index))
-        })
-        .unwrap()
+        let command_id = self
+            .enumerate_action(index, |command_id, _| command_id)
+            .expect("No such action index in section");
+        Action::new(self, command_id, Some(index))
     }
 
+    /// Returns the number of actions in this section.
+    ///
+    /// REAPER doesn't expose the count directly, so this enumerates all actions, which is O(n).
     pub fn action_count(self) -> u32 {
-        self.with_raw(|s| s.action_list_cnt()).unwrap()
+        (0..)
+            .take_while(|i| self.enumerate_action(*i, |_, _| ()).is_some())
+            .count() as u32
     }
 
-    /// # Safety
+    /// Returns information about all actions in this section, including custom actions, scripts
+    /// and actions registered by extensions.
     ///
-    /// Unsafe because at the time when the iterator is evaluated, the section could be gone.
-    pub unsafe fn actions(self) -> impl Iterator<Item = Action> + 'static {
-        let sec = Reaper::get()
-            .medium_reaper()
-            .section_from_unique_id_unchecked(self.id)
-            .unwrap();
-        (0..sec.action_list_cnt()).map(move |i| {
-            let kbd_cmd = sec.get_action_by_index(i).unwrap();
-            Action::new(self, kbd_cmd.cmd(), Some(i))
+    /// The iterator queries REAPER lazily, so it should be consumed right away.
+    pub fn actions(self) -> impl Iterator<Item = ActionInfo> + 'static {
+        (0..).map_while(move |i| self.action_info(i))
+    }
+
+    /// Searches the actions in this section by fuzzy-matching the given query against their
+    /// descriptions and command names.
+    ///
+    /// The best matches come first.
+    pub fn find_actions(self, query: &str) -> Vec<ActionInfo> {
+        find_actions_in(self.actions(), query)
+    }
+
+    fn action_info(self, index: u32) -> Option<ActionInfo> {
+        let (command_id, description) =
+            self.enumerate_action(index, |command_id, desc| (command_id, desc.to_string()))?;
+        let reaper = Reaper::get().medium_reaper();
+        let info = ActionInfo {
+            section: self,
+            index,
+            command_id,
+            command_name: reaper
+                .reverse_named_command_lookup(command_id, |name| name.to_str().to_owned()),
+            description,
+            toggle_state: reaper.get_toggle_command_state_ex(self.id, command_id),
+        };
+        Some(info)
+    }
+
+    pub(crate) fn enumerate_action<R>(
+        self,
+        index: u32,
+        f: impl FnOnce(CommandId, &str) -> R,
+    ) -> Option<R> {
+        self.with_raw(|s| unsafe {
+            Reaper::get().medium_reaper().kbd_enumerate_actions(
+                SectionContext::Sec(s),
+                index,
+                |command_id, desc| f(command_id, desc.to_str()),
+            )
         })
+        .flatten()
+    }
+}
+
+/// Fuzzy-matches the given query against the given actions and returns the matching ones, best
+/// matches first.
+pub(crate) fn find_actions_in(
+    actions: impl Iterator<Item = ActionInfo>,
+    query: &str,
+) -> Vec<ActionInfo> {
+    let mut matches: Vec<_> = actions
+        .filter_map(|info| {
+            let description_score = fuzzy_score(query, &info.description);
+            let command_name_score = info
+                .command_name
+                .as_deref()
+                .and_then(|name| fuzzy_score(query, name));
+            let score = description_score.max(command_name_score)?;
+            Some((score, info))
+        })
+        .collect();
+    matches.sort_by(|(score_a, a), (score_b, b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| a.description.len().cmp(&b.description.len()))
+    });
+    matches.into_iter().map(|(_, info)| info).collect()
+}
+
+/// Returns a score if all characters of the query appear in the candidate in the same order
+/// (case-insensitive).
+///
+/// Consecutive matches and matches at word starts score higher.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let mut query_chars = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    let mut score = 0;
+    let mut prev_matched = false;
+    let mut prev_char: Option<char> = None;
+    for c in candidate.chars() {
+        let Some(&q) = query_chars.peek() else {
+            break;
+        };
+        let matched = c.to_lowercase().eq(std::iter::once(q));
+        if matched {
+            query_chars.next();
+            score += 1;
+            if prev_matched {
+                score += 2;
+            }
+            let is_word_start = match prev_char {
+                None => true,
+                Some(p) => !p.is_alphanumeric(),
+            };
+            if is_word_start {
+                score += 3;
+            }
+        }
+        prev_matched = matched;
+        prev_char = Some(c);
+    }
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy() {
+        assert_eq!(fuzzy_score("", "Transport: Play"), Some(0));
+        assert!(fuzzy_score("tpl", "Transport: Play").is_some());
+        assert!(fuzzy_score("PLAY", "Transport: Play").is_some());
+        assert!(fuzzy_score("yalp", "Transport: Play").is_none());
+        let exact = fuzzy_score("play", "Transport: Play").unwrap();
+        let scattered = fuzzy_score("play", "Track: Panic all tracks lay").unwrap();
+        assert!(exact > scattered);
+        let word_start = fuzzy_score("tp", "Transport: Play").unwrap();
+        let mid_word = fuzzy_score("tp", "Stop").unwrap();
+        assert!(word_start > mid_word);
     }
 }
@@ -1,4 +1,4 @@
-use crate::{FxChain, OwnedSource, Reaper, ReaperSource, Track};
+use crate::{FxChain, Guid, OwnedSource, Reaper, ReaperSource, Track};
 use reaper_medium::{
     DurationInSeconds, FullPitchShiftMode, MediaItemTake, NativeColorValue, PlaybackSpeedFactor,
     ReaperFunctionError, ReaperStringArg, ReaperVolumeValue, RgbColor, Semitones, TakeAttributeKey,
@@ -28,6 +28,15 @@ impl Take {
         todo!()
     }
 
+    pub fn guid(&self) -> Guid {
+        let raw = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_set_media_item_take_info_get_guid(self.raw)
+        };
+        Guid::new(raw)
+    }
+
     pub fn name(&self) -> String {
         Reaper::get()
             .medium_reaper
@@ -0,0 +1,190 @@
+use crate::{Item, Reaper, Take, Track};
+use reaper_medium::{Point, TrackArea, TrackPart};
+
+/// Something in the REAPER user interface which is located at a certain screen point.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ThingAtPoint {
+    /// A track control panel or mixer control panel.
+    TrackPanel {
+        track: Track,
+        area: TrackArea,
+        /// The hit panel element, e.g. `mute`.
+        element: Option<String>,
+    },
+    /// A media item in the arrange view.
+    Item { item: Item, take: Option<Take> },
+    /// An envelope lane or an envelope within the media item area of a track.
+    Envelope { track: Track },
+    /// An FX window which belongs to a track.
+    TrackFx { track: Track },
+    /// Empty space in the arrange view.
+    Arrange { track: Option<Track> },
+    /// The ruler above the arrange view.
+    Ruler,
+    /// The spacer in front of the track with the given index.
+    Spacer { index: u32 },
+    /// An FX chain window.
+    FxChain { track: Option<Track> },
+    /// A floating FX window.
+    FloatingFx { track: Option<Track>, index: u32 },
+    /// A MIDI editor.
+    MidiEditor {
+        /// The hit MIDI editor element as reported by REAPER.
+        element: String,
+    },
+    /// Anything else.
+    Other {
+        track: Option<Track>,
+        /// Description of the hit thing as reported by REAPER.
+        info: String,
+    },
+}
+
+impl ThingAtPoint {
+    /// Returns the track which the thing belongs to, if any.
+    pub fn track(&self) -> Option<Track> {
+        use ThingAtPoint::*;
+        match self {
+            TrackPanel { track, .. } | Envelope { track } | TrackFx { track } => {
+                Some(track.clone())
+            }
+            Item { item, .. } => item.track(),
+            Arrange { track }
+            | FxChain { track }
+            | FloatingFx { track, .. }
+            | Other { track, .. } => track.clone(),
+            Ruler | Spacer { .. } | MidiEditor { .. } => None,
+        }
+    }
+
+    /// Hit tests the given point in screen coordinates.
+    ///
+    /// Returns `None` if nothing relevant has been hit.
+    pub(crate) fn at(point: Point) -> Option<ThingAtPoint> {
+        let reaper = Reaper::get().medium_reaper();
+        let result = reaper.get_thing_from_point(point, 256);
+        let info = result.info.into_string();
+        let track = result.track.map(|t| Track::new(t, None));
+        if info.is_empty() && track.is_none() {
+            return None;
+        }
+        let thing = match classify_info(&info) {
+            InfoKind::TrackPanel { area, element } => match track {
+                Some(track) => ThingAtPoint::TrackPanel {
+                    track,
+                    area,
+                    element: element.map(|e| e.to_string()),
+                },
+                None => ThingAtPoint::Other { track, info },
+            },
+            InfoKind::Arrange => {
+                if let Some(res) = reaper.get_item_from_point(point, true) {
+                    ThingAtPoint::Item {
+                        item: Item::new(res.item),
+                        take: res.take.map(Take::new),
+                    }
+                } else if let Some(res) = reaper.get_track_from_point(point) {
+                    let track = Track::new(res.track, None);
+                    match res.part {
+                        TrackPart::Envelope => ThingAtPoint::Envelope { track },
+                        TrackPart::Fx => ThingAtPoint::TrackFx { track },
+                        _ => ThingAtPoint::Arrange { track: Some(track) },
+                    }
+                } else {
+                    ThingAtPoint::Arrange { track }
+                }
+            }
+            InfoKind::Ruler => ThingAtPoint::Ruler,
+            InfoKind::Spacer { index } => ThingAtPoint::Spacer { index },
+            InfoKind::FxChain => ThingAtPoint::FxChain { track },
+            InfoKind::FloatingFx { index } => ThingAtPoint::FloatingFx { track, index },
+            InfoKind::MidiEditor => ThingAtPoint::MidiEditor { element: info },
+            InfoKind::Other => ThingAtPoint::Other { track, info },
+        };
+        Some(thing)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum InfoKind<'a> {
+    TrackPanel {
+        area: TrackArea,
+        element: Option<&'a str>,
+    },
+    Arrange,
+    Ruler,
+    Spacer {
+        index: u32,
+    },
+    FxChain,
+    FloatingFx {
+        index: u32,
+    },
+    MidiEditor,
+    Other,
+}
+
+/// Classifies the info string returned by `GetThingFromPoint`.
+fn classify_info(info: &str) -> InfoKind<'_> {
+    let (head, tail) = match info.split_once('.') {
+        None => (info, None),
+        Some((head, tail)) => (head, Some(tail)),
+    };
+    match head {
+        "tcp" => InfoKind::TrackPanel {
+            area: TrackArea::Tcp,
+            element: tail,
+        },
+        "mcp" => InfoKind::TrackPanel {
+            area: TrackArea::Mcp,
+            element: tail,
+        },
+        "arrange" => InfoKind::Arrange,
+        "ruler" => InfoKind::Ruler,
+        "fx_chain" => InfoKind::FxChain,
+        _ if head.starts_with("midi_editor") => InfoKind::MidiEditor,
+        _ => {
+            if let Some(index) = parse_indexed(head, "spacer_") {
+                InfoKind::Spacer { index }
+            } else if let Some(index) = parse_indexed(head, "fx_") {
+                InfoKind::FloatingFx { index }
+            } else {
+                InfoKind::Other
+            }
+        }
+    }
+}
+
+fn parse_indexed(text: &str, prefix: &str) -> Option<u32> {
+    text.strip_prefix(prefix)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify() {
+        assert_eq!(
+            classify_info("tcp"),
+            InfoKind::TrackPanel {
+                area: TrackArea::Tcp,
+                element: None
+            }
+        );
+        assert_eq!(
+            classify_info("mcp.mute"),
+            InfoKind::TrackPanel {
+                area: TrackArea::Mcp,
+                element: Some("mute")
+            }
+        );
+        assert_eq!(classify_info("arrange"), InfoKind::Arrange);
+        assert_eq!(classify_info("fx_chain"), InfoKind::FxChain);
+        assert_eq!(classify_info("fx_3"), InfoKind::FloatingFx { index: 3 });
+        assert_eq!(classify_info("spacer_0"), InfoKind::Spacer { index: 0 });
+        assert_eq!(classify_info("midi_editor.notes"), InfoKind::MidiEditor);
+        assert_eq!(classify_info("fx_"), InfoKind::Other);
+        assert_eq!(classify_info("trans"), InfoKind::Other);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::error::ReaperResult;
+use crate::FxRef;
+use reaper_medium::{
+    GetTouchStateArgs, MediaTrack, ReaperNormalizedFxParamValue, TouchedParameterType,
+};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Keeps track of parameters which are currently touched on a control surface, e.g. by touching
+/// a motorized fader.
+///
+/// REAPER's _Touch_ and _Latch_ automation modes only write automation correctly if they know
+/// when a parameter is touched and released:
+///
+/// - Volume, pan and width: REAPER asks the control surface. Forward `get_touch_state()` of your
+///   [`ControlSurfaceMiddleware`] to [`get_touch_state()`] and return `1` from its
+///   `ext_supports_extended_touch()` if width should be supported.
+/// - FX parameters: REAPER treats each value change made via the API as part of a parameter edit
+///   (a touch) until the edit is ended. Change values with [`touch_fx_parameter()`], which sets
+///   the value and marks the parameter as touched. The edit is ended when the parameter is
+///   released.
+///
+/// Touches are released either explicitly or, if a release timeout is set, automatically in
+/// [`run()`] if the parameter hasn't been touched again within the timeout. The timeout is
+/// useful for controllers which don't report releases.
+///
+/// [`ControlSurfaceMiddleware`]: crate::ControlSurfaceMiddleware
+/// [`get_touch_state()`]: Self::get_touch_state
+/// [`touch_fx_parameter()`]: Self::touch_fx_parameter
+/// [`run()`]: Self::run
+#[derive(Debug, Default)]
+pub struct TouchManager {
+    /// Value is the time of the last touch.
+    touches: HashMap<TouchTarget, Instant>,
+    release_timeout: Option<Duration>,
+}
+
+/// A parameter which can be touched on a control surface.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TouchTarget {
+    /// Volume, pan or width of a track.
+    Track {
+        track: MediaTrack,
+        parameter_type: TouchedParameterType,
+    },
+    FxParameter {
+        fx: FxRef,
+        index: u32,
+    },
+}
+
+impl TouchManager {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets after which time without further touches a touch is released automatically.
+    ///
+    /// `None` (the default) means touches are only released explicitly.
+    pub fn set_release_timeout(&mut self, timeout: Option<Duration>) {
+        self.release_timeout = timeout;
+    }
+
+    /// Marks the given parameter as touched.
+    ///
+    /// Should be called for each value change while touched, too, in order to keep the touch
+    /// alive if a release timeout is set. For FX parameters, this doesn't begin an edit in
+    /// REAPER, use [`touch_fx_parameter()`](Self::touch_fx_parameter) for value changes.
+    pub fn touch(&mut self, target: TouchTarget) {
+        self.touch_at(target, Instant::now());
+    }
+
+    /// Sets the value of the given FX parameter and marks it as touched.
+    ///
+    /// Setting the value begins (or continues) the parameter edit in REAPER, so it writes
+    /// automation in _Touch_ and _Latch_ mode until the parameter is released.
+    pub fn touch_fx_parameter(
+        &mut self,
+        fx: FxRef,
+        index: u32,
+        value: ReaperNormalizedFxParamValue,
+    ) -> ReaperResult<()> {
+        fx.to_fx()
+            .ok_or("track not available")?
+            .parameter_by_index(index)
+            .set_reaper_normalized_value(value)?;
+        self.touch(TouchTarget::FxParameter { fx, index });
+        Ok(())
+    }
+
+    /// Releases the given parameter.
+    ///
+    /// For FX parameters, this ends the parameter edit in REAPER.
+    pub fn release(&mut self, target: TouchTarget) -> ReaperResult<()> {
+        if self.touches.remove(&target).is_none() {
+            return Ok(());
+        }
+        end_edit(target)
+    }
+
+    /// Releases all touched parameters.
+    pub fn release_all(&mut self) {
+        for (target, _) in self.touches.drain() {
+            // The FX might be gone already, nothing left to release then
+            let _ = end_edit(target);
+        }
+    }
+
+    pub fn is_touched(&self, target: TouchTarget) -> bool {
+        self.touches.contains_key(&target)
+    }
+
+    /// Answers REAPER's question whether the given track parameter is touched.
+    pub fn get_touch_state(&self, args: GetTouchStateArgs) -> bool {
+        self.is_touched(TouchTarget::Track {
+            track: args.track,
+            parameter_type: args.parameter_type,
+        })
+    }
+
+    /// Releases touches whose release timeout has elapsed.
+    ///
+    /// Should be called in each main loop cycle, e.g. in
+    /// [`ControlSurfaceMiddleware::run()`](crate::ControlSurfaceMiddleware::run).
+    pub fn run(&mut self) {
+        for target in self.remove_expired_touches(Instant::now()) {
+            let _ = end_edit(target);
+        }
+    }
+
+    fn touch_at(&mut self, target: TouchTarget, now: Instant) {
+        self.touches.insert(target, now);
+    }
+
+    fn remove_expired_touches(&mut self, now: Instant) -> Vec<TouchTarget> {
+        let Some(timeout) = self.release_timeout else {
+            return vec![];
+        };
+        let mut expired = vec![];
+        self.touches.retain(|target, last_touch| {
+            let is_expired = now.duration_since(*last_touch) >= timeout;
+            if is_expired {
+                expired.push(*target);
+            }
+            !is_expired
+        });
+        expired
+    }
+}
+
+fn end_edit(target: TouchTarget) -> ReaperResult<()> {
+    match target {
+        TouchTarget::Track { .. } => Ok(()),
+        TouchTarget::FxParameter { fx, index } => fx
+            .to_fx()
+            .ok_or("track not available")?
+            .parameter_by_index(index)
+            .end_edit(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr::NonNull;
+
+    fn track_target(parameter_type: TouchedParameterType) -> TouchTarget {
+        TouchTarget::Track {
+            track: MediaTrack::new(NonNull::dangling().as_ptr()).unwrap(),
+            parameter_type,
+        }
+    }
+
+    #[test]
+    fn answers_touch_state() {
+        let mut manager = TouchManager::new();
+        let volume = track_target(TouchedParameterType::Volume);
+        manager.touch(volume);
+        let args = |parameter_type| GetTouchStateArgs {
+            track: MediaTrack::new(NonNull::dangling().as_ptr()).unwrap(),
+            parameter_type,
+        };
+        assert!(manager.get_touch_state(args(TouchedParameterType::Volume)));
+        assert!(!manager.get_touch_state(args(TouchedParameterType::Pan)));
+        manager.release(volume).unwrap();
+        assert!(!manager.get_touch_state(args(TouchedParameterType::Volume)));
+    }
+
+    #[test]
+    fn releases_touches_after_timeout() {
+        let mut manager = TouchManager::new();
+        let volume = track_target(TouchedParameterType::Volume);
+        let width = track_target(TouchedParameterType::Width);
+        let start = Instant::now();
+        manager.touch_at(volume, start);
+        manager.touch_at(width, start);
+        // Without timeout, touches stay forever
+        let later = start + Duration::from_secs(10);
+        assert!(manager.remove_expired_touches(later).is_empty());
+        manager.set_release_timeout(Some(Duration::from_millis(500)));
+        // Touching again keeps the touch alive
+        manager.touch_at(width, start + Duration::from_millis(400));
+        let t1 = start + Duration::from_millis(600);
+        assert_eq!(manager.remove_expired_touches(t1), vec![volume]);
+        assert!(!manager.is_touched(volume));
+        assert!(manager.is_touched(width));
+        let t2 = start + Duration::from_millis(900);
+        assert_eq!(manager.remove_expired_touches(t2), vec![width]);
+        assert!(!manager.is_touched(width));
+    }
+}
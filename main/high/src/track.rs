@@ -6,8 +6,8 @@ use crate::guid::Guid;
 use crate::track_route::TrackRoute;
 
 use crate::{
-    Chunk, ChunkRegion, Item, Pan, Project, Reaper, ReaperError, SendPartnerType,
-    TrackRoutePartner, Width,
+    Chunk, ChunkRegion, Envelope, Item, Pan, Project, Reaper, ReaperError, SendPartnerType,
+    TrackLane, TrackMeter, TrackRoutePartner, Width,
 };
 
 use crate::error::ReaperResult;
@@ -16,16 +16,17 @@ use enumflags2::BitFlags;
 use helgoboss_midi::Channel;
 use reaper_medium::NotificationBehavior::NotifyAll;
 use reaper_medium::ProjectContext::Proj;
-use reaper_medium::SendTarget::OtherTrack;
+use reaper_medium::SendTarget::{HardwareOutput, OtherTrack};
 use reaper_medium::TrackAttributeKey::{RecArm, RecInput, RecMon, Selected, Solo};
 use reaper_medium::{
-    AutomationMode, BeatAttachMode, ChunkCacheHint, GangBehavior, GlobalAutomationModeOverride,
-    InputMonitoringMode, MediaTrack, NativeColorValue, NotificationBehavior, Progress, ReaProject,
-    ReaperFunctionError, ReaperPanValue, ReaperString, ReaperStringArg, ReaperVolumeValue,
-    ReaperWidthValue, RecordArmMode, RecordingInput, RecordingMode, RgbColor, SetTrackUiFlags,
-    SoloMode, TrackArea, TrackAttributeKey, TrackLocation, TrackMuteOperation, TrackMuteState,
-    TrackPolarity, TrackPolarityOperation, TrackRecArmOperation, TrackSendCategory,
-    TrackSendDirection, TrackSoloOperation, ValueChange,
+    AutomationMode, BeatAttachMode, ChunkCacheHint, FixedLaneSettingsFlag, GangBehavior,
+    GlobalAutomationModeOverride, InputMonitoringMode, ItemPositioningMode, MediaTrack,
+    NativeColorValue, NotificationBehavior, Progress, RazorEdit, ReaProject, ReaperFunctionError,
+    ReaperPanValue, ReaperString, ReaperStringArg, ReaperVolumeValue, ReaperWidthValue,
+    RecordArmMode, RecordingInput, RecordingMode, RgbColor, SetTrackUiFlags, SoloMode, TrackArea,
+    TrackAttributeKey, TrackLocation, TrackMuteOperation, TrackMuteState, TrackPolarity,
+    TrackPolarityOperation, TrackRecArmOperation, TrackSendCategory, TrackSendDirection,
+    TrackSoloOperation, ValueChange,
 };
 use std::convert::TryInto;
 use std::hash::{Hash, Hasher};
@@ -139,6 +140,50 @@ impl Track {
         Either::Right(iter)
     }
 
+    pub fn envelope_count(&self) -> u32 {
+        if self.load_and_check_if_necessary_or_err().is_err() {
+            return 0;
+        }
+        unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .count_track_envelopes(self.raw_internal())
+        }
+    }
+
+    pub fn envelopes(&self) -> impl ExactSizeIterator<Item = Envelope> + 'static {
+        let track = self.clone();
+        (0..self.envelope_count()).map(move |i| {
+            let raw = unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .get_track_envelope(track.raw_internal(), i)
+                    .unwrap()
+            };
+            Envelope::new(track.clone(), raw)
+        })
+    }
+
+    /// Returns the razor edit areas of this track.
+    pub fn razor_edits(&self) -> ReaperResult<Vec<RazorEdit>> {
+        let edits = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_set_media_track_info_get_razor_edits(self.raw()?)?
+        };
+        Ok(edits)
+    }
+
+    /// Replaces the razor edit areas of this track.
+    pub fn set_razor_edits(&self, edits: &[RazorEdit]) -> ReaperResult<()> {
+        unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_set_media_track_info_set_razor_edits(self.raw()?, edits);
+        }
+        Ok(())
+    }
+
     pub fn add_item(&self) -> Result<Item, ReaperFunctionError> {
         self.load_and_check_if_necessary_or_complain();
         let raw_item = unsafe {
@@ -190,6 +235,38 @@ impl Track {
         unsafe { reaper.get_set_media_track_info_set_custom_color(self.raw_internal(), value) };
     }
 
+    /// Returns the name of the layout used in the given area or `None` if the default layout is
+    /// used.
+    pub fn layout(&self, area: TrackArea) -> Option<ReaperString> {
+        if self.load_and_check_if_necessary_or_err().is_err() {
+            return None;
+        }
+        let name = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_set_media_track_info_get_layout(self.raw_internal(), area, |n| n.to_owned())
+        }?;
+        if name.to_str().is_empty() {
+            return None;
+        }
+        Some(name)
+    }
+
+    /// Sets the layout used in the given area. Pass `None` in order to use the default layout.
+    pub fn set_layout(&self, area: TrackArea, name: Option<&str>) -> ReaperResult<()> {
+        self.load_and_check_if_necessary_or_err()?;
+        unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_set_media_track_info_set_layout(
+                    self.raw_internal(),
+                    area,
+                    name.unwrap_or_default(),
+                );
+        }
+        Ok(())
+    }
+
     pub fn set_anticipative_fx_enabled(&self, value: bool) -> ReaperResult<()> {
         self.load_and_check_if_necessary_or_err()?;
         let perf_flags = self.perf_flags_internal();
@@ -579,6 +656,151 @@ impl Track {
         result as _
     }
 
+    pub fn meter(&self) -> TrackMeter {
+        TrackMeter::new(self.clone())
+    }
+
+    pub fn item_positioning_mode(&self) -> ReaperResult<ItemPositioningMode> {
+        let raw = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_media_track_info_value(self.raw()?, TrackAttributeKey::FreeModeEx)
+        };
+        Ok(ItemPositioningMode::from_raw(raw as i32))
+    }
+
+    pub fn set_item_positioning_mode(&self, mode: ItemPositioningMode) -> ReaperResult<()> {
+        let reaper = Reaper::get().medium_reaper();
+        unsafe {
+            reaper.set_media_track_info_value(
+                self.raw()?,
+                TrackAttributeKey::FreeModeEx,
+                mode.to_raw() as f64,
+            )?;
+        }
+        reaper.update_timeline();
+        Ok(())
+    }
+
+    /// Returns whether this track is in fixed lanes mode.
+    pub fn has_fixed_lanes(&self) -> bool {
+        matches!(
+            self.item_positioning_mode(),
+            Ok(ItemPositioningMode::FixedLanes)
+        )
+    }
+
+    /// Returns the number of fixed lanes.
+    pub fn lane_count(&self) -> u32 {
+        if self.load_and_check_if_necessary_or_err().is_err() {
+            return 0;
+        }
+        let result = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_media_track_info_value(self.raw_internal(), TrackAttributeKey::NumFixedLanes)
+        };
+        result as _
+    }
+
+    /// Sets the number of fixed lanes.
+    pub fn set_lane_count(&self, count: u32) -> ReaperResult<()> {
+        unsafe {
+            Reaper::get().medium_reaper().set_media_track_info_value(
+                self.raw()?,
+                TrackAttributeKey::NumFixedLanes,
+                count as f64,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the fixed lane at the given index.
+    pub fn lane_by_index(&self, index: u32) -> Option<TrackLane> {
+        if index >= self.lane_count() {
+            return None;
+        }
+        Some(TrackLane::new(self.clone(), index))
+    }
+
+    /// Returns all fixed lanes, from top to bottom.
+    pub fn lanes(&self) -> impl ExactSizeIterator<Item = TrackLane> + 'static {
+        let track = self.clone();
+        (0..self.lane_count()).map(move |i| TrackLane::new(track.clone(), i))
+    }
+
+    pub fn lane_settings(&self) -> ReaperResult<BitFlags<FixedLaneSettingsFlag>> {
+        let raw = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_media_track_info_value(self.raw()?, TrackAttributeKey::LaneSettings)
+        };
+        Ok(BitFlags::from_bits_truncate(raw as u8))
+    }
+
+    pub fn set_lane_settings(&self, settings: BitFlags<FixedLaneSettingsFlag>) -> ReaperResult<()> {
+        unsafe {
+            Reaper::get().medium_reaper().set_media_track_info_value(
+                self.raw()?,
+                TrackAttributeKey::LaneSettings,
+                settings.bits() as f64,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether all lanes are playing.
+    #[allow(clippy::float_cmp)]
+    pub fn all_lanes_play(&self) -> bool {
+        self.prop_numeric_value(TrackAttributeKey::AllLanesPlay) == 1.0
+    }
+
+    /// Lets either all or no lanes play.
+    pub fn set_all_lanes_play(&self, play: bool) {
+        self.set_prop_enabled(TrackAttributeKey::AllLanesPlay, play);
+    }
+
+    /// Returns the lane which is the current comping target.
+    pub fn comping_target_lane(&self) -> ReaperResult<Option<TrackLane>> {
+        let lane = self
+            .comping_target_lane_index()?
+            .map(|i| TrackLane::new(self.clone(), i));
+        Ok(lane)
+    }
+
+    /// Returns the index of the lane which is the current comping target.
+    ///
+    /// REAPER doesn't expose the comping target as track attribute, so this is read from the
+    /// `LANEREC` line of the track chunk.
+    pub(crate) fn comping_target_lane_index(&self) -> ReaperResult<Option<u32>> {
+        let chunk = self.chunk(MAX_TRACK_CHUNK_SIZE, ChunkCacheHint::UndoMode)?;
+        let index = chunk
+            .region()
+            .find_line_starting_with(LANE_REC_PREFIX)
+            .and_then(|line| parse_comping_target_lane(&line.content()));
+        Ok(index)
+    }
+
+    /// Sets the comping target (`None` means no lane is the comping target).
+    pub(crate) fn set_comping_target_lane_index(&self, index: Option<u32>) -> ReaperResult<()> {
+        let mut chunk = self.chunk(MAX_TRACK_CHUNK_SIZE, ChunkCacheHint::NormalMode)?;
+        let line = chunk
+            .region()
+            .find_line_starting_with(LANE_REC_PREFIX)
+            .ok_or("track has no lanes")?;
+        let new_line = format_comping_target_lane(&line.content(), index);
+        chunk.replace_region(&line, &new_line);
+        self.set_chunk(chunk)
+    }
+
+    /// Returns the approximate current level of the recording input of this track.
+    ///
+    /// Returns `None` if no recording input is set or if the level is not available.
+    pub fn recording_input_level(&self) -> Option<ReaperVolumeValue> {
+        let input = self.recording_input()?;
+        Reaper::get().input_level(input)
+    }
+
     pub fn volume(&self) -> ReaperVolumeValue {
         if self.load_and_check_if_necessary_or_err().is_err() {
             return ReaperVolumeValue::MIN;
@@ -1367,6 +1589,19 @@ impl Track {
         )
     }
 
+    /// Creates a hardware output send with default properties.
+    pub fn add_hardware_output_send(&self) -> ReaperResult<TrackRoute> {
+        self.load_and_check_if_necessary_or_err()?;
+        // Hardware output sends come first, so the returned index is also the route index.
+        let send_index = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .create_track_send(self.raw_internal(), HardwareOutput)?
+        };
+        let route = TrackRoute::new(self.clone(), TrackSendDirection::Send, send_index);
+        Ok(route)
+    }
+
     pub fn receives(&self) -> impl ExactSizeIterator<Item = TrackRoute> + '_ {
         if self.load_and_check_if_necessary_or_err().is_err() {
             return Either::Left(iter::empty());
@@ -1752,6 +1987,28 @@ fn get_track_project_raw(media_track: MediaTrack) -> Option<ReaProject> {
     }
 }
 
+const LANE_REC_PREFIX: &str = "LANEREC ";
+
+/// The first value of the `LANEREC` line is the comping target lane (-1 if none).
+///
+/// REAPER doesn't officially document the `LANEREC` line. The meaning of the first value is
+/// derived from REAPER 7 track chunks, where it follows the lane whose comping button is enabled.
+fn parse_comping_target_lane(line: &str) -> Option<u32> {
+    let value: i32 = line.split_whitespace().nth(1)?.parse().ok()?;
+    value.try_into().ok()
+}
+
+/// Replaces the first value of the given `LANEREC` line, leaving the rest of the line untouched.
+fn format_comping_target_lane(line: &str, index: Option<u32>) -> String {
+    let Some(current) = line.split_whitespace().nth(1) else {
+        return line.to_string();
+    };
+    let start = current.as_ptr() as usize - line.as_ptr() as usize;
+    let end = start + current.len();
+    let value = index.map(|i| i as i32).unwrap_or(-1);
+    format!("{}{value}{}", &line[..start], &line[end..])
+}
+
 fn get_auto_arm_chunk_line(chunk: &Chunk) -> Option<ChunkRegion> {
     chunk.region().find_line_starting_with("AUTO_RECARM 1")
 }
@@ -1814,3 +2071,37 @@ fn build_track_ui_flags(
     }
     flags
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // As written by REAPER 7 for a track with fixed lanes but without comping target
+    const LANE_REC_LINE: &str = "LANEREC -1 -1 -1 0";
+
+    #[test]
+    fn parse_comping_target_lane_line() {
+        assert_eq!(parse_comping_target_lane(LANE_REC_LINE), None);
+        assert_eq!(parse_comping_target_lane("LANEREC 2 -1 -1 0"), Some(2));
+        assert_eq!(parse_comping_target_lane("LANEREC"), None);
+    }
+
+    #[test]
+    fn comping_target_lane_round_trip() {
+        let line = format_comping_target_lane(LANE_REC_LINE, Some(2));
+        assert_eq!(line, "LANEREC 2 -1 -1 0");
+        assert_eq!(parse_comping_target_lane(&line), Some(2));
+        let line = format_comping_target_lane(&line, None);
+        assert_eq!(line, LANE_REC_LINE);
+        assert_eq!(parse_comping_target_lane(&line), None);
+    }
+
+    #[test]
+    fn format_comping_target_lane_keeps_rest_of_line() {
+        assert_eq!(
+            format_comping_target_lane("  LANEREC 10\t-1  -1 0\n", Some(3)),
+            "  LANEREC 3\t-1  -1 0\n"
+        );
+        assert_eq!(format_comping_target_lane("LANEREC", Some(3)), "LANEREC");
+    }
+}
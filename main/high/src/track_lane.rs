@@ -0,0 +1,111 @@
+use crate::error::ReaperResult;
+use crate::{Item, Reaper, Track};
+use reaper_medium::{LanePlayState, TrackAttributeKey};
+
+/// A fixed lane of a track.
+///
+/// Only meaningful if the track is in fixed lanes mode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackLane {
+    track: Track,
+    index: u32,
+}
+
+impl TrackLane {
+    pub(crate) fn new(track: Track, index: u32) -> Self {
+        Self { track, index }
+    }
+
+    pub fn track(&self) -> &Track {
+        &self.track
+    }
+
+    /// Returns the zero-based index of this lane (lane 0 is the top lane).
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns whether this lane still exists.
+    pub fn is_available(&self) -> bool {
+        self.track.is_available() && self.index < self.track.lane_count()
+    }
+
+    /// Returns the name of this lane.
+    ///
+    /// Returns `None` if the track is not in fixed lanes mode.
+    pub fn name(&self) -> ReaperResult<Option<String>> {
+        let name = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_set_media_track_info_get_lane_name(self.track.raw()?, self.index, |n| {
+                    n.to_str().to_owned()
+                })
+        };
+        Ok(name)
+    }
+
+    pub fn set_name(&self, name: &str) -> ReaperResult<()> {
+        unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_set_media_track_info_set_lane_name(self.track.raw()?, self.index, name);
+        }
+        Ok(())
+    }
+
+    pub fn play_state(&self) -> ReaperResult<LanePlayState> {
+        let raw = unsafe {
+            Reaper::get().medium_reaper().get_media_track_info_value(
+                self.track.raw()?,
+                TrackAttributeKey::LanePlays(self.index),
+            )
+        };
+        Ok(LanePlayState::from_raw(raw as i32))
+    }
+
+    pub fn set_play_state(&self, state: LanePlayState) -> ReaperResult<()> {
+        unsafe {
+            Reaper::get().medium_reaper().set_media_track_info_value(
+                self.track.raw()?,
+                TrackAttributeKey::LanePlays(self.index),
+                state.to_raw() as f64,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn is_playing(&self) -> ReaperResult<bool> {
+        Ok(self.play_state()?.is_playing())
+    }
+
+    /// Makes this lane the only one that plays.
+    pub fn activate(&self) -> ReaperResult<()> {
+        self.set_play_state(LanePlayState::PlaysExclusively)
+    }
+
+    /// Returns whether this lane is the comping target of its track.
+    pub fn is_comping_target(&self) -> ReaperResult<bool> {
+        Ok(self.track.comping_target_lane_index()? == Some(self.index))
+    }
+
+    /// Makes this lane the comping target of its track.
+    pub fn make_comping_target(&self) -> ReaperResult<()> {
+        self.track.set_comping_target_lane_index(Some(self.index))
+    }
+
+    /// Switches the comp of the track to this lane.
+    ///
+    /// Makes this lane the comping target and lets it play exclusively.
+    pub fn switch_comp(&self) -> ReaperResult<()> {
+        self.make_comping_target()?;
+        self.activate()
+    }
+
+    /// Returns the items placed on this lane.
+    pub fn items(&self) -> impl Iterator<Item = Item> + 'static {
+        let index = self.index;
+        self.track
+            .items()
+            .filter(move |item| item.fixed_lane() == index)
+    }
+}
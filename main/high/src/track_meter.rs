@@ -0,0 +1,132 @@
+use crate::error::ReaperResult;
+use crate::{Reaper, Track};
+use reaper_medium::{Db, ReaperVolumeValue, TrackAttributeKey, TrackVuMode};
+
+/// Gives access to the VU meter of a track.
+///
+/// All values are read on demand, so this is supposed to be polled regularly (e.g. on each main
+/// loop cycle) when driving meter displays.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackMeter {
+    track: Track,
+}
+
+/// The meter readout of one track channel.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ChannelMeterValue {
+    /// Current peak.
+    pub peak: ReaperVolumeValue,
+    /// Peak hold.
+    pub peak_hold: Db,
+}
+
+impl TrackMeter {
+    pub(crate) fn new(track: Track) -> Self {
+        Self { track }
+    }
+
+    pub fn track(&self) -> &Track {
+        &self.track
+    }
+
+    /// Returns the number of metered channels.
+    pub fn channel_count(&self) -> u32 {
+        self.track.channel_count()
+    }
+
+    pub fn vu_mode(&self) -> ReaperResult<TrackVuMode> {
+        let raw = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_media_track_info_value(self.track.raw()?, TrackAttributeKey::VuMode)
+        };
+        Ok(TrackVuMode::from_raw(raw as i32))
+    }
+
+    pub fn set_vu_mode(&self, mode: TrackVuMode) -> ReaperResult<()> {
+        unsafe {
+            Reaper::get().medium_reaper().set_media_track_info_value(
+                self.track.raw()?,
+                TrackAttributeKey::VuMode,
+                mode.to_raw() as f64,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current peak of the given channel.
+    pub fn peak(&self, channel: u32) -> ReaperResult<ReaperVolumeValue> {
+        let peak = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .track_get_peak_info(self.track.raw()?, channel)
+        };
+        Ok(peak)
+    }
+
+    /// Returns the peak hold of the given channel.
+    pub fn peak_hold(&self, channel: u32) -> ReaperResult<Db> {
+        self.query_peak_hold(channel, false)
+    }
+
+    /// Returns the peak hold of the given channel and resets it.
+    pub fn take_peak_hold(&self, channel: u32) -> ReaperResult<Db> {
+        self.query_peak_hold(channel, true)
+    }
+
+    /// Resets the peak hold of the given channel.
+    pub fn reset_peak_hold(&self, channel: u32) -> ReaperResult<()> {
+        self.query_peak_hold(channel, true)?;
+        Ok(())
+    }
+
+    /// Resets the peak holds of all channels.
+    pub fn reset_all_peak_holds(&self) -> ReaperResult<()> {
+        for ch in 0..self.channel_count() {
+            self.reset_peak_hold(ch)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current meter readout of the given channel.
+    pub fn channel_value(&self, channel: u32) -> ReaperResult<ChannelMeterValue> {
+        let value = ChannelMeterValue {
+            peak: self.peak(channel)?,
+            peak_hold: self.peak_hold(channel)?,
+        };
+        Ok(value)
+    }
+
+    /// Returns the current meter readouts of all channels.
+    pub fn channel_values(&self) -> ReaperResult<Vec<ChannelMeterValue>> {
+        (0..self.channel_count())
+            .map(|ch| self.channel_value(ch))
+            .collect()
+    }
+
+    /// Returns the two loudness readouts.
+    ///
+    /// Returns `None` if this is neither the master track nor a track whose VU meters are set
+    /// to display loudness.
+    pub fn loudness_values(&self) -> ReaperResult<Option<[ChannelMeterValue; 2]>> {
+        if !self.track.is_master_track() && !self.vu_mode()?.mode.is_loudness() {
+            return Ok(None);
+        }
+        let values = [
+            self.channel_value(LOUDNESS_CHANNEL)?,
+            self.channel_value(LOUDNESS_CHANNEL + 1)?,
+        ];
+        Ok(Some(values))
+    }
+
+    fn query_peak_hold(&self, channel: u32, clear: bool) -> ReaperResult<Db> {
+        let db = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .track_get_peak_hold_db(self.track.raw()?, channel, clear)
+        };
+        Ok(db)
+    }
+}
+
+const LOUDNESS_CHANNEL: u32 = 1024;
@@ -0,0 +1,222 @@
+use crate::guid::Guid;
+use crate::track::get_media_track_guid;
+use crate::{Envelope, Fx, FxParameter, Project, Reaper, Track, TrackRoute};
+use reaper_medium::ProjectContext::Proj;
+use reaper_medium::{MediaTrack, ReaProject, TrackEnvelope, TrackSendDirection};
+
+/// A lightweight handle of a track, used in change events.
+///
+/// In contrast to [`Track`], creating it is essentially a no-op because it's just the track
+/// pointer and its project. The GUID is only queried on demand. Use [`to_track()`](Self::to_track)
+/// to get the full track while the track still exists.
+///
+/// Handles of removed tracks contain a dangling pointer, so they can't be converted to a full
+/// track anymore. Removal events carry the GUID instead.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TrackRef {
+    raw: MediaTrack,
+    project: ReaProject,
+}
+
+impl TrackRef {
+    /// If the project is not given, it's queried from REAPER (falling back to the current project
+    /// in REAPER < 5.95).
+    pub fn new(raw: MediaTrack, project: Option<ReaProject>) -> TrackRef {
+        let project = project.unwrap_or_else(|| {
+            let reaper = Reaper::get();
+            unsafe {
+                reaper
+                    .medium_reaper()
+                    .get_set_media_track_info_get_project(raw)
+            }
+            .unwrap_or_else(|| reaper.current_project().raw())
+        });
+        TrackRef { raw, project }
+    }
+
+    pub fn raw(self) -> MediaTrack {
+        self.raw
+    }
+
+    pub fn project(self) -> Project {
+        Project::new(self.project)
+    }
+
+    pub fn is_valid(self) -> bool {
+        Reaper::get()
+            .medium_reaper()
+            .validate_ptr_2(Proj(self.project), self.raw)
+    }
+
+    /// Returns `None` if the track doesn't exist anymore.
+    pub fn guid(self) -> Option<Guid> {
+        if !self.is_valid() {
+            return None;
+        }
+        Some(get_media_track_guid(self.raw))
+    }
+
+    /// Returns the full track or `None` if the track doesn't exist anymore.
+    pub fn to_track(self) -> Option<Track> {
+        if !self.is_valid() {
+            return None;
+        }
+        Some(Track::new(self.raw, Some(self.project)))
+    }
+}
+
+/// A lightweight handle of a track FX, used in change events.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct FxRef {
+    track: TrackRef,
+    is_input_fx: bool,
+    guid: Guid,
+}
+
+impl FxRef {
+    pub fn new(track: TrackRef, is_input_fx: bool, guid: Guid) -> FxRef {
+        FxRef {
+            track,
+            is_input_fx,
+            guid,
+        }
+    }
+
+    pub fn track(self) -> TrackRef {
+        self.track
+    }
+
+    pub fn is_input_fx(self) -> bool {
+        self.is_input_fx
+    }
+
+    pub fn guid(self) -> Guid {
+        self.guid
+    }
+
+    /// Returns whether the track and the FX still exist.
+    pub fn is_valid(self) -> bool {
+        self.to_fx().map(|fx| fx.is_available()).unwrap_or(false)
+    }
+
+    /// Returns the full FX or `None` if the track doesn't exist anymore.
+    pub fn to_fx(self) -> Option<Fx> {
+        let track = self.track.to_track()?;
+        let chain = if self.is_input_fx {
+            track.input_fx_chain()
+        } else {
+            track.normal_fx_chain()
+        };
+        Some(chain.fx_by_guid(&self.guid))
+    }
+}
+
+/// A lightweight handle of a send or receive, used in change events.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TrackRouteRef {
+    track: TrackRef,
+    direction: TrackSendDirection,
+    /// For send direction, the first indices are hardware outputs if there are any!
+    index: u32,
+}
+
+impl TrackRouteRef {
+    pub fn new(track: TrackRef, direction: TrackSendDirection, index: u32) -> TrackRouteRef {
+        TrackRouteRef {
+            track,
+            direction,
+            index,
+        }
+    }
+
+    pub fn track(self) -> TrackRef {
+        self.track
+    }
+
+    pub fn direction(self) -> TrackSendDirection {
+        self.direction
+    }
+
+    pub fn index(self) -> u32 {
+        self.index
+    }
+
+    /// Returns whether the track still exists and has a route at this index.
+    pub fn is_valid(self) -> bool {
+        self.to_route()
+            .map(|route| route.is_available())
+            .unwrap_or(false)
+    }
+
+    /// Returns the full route or `None` if the track doesn't exist anymore.
+    pub fn to_route(self) -> Option<TrackRoute> {
+        let route = TrackRoute::new(self.track.to_track()?, self.direction, self.index);
+        Some(route)
+    }
+}
+
+/// A lightweight handle of a track FX parameter, used in change events.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct FxParameterRef {
+    fx: FxRef,
+    index: u32,
+}
+
+impl FxParameterRef {
+    pub fn new(fx: FxRef, index: u32) -> FxParameterRef {
+        FxParameterRef { fx, index }
+    }
+
+    pub fn fx(self) -> FxRef {
+        self.fx
+    }
+
+    pub fn index(self) -> u32 {
+        self.index
+    }
+
+    /// Returns whether the FX still exists and has a parameter at this index.
+    pub fn is_valid(self) -> bool {
+        self.to_parameter()
+            .map(|parameter| parameter.is_available())
+            .unwrap_or(false)
+    }
+
+    /// Returns the full parameter or `None` if the track doesn't exist anymore.
+    pub fn to_parameter(self) -> Option<FxParameter> {
+        Some(self.fx.to_fx()?.parameter_by_index(self.index))
+    }
+}
+
+/// A lightweight handle of a track envelope, used in change events.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EnvelopeRef {
+    track: TrackRef,
+    raw: TrackEnvelope,
+}
+
+impl EnvelopeRef {
+    pub fn new(track: TrackRef, raw: TrackEnvelope) -> EnvelopeRef {
+        EnvelopeRef { track, raw }
+    }
+
+    pub fn track(self) -> TrackRef {
+        self.track
+    }
+
+    pub fn raw(self) -> TrackEnvelope {
+        self.raw
+    }
+
+    pub fn is_valid(self) -> bool {
+        self.track.is_valid()
+            && Reaper::get()
+                .medium_reaper()
+                .validate_ptr_2(Proj(self.track.project), self.raw)
+    }
+
+    /// Returns the full envelope or `None` if the track doesn't exist anymore.
+    pub fn to_envelope(self) -> Option<Envelope> {
+        Some(Envelope::new(self.track.to_track()?, self.raw))
+    }
+}
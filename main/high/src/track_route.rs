@@ -1,9 +1,12 @@
 use crate::{Pan, Reaper, Track};
 
 use crate::error::ReaperResult;
+use helgoboss_midi::Channel;
 use reaper_medium::{
-    AutomationMode, EditMode, MediaTrack, ReaperFunctionError, ReaperString, ReaperVolumeValue,
-    TrackSendAttributeKey, TrackSendCategory, TrackSendDirection, TrackSendRef, VolumeAndPan,
+    AutomationMode, EditMode, EnvChunkName, MediaTrack, ReaperFunctionError, ReaperString,
+    ReaperVolumeValue, SendDestinationChannels, SendMidiFlags, SendMode, SendSourceChannels,
+    TrackEnvelope, TrackSendAttributeKey, TrackSendCategory, TrackSendDirection, TrackSendRef,
+    VolumeAndPan,
 };
 use std::fmt;
 use TrackSendDirection::*;
@@ -234,6 +237,137 @@ impl TrackRoute {
         self.set_prop_numeric_value(TrackSendAttributeKey::DstChan, raw_dst_channel as _)
     }
 
+    pub fn send_mode(&self) -> SendMode {
+        let raw_mode = self.prop_numeric_value(TrackSendAttributeKey::SendMode) as i32;
+        SendMode::from_raw(raw_mode)
+    }
+
+    pub fn set_send_mode(&self, mode: SendMode) -> ReaperResult<()> {
+        self.set_prop_numeric_value(TrackSendAttributeKey::SendMode, mode.to_raw() as _)
+    }
+
+    /// Returns `None` if this route doesn't send audio.
+    pub fn src_channels(&self) -> Option<SendSourceChannels> {
+        let raw = self.prop_numeric_value(TrackSendAttributeKey::SrcChan) as i32;
+        SendSourceChannels::from_raw(raw)
+    }
+
+    /// Passing `None` disables sending audio.
+    pub fn set_src_channels(&self, channels: Option<SendSourceChannels>) -> ReaperResult<()> {
+        let raw = SendSourceChannels::option_to_raw(channels);
+        self.set_prop_numeric_value(TrackSendAttributeKey::SrcChan, raw as _)
+    }
+
+    pub fn dst_channels(&self) -> SendDestinationChannels {
+        let raw = self.prop_numeric_value(TrackSendAttributeKey::DstChan) as i32;
+        SendDestinationChannels::from_raw(raw)
+    }
+
+    pub fn set_dst_channels(&self, channels: SendDestinationChannels) -> ReaperResult<()> {
+        self.set_prop_numeric_value(TrackSendAttributeKey::DstChan, channels.to_raw() as _)
+    }
+
+    pub fn audio_is_enabled(&self) -> bool {
+        self.src_channels().is_some()
+    }
+
+    /// Enabling audio on a route that doesn't send audio yet uses the first stereo channel pair
+    /// as source.
+    pub fn set_audio_enabled(&self, enabled: bool) -> ReaperResult<()> {
+        if self.audio_is_enabled() == enabled {
+            return Ok(());
+        }
+        let channels = if enabled {
+            Some(SendSourceChannels::stereo(0))
+        } else {
+            None
+        };
+        self.set_src_channels(channels)
+    }
+
+    pub fn midi_flags(&self) -> SendMidiFlags {
+        let raw = self.prop_numeric_value(TrackSendAttributeKey::MidiFlags) as i32;
+        SendMidiFlags::from_raw(raw)
+    }
+
+    pub fn set_midi_flags(&self, flags: SendMidiFlags) -> ReaperResult<()> {
+        self.set_prop_numeric_value(TrackSendAttributeKey::MidiFlags, flags.to_raw() as _)
+    }
+
+    pub fn midi_is_enabled(&self) -> bool {
+        self.midi_flags().enabled
+    }
+
+    pub fn set_midi_enabled(&self, enabled: bool) -> ReaperResult<()> {
+        self.modify_midi_flags(|flags| flags.enabled = enabled)
+    }
+
+    /// Sets the MIDI source bus and channel (`None` means all).
+    pub fn set_midi_source(&self, bus: Option<u8>, channel: Option<Channel>) -> ReaperResult<()> {
+        self.modify_midi_flags(|flags| {
+            flags.src_bus = bus;
+            flags.src_channel = channel;
+        })
+    }
+
+    /// Sets the MIDI destination bus (`None` means all) and channel (`None` means original).
+    pub fn set_midi_destination(
+        &self,
+        bus: Option<u8>,
+        channel: Option<Channel>,
+    ) -> ReaperResult<()> {
+        self.modify_midi_flags(|flags| {
+            flags.dst_bus = bus;
+            flags.dst_channel = channel;
+        })
+    }
+
+    fn modify_midi_flags(&self, modify: impl FnOnce(&mut SendMidiFlags)) -> ReaperResult<()> {
+        let mut flags = self.midi_flags();
+        modify(&mut flags);
+        self.set_midi_flags(flags)
+    }
+
+    /// Returns the envelope with the given name, e.g. [`EnvChunkName::VolEnv`],
+    /// [`EnvChunkName::PanEnv`] or [`EnvChunkName::MuteEnv`].
+    pub fn envelope(&self, name: EnvChunkName) -> Option<TrackEnvelope> {
+        let (category, index) = self.category_with_index();
+        unsafe {
+            Reaper::get().medium_reaper().get_track_send_info_env(
+                self.track().raw_unchecked(),
+                category,
+                index,
+                name,
+            )
+        }
+    }
+
+    /// Returns the names of the hardware output channels this route sends to, joined by " / ".
+    ///
+    /// Returns `None` if this is not a hardware output send, if it doesn't send audio or if it
+    /// sends to ReaRoute.
+    pub fn hardware_output_name(&self) -> Option<String> {
+        let (category, _) = self.category_with_index();
+        if category != TrackSendCategory::HardwareOutput {
+            return None;
+        }
+        let src_channels = self.src_channels()?;
+        let dst_channels = self.dst_channels();
+        if dst_channels.rea_route {
+            return None;
+        }
+        let count = if dst_channels.mix_to_mono {
+            1
+        } else {
+            src_channels.count
+        };
+        let reaper = Reaper::get();
+        let names: Option<Vec<String>> = (dst_channels.offset..dst_channels.offset + count)
+            .map(|i| reaper.output_channel_name(i))
+            .collect();
+        Some(names?.join(" / "))
+    }
+
     pub fn set_automation_mode(&self, mode: AutomationMode) -> ReaperResult<()> {
         self.set_prop_numeric_value(TrackSendAttributeKey::AutoMode, mode.to_raw() as _)
     }
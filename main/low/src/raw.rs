@@ -25,6 +25,9 @@ pub use super::bindings::root::{
 
 pub use super::file_in_project_callback::file_in_project_ex2_t;
 
+/// Opaque joystick device type defined by REAPER.
+pub use super::bindings::root::reaper_functions::joystick_device;
+
 /// Structs, types and constants defined by `swell.h` (on Linux and Mac OS X) and
 /// `windows.h` (on Windows).
 ///
@@ -0,0 +1,62 @@
+use crate::{Hwnd, ReaperString};
+
+/// Registration of a dockable window, created via [`Reaper::dock_window_add_ex()`].
+///
+/// Removes the window from the docker when dropped. Drop it before the window is destroyed.
+///
+/// [`Reaper::dock_window_add_ex()`]: crate::Reaper::dock_window_add_ex
+#[derive(Debug)]
+pub struct DockRegistration {
+    low: reaper_low::Reaper,
+    window: Hwnd,
+}
+
+impl DockRegistration {
+    pub(crate) fn new(low: reaper_low::Reaper, window: Hwnd) -> Self {
+        Self { low, window }
+    }
+
+    /// Returns the registered window.
+    pub fn window(&self) -> Hwnd {
+        self.window
+    }
+}
+
+impl Drop for DockRegistration {
+    fn drop(&mut self) {
+        unsafe {
+            self.low.DockWindowRemove(self.window.as_ptr());
+        }
+    }
+}
+
+/// Registration of a window in screensets, created via [`Reaper::screenset_register_new()`].
+///
+/// Unregisters the window from screensets when dropped.
+///
+/// [`Reaper::screenset_register_new()`]: crate::Reaper::screenset_register_new
+#[derive(Debug)]
+pub struct ScreensetRegistration {
+    low: reaper_low::Reaper,
+    // REAPER keeps the pointer, so we need to keep the string alive until unregistration.
+    id: ReaperString,
+}
+
+impl ScreensetRegistration {
+    pub(crate) fn new(low: reaper_low::Reaper, id: ReaperString) -> Self {
+        Self { low, id }
+    }
+
+    /// Returns the screenset ID of the window.
+    pub fn id(&self) -> &ReaperString {
+        &self.id
+    }
+}
+
+impl Drop for ScreensetRegistration {
+    fn drop(&mut self) {
+        unsafe {
+            self.low.screenset_unregister(self.id.as_ptr() as *mut _);
+        }
+    }
+}
@@ -130,3 +130,46 @@ pub enum SetTrackUiFlags {
 pub enum GetThemeColorFlags {
     OriginalColor = 1,
 }
+
+/// Defines how a theme color is set.
+#[enumflags2::bitflags]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u32)]
+pub enum SetThemeColorFlags {
+    /// Bypasses color transformations (e.g. brightness/contrast adjustments of the theme).
+    BypassTransformations = 1,
+}
+
+/// Settings of a track in fixed lanes mode (`C_LANESETTINGS`).
+#[enumflags2::bitflags]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u8)]
+pub enum FixedLaneSettingsFlag {
+    /// Auto-removes empty lanes at the bottom.
+    AutoRemoveEmptyLanes = 1,
+    /// Doesn't auto-comp new recordings.
+    NoAutoCompNewRecording = 2,
+    /// Newly recorded lanes play exclusively (instead of being added in layers).
+    NewRecordingPlaysExclusively = 4,
+    /// Big lanes (instead of small lanes).
+    BigLanes = 8,
+    /// Adds new recordings at the bottom (instead of recording into the first available lane).
+    AddNewRecordingAtBottom = 16,
+    /// Hides the lane buttons.
+    HideLaneButtons = 32,
+}
+
+/// Modifier keys of a mouse modifier assignment.
+#[enumflags2::bitflags]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u8)]
+pub enum MouseModifierFlag {
+    /// Shift key.
+    Shift = 1,
+    /// Control key (command key on macOS).
+    Control = 2,
+    /// Alt key (option key on macOS).
+    Alt = 4,
+    /// Windows key (control key on macOS).
+    Win = 8,
+}
@@ -1,10 +1,10 @@
 use crate::{
-    AccelMsg, ActionValueChange, CommandId, Hmenu, Hwnd, HwndInfoType, KbdSectionInfo,
-    MenuHookFlag, ReaProject, ReaperStr, SectionContext, WindowContext,
+    AccelMsg, ActionValueChange, CommandId, Hidden, Hmenu, Hwnd, HwndInfoType, KbdSectionInfo,
+    MenuHookFlag, ReaProject, ReaperStr, ScreensetAction, SectionContext, WindowContext,
 };
 use reaper_low::raw::{HWND, INT_PTR, MSG};
 use reaper_low::{firewall, raw};
-use std::ffi::c_char;
+use std::ffi::{c_char, c_void};
 use std::os::raw::c_int;
 use std::ptr::{null, NonNull};
 
@@ -159,6 +159,61 @@ pub(crate) extern "C" fn delegating_toolbar_icon_map<T: ToolbarIconMap>(
     .unwrap_or(null())
 }
 
+/// Consumers need to implement this trait in order to let windows take part in screensets (saving
+/// and restoring their state and dock position).
+pub trait ScreensetCallback {
+    /// The actual callback function invoked by REAPER whenever it needs something from a window
+    /// registered via [`Reaper::screenset_register_new()`].
+    ///
+    /// See [`ScreensetAction`] for the expected return values.
+    ///
+    /// [`Reaper::screenset_register_new()`]: crate::Reaper::screenset_register_new
+    fn call(id: &ReaperStr, action: ScreensetAction) -> isize;
+}
+
+pub(crate) extern "C" fn delegating_screenset_callback<T: ScreensetCallback>(
+    action: c_int,
+    id: *const c_char,
+    _param: *mut c_void,
+    action_parm: *mut c_void,
+    action_parm_size: c_int,
+) -> raw::LRESULT {
+    firewall(|| {
+        let id = unsafe { ReaperStr::from_ptr(id) };
+        let size = action_parm_size.max(0) as usize;
+        let action = match action {
+            SCREENSET_ACTION_GETHWND => ScreensetAction::GetWindow,
+            SCREENSET_ACTION_IS_DOCKED => ScreensetAction::IsDocked,
+            SCREENSET_ACTION_SWITCH_DOCK => ScreensetAction::SwitchDock,
+            SCREENSET_ACTION_LOAD_STATE if action_parm.is_null() && size == 0 => {
+                ScreensetAction::Hide
+            }
+            SCREENSET_ACTION_LOAD_STATE if !action_parm.is_null() => {
+                ScreensetAction::LoadState(unsafe {
+                    std::slice::from_raw_parts(action_parm as *const u8, size)
+                })
+            }
+            SCREENSET_ACTION_SAVE_STATE if !action_parm.is_null() => {
+                ScreensetAction::SaveState(unsafe {
+                    std::slice::from_raw_parts_mut(action_parm as *mut u8, size)
+                })
+            }
+            SCREENSET_ACTION_WANT_STATE_SIZE => ScreensetAction::WantStateSize,
+            x => ScreensetAction::Unknown(Hidden(x)),
+        };
+        T::call(id, action)
+    })
+    .unwrap_or(0)
+}
+
+// See `reaper_plugin.h`
+const SCREENSET_ACTION_GETHWND: c_int = 0;
+const SCREENSET_ACTION_IS_DOCKED: c_int = 1;
+const SCREENSET_ACTION_SWITCH_DOCK: c_int = 4;
+const SCREENSET_ACTION_LOAD_STATE: c_int = 0x100;
+const SCREENSET_ACTION_SAVE_STATE: c_int = 0x101;
+const SCREENSET_ACTION_WANT_STATE_SIZE: c_int = 0x102;
+
 /// Consumers need to implement this trait in order to let REAPER know if a toggleable action is
 /// currently *on* or *off*.
 pub trait ToggleAction {
@@ -240,3 +295,56 @@ pub(crate) extern "C" fn delegating_hook_post_command_2<T: HookPostCommand2>(
         );
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr::null_mut;
+
+    struct TestScreenset;
+
+    impl ScreensetCallback for TestScreenset {
+        fn call(_: &ReaperStr, action: ScreensetAction) -> isize {
+            use ScreensetAction::*;
+            match action {
+                GetWindow => 1,
+                IsDocked => 2,
+                SwitchDock => 3,
+                Hide => 4,
+                WantStateSize => 5,
+                LoadState(state) => 100 + state.len() as isize,
+                SaveState(buffer) => {
+                    buffer[..3].copy_from_slice(b"abc");
+                    3
+                }
+                Unknown(_) => -1,
+            }
+        }
+    }
+
+    fn call(action: c_int, action_parm: *mut c_void, action_parm_size: c_int) -> raw::LRESULT {
+        delegating_screenset_callback::<TestScreenset>(
+            action,
+            b"id\0".as_ptr() as *const c_char,
+            null_mut(),
+            action_parm,
+            action_parm_size,
+        )
+    }
+
+    #[test]
+    fn screenset_actions() {
+        assert_eq!(call(0, null_mut(), 0), 1);
+        assert_eq!(call(1, null_mut(), 0), 2);
+        assert_eq!(call(4, null_mut(), 0), 3);
+        // Load state without state means hide
+        assert_eq!(call(0x100, null_mut(), 0), 4);
+        let mut state = *b"state";
+        assert_eq!(call(0x100, state.as_mut_ptr() as *mut c_void, 5), 105);
+        let mut buffer = [0u8; 16];
+        assert_eq!(call(0x101, buffer.as_mut_ptr() as *mut c_void, 16), 3);
+        assert_eq!(&buffer[..3], b"abc");
+        assert_eq!(call(0x102, null_mut(), 0), 5);
+        assert_eq!(call(8, null_mut(), 0), -1);
+    }
+}
@@ -0,0 +1,91 @@
+use reaper_low::raw;
+use std::ptr::NonNull;
+
+/// Owned joystick or game controller device, created via [`Reaper::joystick_create()`].
+///
+/// The state queries only return meaningful values after [`update()`](Self::update) has
+/// succeeded. Destroys the device when dropped. Must only be used in the main thread.
+///
+/// [`Reaper::joystick_create()`]: crate::Reaper::joystick_create
+#[derive(Debug)]
+pub struct OwnedJoystickDevice {
+    low: reaper_low::Reaper,
+    device: NonNull<raw::joystick_device>,
+}
+
+impl OwnedJoystickDevice {
+    pub(crate) fn new(low: reaper_low::Reaper, device: NonNull<raw::joystick_device>) -> Self {
+        Self { low, device }
+    }
+
+    /// Reads the current state from the hardware.
+    ///
+    /// Returns `false` if the device couldn't be read (e.g. because it has been disconnected).
+    pub fn update(&self) -> bool {
+        unsafe { self.low.joystick_update(self.device.as_ptr()) }
+    }
+
+    /// Returns the number of buttons, axes and POVs (hat switches) of this device.
+    pub fn info(&self) -> JoystickDeviceInfo {
+        let mut axis_count = 0;
+        let mut pov_count = 0;
+        let button_count = unsafe {
+            self.low
+                .joystick_getinfo(self.device.as_ptr(), &mut axis_count, &mut pov_count)
+        };
+        JoystickDeviceInfo {
+            button_count: button_count.max(0) as u32,
+            axis_count: axis_count.max(0) as u32,
+            pov_count: pov_count.max(0) as u32,
+        }
+    }
+
+    /// Returns the value of the given axis, usually between -1.0 and 1.0.
+    pub fn axis(&self, index: u32) -> f64 {
+        unsafe {
+            self.low
+                .joystick_getaxis(self.device.as_ptr(), index as i32)
+        }
+    }
+
+    /// Returns the value of the given POV (hat switch) in degrees.
+    ///
+    /// Returns `None` if the POV is centered (not pressed in any direction) or doesn't exist. REAPER
+    /// reports this as 655.35.
+    pub fn pov(&self, index: u32) -> Option<f64> {
+        let value = unsafe { self.low.joystick_getpov(self.device.as_ptr(), index as i32) };
+        if (0.0..360.0).contains(&value) {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the pressed buttons as bit mask (bit 0 = first button).
+    ///
+    /// Only the first 32 buttons are available.
+    pub fn button_mask(&self) -> u32 {
+        unsafe { self.low.joystick_getbuttonmask(self.device.as_ptr()) }
+    }
+
+    /// Returns the raw pointer.
+    pub fn as_ptr(&self) -> *mut raw::joystick_device {
+        self.device.as_ptr()
+    }
+}
+
+impl Drop for OwnedJoystickDevice {
+    fn drop(&mut self) {
+        unsafe {
+            self.low.joystick_destroy(self.device.as_ptr());
+        }
+    }
+}
+
+/// Capabilities of a joystick device as returned by [`OwnedJoystickDevice::info()`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct JoystickDeviceInfo {
+    pub button_count: u32,
+    pub axis_count: u32,
+    pub pov_count: u32,
+}
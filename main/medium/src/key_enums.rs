@@ -1,4 +1,4 @@
-use crate::{concat_reaper_strs, ReaperStr, ReaperStringArg};
+use crate::{concat_reaper_strs, ReaperStr, ReaperString, ReaperStringArg};
 
 use std::borrow::Cow;
 
@@ -349,6 +349,62 @@ pub enum TrackAttributeKey<'a> {
     ///
     /// [`update_timeline`]: struct.ReaperSession.html#method.update_timeline
     FreeMode,
+    /// Track item positioning mode.
+    ///
+    /// `*mut i32`
+    ///
+    /// - 0 → normal
+    /// - 1 → free item positioning
+    /// - 2 → fixed lanes
+    ///
+    /// Call [`update_timeline`] after changing.
+    ///
+    /// [`update_timeline`]: struct.ReaperSession.html#method.update_timeline
+    FreeModeEx,
+    /// Number of track fixed lanes.
+    ///
+    /// `*mut i32`
+    ///
+    /// Fine to set, but the returned value is read-only.
+    NumFixedLanes,
+    /// Fixed lane collapse state.
+    ///
+    /// `*mut char`
+    ///
+    /// - 1 → lanes collapsed
+    /// - 2 → track displays as non-fixed-lanes but hidden lanes exist
+    LanesCollapsed,
+    /// Fixed lane settings.
+    ///
+    /// `*mut char`
+    ///
+    /// - &1 → auto-remove empty lanes at bottom
+    /// - &2 → do not auto-comp new recording
+    /// - &4 → newly recorded lanes play exclusively (else add lanes in layers)
+    /// - &8 → big lanes (else small lanes)
+    /// - &16 → add new recording at bottom (else record into first available lane)
+    /// - &32 → hide lane buttons
+    LaneSettings,
+    /// Play state of the fixed lane with the given zero-based index.
+    ///
+    /// `*mut char`
+    ///
+    /// - 0 → lane doesn't play
+    /// - 1 → lane plays exclusively
+    /// - 2 → lane plays and other lanes also play
+    ///
+    /// Fine to set, but the returned value is read-only.
+    LanePlays(u32),
+    /// Play state of all fixed lanes.
+    ///
+    /// `*mut char`
+    ///
+    /// - 0 → no lanes play
+    /// - 1 → all lanes play
+    /// - 2 → some lanes play
+    ///
+    /// Fine to set to 0 or 1, but the returned value is read-only.
+    AllLanesPlay,
     /// Track timebase.
     ///
     /// `*mut char`
@@ -389,6 +445,29 @@ pub enum TrackAttributeKey<'a> {
     ///
     /// [`PlayOffsetFlag`]: #variant.PlayOffsetFlag
     PlayOffset,
+    /// Name of the fixed lane with the given zero-based index.
+    ///
+    /// `*mut char`
+    ///
+    /// Returns null for tracks which are not in fixed lanes mode.
+    LaneName(u32),
+    /// Razor edit areas as space-separated triples of start time, end time and envelope GUID.
+    ///
+    /// `*const char`
+    ///
+    /// See [`RazorEdit::parse_list()`].
+    ///
+    /// [`RazorEdit::parse_list()`]: struct.RazorEdit.html#method.parse_list
+    RazorEdits,
+    /// Razor edit areas as comma-separated tuples of start time, end time and optionally envelope
+    /// GUID, top y-position and bottom y-position.
+    ///
+    /// `*const char`
+    ///
+    /// See [`RazorEdit::parse_ext_list()`].
+    ///
+    /// [`RazorEdit::parse_ext_list()`]: struct.RazorEdit.html#method.parse_ext_list
+    RazorEditsExt,
     /// If a variant is missing in this enum, you can use this custom one as a resort.
     ///
     /// Use [`custom()`] to create this variant.
@@ -423,6 +502,10 @@ impl<'a> TrackAttributeKey<'a> {
             ShowInMixer => reaper_str!("B_SHOWINMIXER").into(),
             ShowInTcp => reaper_str!("B_SHOWINTCP").into(),
             BeatAttachMode => reaper_str!("C_BEATATTACHMODE").into(),
+            AllLanesPlay => reaper_str!("C_ALLLANESPLAY").into(),
+            LanePlays(index) => ReaperString::from_string(format!("C_LANEPLAYS:{index}")).into(),
+            LanesCollapsed => reaper_str!("C_LANESCOLLAPSED").into(),
+            LaneSettings => reaper_str!("C_LANESETTINGS").into(),
             MainSendOffs => reaper_str!("C_MAINSEND_OFFS").into(),
             DualPanL => reaper_str!("D_DUALPANL").into(),
             DualPanR => reaper_str!("D_DUALPANR").into(),
@@ -438,6 +521,7 @@ impl<'a> TrackAttributeKey<'a> {
             CustomColor => reaper_str!("I_CUSTOMCOLOR").into(),
             FolderCompact => reaper_str!("I_FOLDERCOMPACT").into(),
             FolderDepth => reaper_str!("I_FOLDERDEPTH").into(),
+            FreeModeEx => reaper_str!("I_FREEMODE").into(),
             FxEn => reaper_str!("I_FXEN").into(),
             HeightOverride => reaper_str!("I_HEIGHTOVERRIDE").into(),
             McpH => reaper_str!("I_MCPH").into(),
@@ -446,6 +530,7 @@ impl<'a> TrackAttributeKey<'a> {
             McpY => reaper_str!("I_MCPY").into(),
             MidiHwOut => reaper_str!("I_MIDIHWOUT").into(),
             Nchan => reaper_str!("I_NCHAN").into(),
+            NumFixedLanes => reaper_str!("I_NUMFIXEDLANES").into(),
             VuMode => reaper_str!("I_VUMODE").into(),
             PanMode => reaper_str!("I_PANMODE").into(),
             PerfFlags => reaper_str!("I_PERFFLAGS").into(),
@@ -470,10 +555,13 @@ impl<'a> TrackAttributeKey<'a> {
                 concat_reaper_strs(reaper_str!("P_EXT:"), extension_specific_key.as_ref()).into()
             }
             Icon => reaper_str!("P_ICON").into(),
+            LaneName(index) => ReaperString::from_string(format!("P_LANENAME:{index}")).into(),
             McpLayout => reaper_str!("P_MCP_LAYOUT").into(),
             Name => reaper_str!("P_NAME").into(),
             ParTrack => reaper_str!("P_PARTRACK").into(),
             Project => reaper_str!("P_PROJECT").into(),
+            RazorEdits => reaper_str!("P_RAZOREDITS").into(),
+            RazorEditsExt => reaper_str!("P_RAZOREDITS_EXT").into(),
             TcpLayout => reaper_str!("P_TCP_LAYOUT").into(),
             Custom(key) => key,
         }
@@ -526,6 +614,8 @@ pub enum TakeAttributeKey<'a> {
     ///
     /// If you do not |0x1000000, then it will not be used, but will store the color.
     CustomColor,
+    /// Take GUID.
+    Guid,
     /// If a variant is missing in this enum, you can use this custom one as a resort.
     ///
     /// Use [`custom()`] to create this variant.
@@ -554,6 +644,7 @@ impl<'a> TakeAttributeKey<'a> {
             Pitch => reaper_str!("D_PITCH").into(),
             PitchMode => reaper_str!("I_PITCHMODE").into(),
             CustomColor => reaper_str!("I_CUSTOMCOLOR").into(),
+            Guid => reaper_str!("GUID").into(),
             Custom(key) => key,
         }
     }
@@ -570,6 +661,8 @@ pub enum ItemAttributeKey<'a> {
     Mute,
     /// Loop source.
     LoopSrc,
+    /// Selected in arrange view.
+    Selected,
     /// Item timebase.
     ///
     /// - -1 = track or project default
@@ -625,6 +718,8 @@ pub enum ItemAttributeKey<'a> {
     ///
     /// Fine to call with setNewValue, but returned value is read-only.
     FixedLane,
+    /// Item GUID.
+    Guid,
     /// If a variant is missing in this enum, you can use this custom one as a resort.
     ///
     /// Use [`custom()`] to create this variant.
@@ -649,6 +744,7 @@ impl<'a> ItemAttributeKey<'a> {
             Length => reaper_str!("D_LENGTH").into(),
             Mute => reaper_str!("B_MUTE").into(),
             LoopSrc => reaper_str!("B_LOOPSRC").into(),
+            Selected => reaper_str!("B_UISEL").into(),
             BeatAttachMode => reaper_str!("C_BEATATTACHMODE").into(),
             AutoStretch => reaper_str!("C_AUTOSTRETCH").into(),
             Vol => reaper_str!("D_VOL").into(),
@@ -666,6 +762,7 @@ impl<'a> ItemAttributeKey<'a> {
             FreeModeY => reaper_str!("F_FREEMODE_Y").into(),
             FreeModeH => reaper_str!("F_FREEMODE_H").into(),
             FixedLane => reaper_str!("I_FIXEDLANE").into(),
+            Guid => reaper_str!("GUID").into(),
             Custom(key) => key,
         }
     }
@@ -742,6 +839,8 @@ pub enum TrackSendAttributeKey<'a> {
     /// - 1 → pre-fx
     /// - 2 → post-fx (deprecated)
     /// - 3 → post-fx
+    ///
+    /// See [`SendMode`](enum.SendMode.html).
     SendMode,
     /// Automation mode.
     ///
@@ -759,11 +858,15 @@ pub enum TrackSendAttributeKey<'a> {
     /// `*mut i32`
     ///
     /// Index, &1024 → mono, -1 → none
+    ///
+    /// See [`SendSourceChannels`](struct.SendSourceChannels.html).
     SrcChan,
     /// Destination channel.
     ///
     /// `*mut i32`
     /// Index, &1024 → mono, otherwise stereo pair, hwout: &512 → rearoute
+    ///
+    /// See [`SendDestinationChannels`](struct.SendDestinationChannels.html).
     DstChan,
     /// MIDI flags.
     ///
@@ -771,6 +874,8 @@ pub enum TrackSendAttributeKey<'a> {
     ///
     /// - Low 5 bits → source channel (0 → all, 1..=16)
     /// - Next 5 bits → destination channel (0 → original, 1..=16)
+    ///
+    /// See [`SendMidiFlags`](struct.SendMidiFlags.html).
     MidiFlags,
     /// If a variant is missing in this enum, you can use this custom one as a resort.
     ///
@@ -910,6 +1015,287 @@ impl<'a> EnvChunkName<'a> {
     }
 }
 
+/// Theme color key which you can pass to [`get_theme_color()`] or [`set_theme_color()`].
+///
+/// [`get_theme_color()`]: struct.Reaper.html#method.get_theme_color
+/// [`set_theme_color()`]: struct.Reaper.html#method.set_theme_color
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ThemeColorKey<'a> {
+    /// Main window/transport background.
+    ///
+    /// `col_main_bg2`
+    MainBg2,
+    /// Main window/transport text.
+    ///
+    /// `col_main_text2`
+    MainText2,
+    /// Main window text shadow (ignored if too close to text color).
+    ///
+    /// `col_main_textshadow`
+    MainTextShadow,
+    /// Main window 3D highlight.
+    ///
+    /// `col_main_3dhl`
+    Main3dHighlight,
+    /// Main window 3D shadow.
+    ///
+    /// `col_main_3dsh`
+    Main3dShadow,
+    /// Main window pane resize mouseover.
+    ///
+    /// `col_main_resize2`
+    MainResize2,
+    /// Themed window text.
+    ///
+    /// `col_main_text`
+    MainText,
+    /// Themed window background.
+    ///
+    /// `col_main_bg`
+    MainBg,
+    /// Themed window edit background.
+    ///
+    /// `col_main_editbk`
+    MainEditBg,
+    /// Transport edit background.
+    ///
+    /// `col_transport_editbk`
+    TransportEditBg,
+    /// Toolbar button text.
+    ///
+    /// `col_toolbar_text`
+    ToolbarText,
+    /// Toolbar button enabled text.
+    ///
+    /// `col_toolbar_text_on`
+    ToolbarTextOn,
+    /// Toolbar frame when floating or docked.
+    ///
+    /// `col_toolbar_frame`
+    ToolbarFrame,
+    /// Toolbar button armed color.
+    ///
+    /// `toolbararmed_color`
+    ToolbarArmed,
+    /// Empty track list area.
+    ///
+    /// `col_tracklistbg`
+    TrackListBg,
+    /// Empty mixer list area.
+    ///
+    /// `col_mixerbg`
+    MixerBg,
+    /// Empty arrange view area.
+    ///
+    /// `col_arrangebg`
+    ArrangeBg,
+    /// Empty arrange view area vertical grid shading.
+    ///
+    /// `arrange_vgrid`
+    ArrangeVerticalGrid,
+    /// Timeline foreground.
+    ///
+    /// `col_tl_fg`
+    TimelineForeground,
+    /// Timeline foreground (secondary markings).
+    ///
+    /// `col_tl_fg2`
+    TimelineForeground2,
+    /// Timeline background.
+    ///
+    /// `col_tl_bg`
+    TimelineBg,
+    /// Time selection color.
+    ///
+    /// `col_tl_bgsel`
+    TimeSelectionBg,
+    /// Timeline background (in loop points).
+    ///
+    /// `col_tl_bgsel2`
+    TimeSelectionBg2,
+    /// Transport status background.
+    ///
+    /// `col_trans_bg`
+    TransportStatusBg,
+    /// Transport status text.
+    ///
+    /// `col_trans_fg`
+    TransportStatusText,
+    /// Odd tracks background.
+    ///
+    /// `col_tr1_bg`
+    Track1Bg,
+    /// Even tracks background.
+    ///
+    /// `col_tr2_bg`
+    Track2Bg,
+    /// Selected odd tracks background.
+    ///
+    /// `selcol_tr1_bg`
+    SelectedTrack1Bg,
+    /// Selected even tracks background.
+    ///
+    /// `selcol_tr2_bg`
+    SelectedTrack2Bg,
+    /// Odd tracks divider line.
+    ///
+    /// `col_tr1_divline`
+    Track1DividerLine,
+    /// Even tracks divider line.
+    ///
+    /// `col_tr2_divline`
+    Track2DividerLine,
+    /// Odd tracks selected media item background.
+    ///
+    /// `col_tr1_itembgsel`
+    Track1ItemBg,
+    /// Even tracks selected media item background.
+    ///
+    /// `col_tr2_itembgsel`
+    Track2ItemBg,
+    /// Media item label.
+    ///
+    /// `col_mi_label`
+    MediaItemLabel,
+    /// Media item label (selected).
+    ///
+    /// `col_mi_label_sel`
+    MediaItemLabelSelected,
+    /// Edit cursor.
+    ///
+    /// `col_cursor`
+    Cursor,
+    /// Edit cursor (alternate).
+    ///
+    /// `col_cursor2`
+    Cursor2,
+    /// Play cursor.
+    ///
+    /// `playcursor_color`
+    PlayCursor,
+    /// Grid lines (start of measure).
+    ///
+    /// `col_gridlines`
+    GridLines,
+    /// Grid lines (start of beats).
+    ///
+    /// `col_gridlines2`
+    GridLines2,
+    /// Grid lines (in between beats).
+    ///
+    /// `col_gridlines3`
+    GridLines3,
+    /// Selected track control panel background.
+    ///
+    /// `col_seltrack`
+    SelectedTrack,
+    /// Unselected track control panel background.
+    ///
+    /// `col_seltrack2`
+    SelectedTrack2,
+    /// Markers.
+    ///
+    /// `marker`
+    Marker,
+    /// Regions.
+    ///
+    /// `region`
+    Region,
+    /// Region lane background.
+    ///
+    /// `region_lane_bg`
+    RegionLaneBg,
+    /// Marker lane background.
+    ///
+    /// `marker_lane_bg`
+    MarkerLaneBg,
+    /// Media item peaks (odd tracks).
+    ///
+    /// `col_peaks`
+    WaveformPeaks,
+    /// Media item peaks (even tracks).
+    ///
+    /// `col_peaks2`
+    WaveformPeaks2,
+    /// If a variant is missing in this enum, you can use this custom one as a resort.
+    ///
+    /// Use [`custom()`] to create this variant.
+    ///
+    /// [`custom()`]: #method.custom
+    Custom(Cow<'a, ReaperStr>),
+}
+
+impl<'a> ThemeColorKey<'a> {
+    /// Convenience function for creating a [`Custom`] key.
+    ///
+    /// [`Custom`]: #variant.Custom
+    pub fn custom(key: impl Into<ReaperStringArg<'a>>) -> ThemeColorKey<'a> {
+        ThemeColorKey::Custom(key.into().into_inner())
+    }
+
+    pub(crate) fn into_raw(self) -> Cow<'a, ReaperStr> {
+        use ThemeColorKey::*;
+        match self {
+            MainBg2 => reaper_str!("col_main_bg2").into(),
+            MainText2 => reaper_str!("col_main_text2").into(),
+            MainTextShadow => reaper_str!("col_main_textshadow").into(),
+            Main3dHighlight => reaper_str!("col_main_3dhl").into(),
+            Main3dShadow => reaper_str!("col_main_3dsh").into(),
+            MainResize2 => reaper_str!("col_main_resize2").into(),
+            MainText => reaper_str!("col_main_text").into(),
+            MainBg => reaper_str!("col_main_bg").into(),
+            MainEditBg => reaper_str!("col_main_editbk").into(),
+            TransportEditBg => reaper_str!("col_transport_editbk").into(),
+            ToolbarText => reaper_str!("col_toolbar_text").into(),
+            ToolbarTextOn => reaper_str!("col_toolbar_text_on").into(),
+            ToolbarFrame => reaper_str!("col_toolbar_frame").into(),
+            ToolbarArmed => reaper_str!("toolbararmed_color").into(),
+            TrackListBg => reaper_str!("col_tracklistbg").into(),
+            MixerBg => reaper_str!("col_mixerbg").into(),
+            ArrangeBg => reaper_str!("col_arrangebg").into(),
+            ArrangeVerticalGrid => reaper_str!("arrange_vgrid").into(),
+            TimelineForeground => reaper_str!("col_tl_fg").into(),
+            TimelineForeground2 => reaper_str!("col_tl_fg2").into(),
+            TimelineBg => reaper_str!("col_tl_bg").into(),
+            TimeSelectionBg => reaper_str!("col_tl_bgsel").into(),
+            TimeSelectionBg2 => reaper_str!("col_tl_bgsel2").into(),
+            TransportStatusBg => reaper_str!("col_trans_bg").into(),
+            TransportStatusText => reaper_str!("col_trans_fg").into(),
+            Track1Bg => reaper_str!("col_tr1_bg").into(),
+            Track2Bg => reaper_str!("col_tr2_bg").into(),
+            SelectedTrack1Bg => reaper_str!("selcol_tr1_bg").into(),
+            SelectedTrack2Bg => reaper_str!("selcol_tr2_bg").into(),
+            Track1DividerLine => reaper_str!("col_tr1_divline").into(),
+            Track2DividerLine => reaper_str!("col_tr2_divline").into(),
+            Track1ItemBg => reaper_str!("col_tr1_itembgsel").into(),
+            Track2ItemBg => reaper_str!("col_tr2_itembgsel").into(),
+            MediaItemLabel => reaper_str!("col_mi_label").into(),
+            MediaItemLabelSelected => reaper_str!("col_mi_label_sel").into(),
+            Cursor => reaper_str!("col_cursor").into(),
+            Cursor2 => reaper_str!("col_cursor2").into(),
+            PlayCursor => reaper_str!("playcursor_color").into(),
+            GridLines => reaper_str!("col_gridlines").into(),
+            GridLines2 => reaper_str!("col_gridlines2").into(),
+            GridLines3 => reaper_str!("col_gridlines3").into(),
+            SelectedTrack => reaper_str!("col_seltrack").into(),
+            SelectedTrack2 => reaper_str!("col_seltrack2").into(),
+            Marker => reaper_str!("marker").into(),
+            Region => reaper_str!("region").into(),
+            RegionLaneBg => reaper_str!("region_lane_bg").into(),
+            MarkerLaneBg => reaper_str!("marker_lane_bg").into(),
+            WaveformPeaks => reaper_str!("col_peaks").into(),
+            WaveformPeaks2 => reaper_str!("col_peaks2").into(),
+            Custom(key) => key,
+        }
+    }
+}
+
+impl<'a> From<ThemeColorKey<'a>> for ReaperStringArg<'a> {
+    fn from(key: ThemeColorKey<'a>) -> Self {
+        key.into_raw().into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -939,4 +1325,16 @@ mod tests {
             reaper_str!("BLA")
         );
     }
+
+    #[test]
+    fn serialize_theme_color_key() {
+        assert_eq!(
+            ThemeColorKey::ArrangeBg.into_raw().as_ref(),
+            reaper_str!("col_arrangebg")
+        );
+        assert_eq!(
+            ThemeColorKey::custom("col_fadearm").into_raw().as_ref(),
+            reaper_str!("col_fadearm")
+        );
+    }
 }
@@ -403,6 +403,12 @@ pub use recording_input::*;
 mod recording_mode;
 pub use recording_mode::*;
 
+mod send_routing;
+pub use send_routing::*;
+
+mod razor_edit;
+pub use razor_edit::*;
+
 mod automation_mode;
 pub use automation_mode::*;
 
@@ -424,3 +430,12 @@ pub use mutex::*;
 
 mod project_state_context;
 pub use project_state_context::*;
+
+mod dock;
+pub use dock::*;
+
+mod osc;
+pub use osc::*;
+
+mod joystick;
+pub use joystick::*;
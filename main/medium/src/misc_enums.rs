@@ -192,6 +192,58 @@ impl HwndInfoType {
     }
 }
 
+/// Possible things that REAPER wants from a window registered in screensets.
+#[derive(Eq, PartialEq, Hash, Debug)]
+pub enum ScreensetAction<'a> {
+    /// REAPER queries the window handle.
+    ///
+    /// - Return the window handle (pointer as `isize`) or 0 if the window is not open
+    GetWindow,
+    /// REAPER queries whether the window is docked.
+    ///
+    /// - Return 1 if docked, 0 otherwise
+    IsDocked,
+    /// REAPER asks to dock the window if it's floating and vice versa.
+    SwitchDock,
+    /// REAPER asks to restore the given window state, previously saved in a screenset.
+    LoadState(&'a [u8]),
+    /// REAPER asks to hide the window because the screenset doesn't contain it.
+    Hide,
+    /// REAPER asks to save the window state into the given buffer.
+    ///
+    /// - Return the number of bytes written
+    SaveState(&'a mut [u8]),
+    /// REAPER asks how big the buffer passed with [`SaveState`](Self::SaveState) should be.
+    ///
+    /// - Return the desired size in bytes (REAPER might pass a smaller buffer anyway)
+    WantStateSize,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<i32>),
+}
+
+/// Which layout information to query in a theme layout section.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ThemeLayoutIndex {
+    /// The currently active layout.
+    Current,
+    /// The description of the section (not available for the `global` section).
+    SectionDescription,
+    /// The name of the layout at the given index.
+    Layout(u32),
+}
+
+impl ThemeLayoutIndex {
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        match self {
+            ThemeLayoutIndex::Current => -1,
+            ThemeLayoutIndex::SectionDescription => -2,
+            ThemeLayoutIndex::Layout(i) => i as i32,
+        }
+    }
+}
+
 /// Determines whether to refresh the UI.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum UiRefreshBehavior {
@@ -1998,3 +2050,259 @@ pub struct CommandItem<S1, S2> {
     /// Example: `toolbar_add.png`
     pub icon_file_name: Option<S2>,
 }
+
+/// VU meter mode of a track (`I_VUMODE`).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum VuMode {
+    /// Stereo peaks.
+    StereoPeaks,
+    /// Multichannel peaks.
+    MultichannelPeaks,
+    /// Stereo RMS.
+    StereoRms,
+    /// Combined RMS.
+    CombinedRms,
+    /// Momentary loudness (LUFS-M).
+    LufsMomentary,
+    /// Short-term loudness with maximum readout (LUFS-S).
+    LufsShortTermMax,
+    /// Short-term loudness with current readout (LUFS-S).
+    LufsShortTermCurrent,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<i32>),
+}
+
+impl VuMode {
+    /// Converts an integer as returned by the low-level API to a VU mode.
+    ///
+    /// The flag for calculating LUFS on channels 1+2 only is ignored.
+    pub fn from_raw(v: i32) -> Self {
+        use VuMode::*;
+        match v & !VU_MODE_LUFS_FIRST_PAIR_ONLY_FLAG {
+            0 => StereoPeaks,
+            2 => MultichannelPeaks,
+            4 => StereoRms,
+            8 => CombinedRms,
+            12 => LufsMomentary,
+            16 => LufsShortTermMax,
+            20 => LufsShortTermCurrent,
+            x => Unknown(Hidden(x)),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        use VuMode::*;
+        match self {
+            StereoPeaks => 0,
+            MultichannelPeaks => 2,
+            StereoRms => 4,
+            CombinedRms => 8,
+            LufsMomentary => 12,
+            LufsShortTermMax => 16,
+            LufsShortTermCurrent => 20,
+            Unknown(Hidden(x)) => x,
+        }
+    }
+
+    /// Returns whether this mode displays loudness.
+    pub fn is_loudness(self) -> bool {
+        use VuMode::*;
+        matches!(
+            self,
+            LufsMomentary | LufsShortTermMax | LufsShortTermCurrent
+        )
+    }
+}
+
+/// Full VU meter configuration of a track (`I_VUMODE`).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TrackVuMode {
+    /// The VU mode.
+    pub mode: VuMode,
+    /// Whether the LUFS calculation should take channels 1+2 only into account.
+    pub lufs_on_first_channel_pair_only: bool,
+}
+
+impl TrackVuMode {
+    /// Converts an integer as returned by the low-level API to a track VU mode.
+    pub fn from_raw(v: i32) -> Self {
+        Self {
+            mode: VuMode::from_raw(v),
+            lufs_on_first_channel_pair_only: v & VU_MODE_LUFS_FIRST_PAIR_ONLY_FLAG != 0,
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        let flag = if self.lufs_on_first_channel_pair_only {
+            VU_MODE_LUFS_FIRST_PAIR_ONLY_FLAG
+        } else {
+            0
+        };
+        self.mode.to_raw() | flag
+    }
+}
+
+const VU_MODE_LUFS_FIRST_PAIR_ONLY_FLAG: i32 = 32;
+
+/// Defines how items are positioned vertically within a track (`I_FREEMODE`).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ItemPositioningMode {
+    /// Items are stacked according to their overlap.
+    Normal,
+    /// Free item positioning.
+    FreeItemPositioning,
+    /// Items are arranged in fixed lanes.
+    FixedLanes,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<i32>),
+}
+
+impl ItemPositioningMode {
+    /// Converts an integer as returned by the low-level API to an item positioning mode.
+    pub fn from_raw(v: i32) -> Self {
+        use ItemPositioningMode::*;
+        match v {
+            0 => Normal,
+            1 => FreeItemPositioning,
+            2 => FixedLanes,
+            x => Unknown(Hidden(x)),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        use ItemPositioningMode::*;
+        match self {
+            Normal => 0,
+            FreeItemPositioning => 1,
+            FixedLanes => 2,
+            Unknown(Hidden(x)) => x,
+        }
+    }
+}
+
+/// Play state of a fixed lane (`C_LANEPLAYS:n`).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum LanePlayState {
+    /// The lane doesn't play.
+    NotPlaying,
+    /// The lane plays exclusively (it's soloed).
+    PlaysExclusively,
+    /// The lane plays and other lanes also play.
+    PlaysWithOthers,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<i32>),
+}
+
+impl LanePlayState {
+    /// Converts an integer as returned by the low-level API to a lane play state.
+    pub fn from_raw(v: i32) -> Self {
+        use LanePlayState::*;
+        match v {
+            0 => NotPlaying,
+            1 => PlaysExclusively,
+            2 => PlaysWithOthers,
+            x => Unknown(Hidden(x)),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        use LanePlayState::*;
+        match self {
+            NotPlaying => 0,
+            PlaysExclusively => 1,
+            PlaysWithOthers => 2,
+            Unknown(Hidden(x)) => x,
+        }
+    }
+
+    /// Returns whether the lane plays at all.
+    pub fn is_playing(self) -> bool {
+        matches!(
+            self,
+            LanePlayState::PlaysExclusively | LanePlayState::PlaysWithOthers
+        )
+    }
+}
+
+/// The part of the main window which has the keyboard focus (as far as REAPER is concerned).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum CursorContext {
+    /// Track control panels.
+    Tracks,
+    /// Items in the arrange view.
+    Items,
+    /// Envelopes in the arrange view.
+    Envelopes,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<i32>),
+}
+
+impl CursorContext {
+    /// Converts an integer as returned by the low-level API to a cursor context.
+    pub fn from_raw(v: i32) -> Self {
+        use CursorContext::*;
+        match v {
+            0 => Tracks,
+            1 => Items,
+            2 => Envelopes,
+            x => Unknown(Hidden(x)),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        use CursorContext::*;
+        match self {
+            Tracks => 0,
+            Items => 1,
+            Envelopes => 2,
+            Unknown(Hidden(x)) => x,
+        }
+    }
+}
+
+/// The part of a track which is probably located at a certain screen point.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TrackPart {
+    /// The track itself (e.g. its panel or its media item area).
+    Track,
+    /// An envelope of the track.
+    Envelope,
+    /// A track FX window.
+    Fx,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<i32>),
+}
+
+impl TrackPart {
+    /// Converts an integer as returned by the low-level API to a track part.
+    pub fn from_raw(v: i32) -> Self {
+        use TrackPart::*;
+        match v {
+            0 => Track,
+            1 => Envelope,
+            2 => Fx,
+            x => Unknown(Hidden(x)),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        use TrackPart::*;
+        match self {
+            Track => 0,
+            Envelope => 1,
+            Fx => 2,
+            Unknown(Hidden(x)) => x,
+        }
+    }
+}
@@ -113,6 +113,19 @@ impl TryFrom<u32> for CommandId {
 pub struct SectionId(pub(crate) u32);
 
 impl SectionId {
+    /// The main section.
+    pub const MAIN: SectionId = SectionId(0);
+    /// The main section (alt recording).
+    pub const MAIN_ALT_RECORDING: SectionId = SectionId(100);
+    /// The MIDI editor section.
+    pub const MIDI_EDITOR: SectionId = SectionId(32060);
+    /// The MIDI event list editor section.
+    pub const MIDI_EVENT_LIST_EDITOR: SectionId = SectionId(32061);
+    /// The MIDI inline editor section.
+    pub const MIDI_INLINE_EDITOR: SectionId = SectionId(32062);
+    /// The media explorer section.
+    pub const MEDIA_EXPLORER: SectionId = SectionId(32063);
+
     /// Creates a section ID.
     pub fn new(number: u32) -> SectionId {
         SectionId(number)
@@ -0,0 +1,443 @@
+use crate::{ReaperFunctionError, ReaperFunctionResult};
+use reaper_low::firewall;
+use std::ffi::{c_char, c_void};
+use std::os::raw::c_int;
+use std::ptr::NonNull;
+
+/// Consumers need to implement this trait in order to receive OSC messages which REAPER sends to
+/// a local OSC handler (see [`Reaper::create_local_osc_handler()`]).
+///
+/// [`Reaper::create_local_osc_handler()`]: crate::Reaper::create_local_osc_handler
+pub trait LocalOscCallback {
+    /// Called in the main thread for each packet sent by REAPER.
+    ///
+    /// Packets which can't be decoded are skipped.
+    fn call(&mut self, packet: OscPacket);
+}
+
+/// A local OSC handler, created via [`Reaper::create_local_osc_handler()`].
+///
+/// Behaves like an OSC control surface without network connection: REAPER sends feedback to the
+/// callback and messages sent via [`send()`](Self::send) are processed by REAPER. The handler is
+/// destroyed when dropped.
+///
+/// [`Reaper::create_local_osc_handler()`]: crate::Reaper::create_local_osc_handler
+#[derive(Debug)]
+pub struct LocalOscHandler<T: LocalOscCallback> {
+    low: reaper_low::Reaper,
+    handle: NonNull<c_void>,
+    // Passed to REAPER as object pointer, so it must have a stable address.
+    callback: Box<T>,
+}
+
+impl<T: LocalOscCallback> LocalOscHandler<T> {
+    pub(crate) fn create(low: reaper_low::Reaper, callback: T) -> ReaperFunctionResult<Self> {
+        let mut callback = Box::new(callback);
+        let handle = unsafe {
+            low.CreateLocalOscHandler(
+                callback.as_mut() as *mut T as *mut c_void,
+                delegating_local_osc_callback::<T> as *mut c_void,
+            )
+        };
+        let handle = NonNull::new(handle)
+            .ok_or_else(|| ReaperFunctionError::new("couldn't create local OSC handler"))?;
+        Ok(Self {
+            low,
+            handle,
+            callback,
+        })
+    }
+
+    /// Sends the given packet to REAPER as if it came from an OSC device.
+    pub fn send(&self, packet: &OscPacket) {
+        let data = packet.encode();
+        unsafe {
+            self.low.SendLocalOscMessage(
+                self.handle.as_ptr(),
+                data.as_ptr() as *const c_char,
+                data.len() as c_int,
+            );
+        }
+    }
+
+    pub fn callback(&self) -> &T {
+        &self.callback
+    }
+}
+
+impl<T: LocalOscCallback> Drop for LocalOscHandler<T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.low.DestroyLocalOscHandler(self.handle.as_ptr());
+        }
+    }
+}
+
+extern "C" fn delegating_local_osc_callback<T: LocalOscCallback>(
+    obj: *mut c_void,
+    msg: *const c_char,
+    msglen: c_int,
+) {
+    firewall(|| {
+        if obj.is_null() || msg.is_null() || msglen <= 0 {
+            return;
+        }
+        let callback = unsafe { &mut *(obj as *mut T) };
+        let data = unsafe { std::slice::from_raw_parts(msg as *const u8, msglen as usize) };
+        match OscPacket::decode(data) {
+            Ok(packet) => callback.call(packet),
+            Err(error) => tracing::debug!(msg = "Skipped undecodable OSC packet", %error),
+        }
+    });
+}
+
+/// An OSC packet, either a single message or a bundle.
+#[derive(Clone, PartialEq, Debug)]
+pub enum OscPacket {
+    Message(OscMessage),
+    Bundle(OscBundle),
+}
+
+/// An OSC message consisting of an address pattern and arguments.
+#[derive(Clone, PartialEq, Debug)]
+pub struct OscMessage {
+    /// Address pattern, e.g. `/track/1/volume`.
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+/// An OSC bundle containing packets which should be processed at the same time.
+#[derive(Clone, PartialEq, Debug)]
+pub struct OscBundle {
+    /// NTP time tag. 1 means "immediately".
+    pub time_tag: u64,
+    pub content: Vec<OscPacket>,
+}
+
+/// An OSC argument.
+#[derive(Clone, PartialEq, Debug)]
+pub enum OscArg {
+    /// `i`
+    Int(i32),
+    /// `f`
+    Float(f32),
+    /// `s`
+    String(String),
+    /// `b`
+    Blob(Vec<u8>),
+    /// `h`
+    Long(i64),
+    /// `d`
+    Double(f64),
+    /// `T` or `F`
+    Bool(bool),
+    /// `N`
+    Nil,
+}
+
+impl OscPacket {
+    /// Decodes the given binary OSC packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given data is not a valid OSC packet or contains unsupported
+    /// argument types.
+    pub fn decode(data: &[u8]) -> ReaperFunctionResult<OscPacket> {
+        if data.starts_with(BUNDLE_TAG) {
+            OscBundle::decode(data).map(OscPacket::Bundle)
+        } else {
+            OscMessage::decode(data).map(OscPacket::Message)
+        }
+    }
+
+    /// Encodes this packet in the binary OSC format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        self.encode_into(&mut buffer);
+        buffer
+    }
+
+    fn encode_into(&self, buffer: &mut Vec<u8>) {
+        match self {
+            OscPacket::Message(m) => m.encode_into(buffer),
+            OscPacket::Bundle(b) => b.encode_into(buffer),
+        }
+    }
+}
+
+impl OscMessage {
+    pub fn new(address: impl Into<String>, args: Vec<OscArg>) -> Self {
+        Self {
+            address: address.into(),
+            args,
+        }
+    }
+
+    /// Decodes the given binary OSC message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given data is not a valid OSC message or contains unsupported
+    /// argument types.
+    pub fn decode(data: &[u8]) -> ReaperFunctionResult<OscMessage> {
+        let mut reader = Reader { data, pos: 0 };
+        let address = reader.read_string()?;
+        if !address.starts_with('/') {
+            return Err(ReaperFunctionError::new(
+                "OSC address must start with slash",
+            ));
+        }
+        // Type tags are optional in very old implementations
+        if reader.is_at_end() {
+            return Ok(OscMessage::new(address, vec![]));
+        }
+        let type_tags = reader.read_string()?;
+        let type_tags = type_tags
+            .strip_prefix(',')
+            .ok_or_else(|| ReaperFunctionError::new("OSC type tags must start with comma"))?;
+        let args = type_tags
+            .chars()
+            .map(|tag| reader.read_arg(tag))
+            .collect::<Result<_, _>>()?;
+        Ok(OscMessage::new(address, args))
+    }
+
+    /// Encodes this message in the binary OSC format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        self.encode_into(&mut buffer);
+        buffer
+    }
+
+    fn encode_into(&self, buffer: &mut Vec<u8>) {
+        write_string(buffer, &self.address);
+        let type_tags: String = std::iter::once(',')
+            .chain(self.args.iter().map(OscArg::type_tag))
+            .collect();
+        write_string(buffer, &type_tags);
+        for arg in &self.args {
+            arg.encode_into(buffer);
+        }
+    }
+}
+
+impl OscBundle {
+    /// Decodes the given binary OSC bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given data is not a valid OSC bundle.
+    pub fn decode(data: &[u8]) -> ReaperFunctionResult<OscBundle> {
+        let mut reader = Reader { data, pos: 0 };
+        if reader.read_bytes(BUNDLE_TAG.len())? != BUNDLE_TAG {
+            return Err(ReaperFunctionError::new("not an OSC bundle"));
+        }
+        let time_tag = u64::from_be_bytes(reader.read_array()?);
+        let mut content = Vec::new();
+        while !reader.is_at_end() {
+            let size = reader.read_size()?;
+            let element = reader.read_bytes(size)?;
+            content.push(OscPacket::decode(element)?);
+        }
+        Ok(OscBundle { time_tag, content })
+    }
+
+    fn encode_into(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(BUNDLE_TAG);
+        buffer.extend_from_slice(&self.time_tag.to_be_bytes());
+        for packet in &self.content {
+            let size_pos = buffer.len();
+            buffer.extend_from_slice(&[0; 4]);
+            packet.encode_into(buffer);
+            let size = (buffer.len() - size_pos - 4) as i32;
+            buffer[size_pos..size_pos + 4].copy_from_slice(&size.to_be_bytes());
+        }
+    }
+}
+
+impl OscArg {
+    /// Returns the value as `f32` if this is a numeric argument.
+    pub fn to_f32(&self) -> Option<f32> {
+        match self {
+            OscArg::Int(v) => Some(*v as f32),
+            OscArg::Float(v) => Some(*v),
+            OscArg::Long(v) => Some(*v as f32),
+            OscArg::Double(v) => Some(*v as f32),
+            _ => None,
+        }
+    }
+
+    fn type_tag(&self) -> char {
+        match self {
+            OscArg::Int(_) => 'i',
+            OscArg::Float(_) => 'f',
+            OscArg::String(_) => 's',
+            OscArg::Blob(_) => 'b',
+            OscArg::Long(_) => 'h',
+            OscArg::Double(_) => 'd',
+            OscArg::Bool(true) => 'T',
+            OscArg::Bool(false) => 'F',
+            OscArg::Nil => 'N',
+        }
+    }
+
+    fn encode_into(&self, buffer: &mut Vec<u8>) {
+        match self {
+            OscArg::Int(v) => buffer.extend_from_slice(&v.to_be_bytes()),
+            OscArg::Float(v) => buffer.extend_from_slice(&v.to_be_bytes()),
+            OscArg::String(v) => write_string(buffer, v),
+            OscArg::Blob(v) => {
+                buffer.extend_from_slice(&(v.len() as i32).to_be_bytes());
+                buffer.extend_from_slice(v);
+                pad(buffer);
+            }
+            OscArg::Long(v) => buffer.extend_from_slice(&v.to_be_bytes()),
+            OscArg::Double(v) => buffer.extend_from_slice(&v.to_be_bytes()),
+            OscArg::Bool(_) | OscArg::Nil => {}
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn is_at_end(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn read_bytes(&mut self, count: usize) -> ReaperFunctionResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(count)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| ReaperFunctionError::new("OSC packet too short"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> ReaperFunctionResult<[u8; N]> {
+        let bytes = self.read_bytes(N)?;
+        Ok(bytes.try_into().expect("slice has correct length"))
+    }
+
+    fn read_size(&mut self) -> ReaperFunctionResult<usize> {
+        let size = i32::from_be_bytes(self.read_array()?);
+        usize::try_from(size).map_err(|_| ReaperFunctionError::new("negative size in OSC packet"))
+    }
+
+    fn skip_padding(&mut self) {
+        self.pos = padded_len(self.pos).min(self.data.len());
+    }
+
+    fn read_string(&mut self) -> ReaperFunctionResult<String> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| ReaperFunctionError::new("unterminated string in OSC packet"))?;
+        let string = std::str::from_utf8(&rest[..len])
+            .map_err(|_| ReaperFunctionError::new("OSC string is not valid UTF-8"))?
+            .to_string();
+        self.pos += len + 1;
+        self.skip_padding();
+        Ok(string)
+    }
+
+    fn read_arg(&mut self, type_tag: char) -> ReaperFunctionResult<OscArg> {
+        let arg = match type_tag {
+            'i' => OscArg::Int(i32::from_be_bytes(self.read_array()?)),
+            'f' => OscArg::Float(f32::from_be_bytes(self.read_array()?)),
+            's' => OscArg::String(self.read_string()?),
+            'b' => {
+                let size = self.read_size()?;
+                let blob = self.read_bytes(size)?.to_vec();
+                self.skip_padding();
+                OscArg::Blob(blob)
+            }
+            'h' => OscArg::Long(i64::from_be_bytes(self.read_array()?)),
+            'd' => OscArg::Double(f64::from_be_bytes(self.read_array()?)),
+            'T' => OscArg::Bool(true),
+            'F' => OscArg::Bool(false),
+            'N' => OscArg::Nil,
+            _ => return Err(ReaperFunctionError::new("unsupported OSC argument type")),
+        };
+        Ok(arg)
+    }
+}
+
+fn write_string(buffer: &mut Vec<u8>, value: &str) {
+    buffer.extend_from_slice(value.as_bytes());
+    buffer.push(0);
+    pad(buffer);
+}
+
+fn pad(buffer: &mut Vec<u8>) {
+    buffer.resize(padded_len(buffer.len()), 0);
+}
+
+fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+const BUNDLE_TAG: &[u8] = b"#bundle\0";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_message() {
+        let message = OscMessage::new("/track/1/volume", vec![OscArg::Float(0.5)]);
+        assert_eq!(
+            message.encode(),
+            b"/track/1/volume\0,f\0\0\x3f\x00\x00\x00".to_vec()
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let packet = OscPacket::Bundle(OscBundle {
+            time_tag: 1,
+            content: vec![
+                OscPacket::Message(OscMessage::new(
+                    "/action",
+                    vec![
+                        OscArg::Int(40044),
+                        OscArg::String("abc".to_string()),
+                        OscArg::Blob(vec![1, 2, 3, 4, 5]),
+                        OscArg::Long(-7),
+                        OscArg::Double(0.25),
+                        OscArg::Bool(true),
+                        OscArg::Bool(false),
+                        OscArg::Nil,
+                    ],
+                )),
+                OscPacket::Bundle(OscBundle {
+                    time_tag: 2,
+                    content: vec![OscPacket::Message(OscMessage::new("/play", vec![]))],
+                }),
+            ],
+        });
+        let encoded = packet.encode();
+        assert_eq!(encoded.len() % 4, 0);
+        assert_eq!(OscPacket::decode(&encoded).unwrap(), packet);
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert!(OscPacket::decode(b"").is_err());
+        assert!(OscPacket::decode(b"/abc").is_err());
+        assert!(OscPacket::decode(b"abc\0").is_err());
+        assert!(OscPacket::decode(b"/abc\0\0\0\0,i\0\0\0\0").is_err());
+        assert!(OscPacket::decode(b"/abc\0\0\0\0,x\0\0").is_err());
+        assert!(OscPacket::decode(b"#bundle\0\0\0\0\0\0\0\0\x01\0\0\0\x08/a\0\0").is_err());
+        assert_eq!(
+            OscPacket::decode(b"/abc\0\0\0\0").unwrap(),
+            OscPacket::Message(OscMessage::new("/abc", vec![]))
+        );
+    }
+}
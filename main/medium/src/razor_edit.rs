@@ -0,0 +1,273 @@
+use crate::{PositionInSeconds, ReaperFunctionError, ReaperFunctionResult};
+use std::fmt::Write;
+
+/// A razor edit area of a track (`P_RAZOREDITS` and `P_RAZOREDITS_EXT`).
+#[derive(Clone, PartialEq, Debug)]
+pub struct RazorEdit {
+    /// Start of the time range.
+    pub start: PositionInSeconds,
+    /// End of the time range.
+    pub end: PositionInSeconds,
+    /// GUID of the envelope, including braces.
+    ///
+    /// `None` means that the area covers the media items of the track.
+    pub envelope_guid: Option<String>,
+    /// Vertical extent of the area within the track.
+    ///
+    /// Only relevant for tracks with free item positioning or fixed lanes. `None` means the area
+    /// covers the complete track height.
+    pub lane_extent: Option<RazorEditLaneExtent>,
+}
+
+/// Vertical extent of a razor edit area within a track.
+///
+/// The values are relative to the track height: 0.0 is the top of the track, 1.0 the bottom.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RazorEditLaneExtent {
+    /// Top y-position.
+    pub top: f64,
+    /// Bottom y-position.
+    pub bottom: f64,
+}
+
+impl RazorEdit {
+    /// Creates a razor edit area covering the media items of the complete track height.
+    pub fn new(start: PositionInSeconds, end: PositionInSeconds) -> Self {
+        Self {
+            start,
+            end,
+            envelope_guid: None,
+            lane_extent: None,
+        }
+    }
+
+    /// Returns whether this area covers an envelope (as opposed to media items).
+    pub fn is_envelope_area(&self) -> bool {
+        self.envelope_guid.is_some()
+    }
+
+    /// Parses the space-separated triples of a `P_RAZOREDITS` string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is malformed.
+    pub fn parse_list(text: &str) -> ReaperFunctionResult<Vec<RazorEdit>> {
+        let tokens = tokenize(text)?;
+        if tokens.len() % 3 != 0 {
+            return Err(ReaperFunctionError::new(
+                "razor edit list doesn't consist of triples",
+            ));
+        }
+        tokens
+            .chunks(3)
+            .map(|triple| {
+                let edit = RazorEdit {
+                    start: parse_position(triple[0])?,
+                    end: parse_position(triple[1])?,
+                    envelope_guid: parse_guid(triple[2]),
+                    lane_extent: None,
+                };
+                Ok(edit)
+            })
+            .collect()
+    }
+
+    /// Parses the comma-separated tuples of a `P_RAZOREDITS_EXT` string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is malformed.
+    pub fn parse_ext_list(text: &str) -> ReaperFunctionResult<Vec<RazorEdit>> {
+        text.split(',')
+            .filter(|area| !area.trim().is_empty())
+            .map(|area| {
+                let tokens = tokenize(area)?;
+                let (start, end, guid, extent) = match tokens.as_slice() {
+                    [start, end] => (start, end, None, None),
+                    [start, end, guid] => (start, end, Some(guid), None),
+                    [start, end, guid, top, bottom] => {
+                        (start, end, Some(guid), Some((top, bottom)))
+                    }
+                    _ => {
+                        return Err(ReaperFunctionError::new(
+                            "razor edit area has unexpected number of values",
+                        ))
+                    }
+                };
+                let lane_extent = match extent {
+                    None => None,
+                    Some((top, bottom)) => Some(RazorEditLaneExtent {
+                        top: parse_f64(top)?,
+                        bottom: parse_f64(bottom)?,
+                    }),
+                };
+                let edit = RazorEdit {
+                    start: parse_position(start)?,
+                    end: parse_position(end)?,
+                    envelope_guid: guid.and_then(|g| parse_guid(g)),
+                    lane_extent,
+                };
+                Ok(edit)
+            })
+            .collect()
+    }
+
+    /// Formats the given areas as `P_RAZOREDITS` string.
+    ///
+    /// Lane extents are not supported by this format and therefore omitted.
+    pub fn format_list(edits: &[RazorEdit]) -> String {
+        let mut text = String::new();
+        for (i, edit) in edits.iter().enumerate() {
+            if i > 0 {
+                text.push(' ');
+            }
+            let _ = write!(
+                text,
+                "{} {} \"{}\"",
+                edit.start.get(),
+                edit.end.get(),
+                edit.envelope_guid.as_deref().unwrap_or_default()
+            );
+        }
+        text
+    }
+
+    /// Formats the given areas as `P_RAZOREDITS_EXT` string.
+    pub fn format_ext_list(edits: &[RazorEdit]) -> String {
+        let mut text = String::new();
+        for (i, edit) in edits.iter().enumerate() {
+            if i > 0 {
+                text.push(',');
+            }
+            let _ = write!(text, "{} {}", edit.start.get(), edit.end.get());
+            if edit.envelope_guid.is_some() || edit.lane_extent.is_some() {
+                let guid = edit.envelope_guid.as_deref().unwrap_or_default();
+                let _ = write!(text, " \"{guid}\"");
+            }
+            if let Some(extent) = edit.lane_extent {
+                let _ = write!(text, " {} {}", extent.top, extent.bottom);
+            }
+        }
+        text
+    }
+}
+
+/// Splits the given text at whitespace, treating quoted strings as one token (without quotes).
+fn tokenize(text: &str) -> ReaperFunctionResult<Vec<&str>> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let (token, remainder) = if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| ReaperFunctionError::new("unterminated quote in razor edit"))?;
+            (&quoted[..end], &quoted[end + 1..])
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+        tokens.push(token);
+        rest = remainder.trim_start();
+    }
+    Ok(tokens)
+}
+
+fn parse_f64(token: &str) -> ReaperFunctionResult<f64> {
+    token
+        .parse()
+        .map_err(|_| ReaperFunctionError::new("invalid number in razor edit"))
+}
+
+fn parse_position(token: &str) -> ReaperFunctionResult<PositionInSeconds> {
+    PositionInSeconds::new(parse_f64(token)?)
+        .map_err(|_| ReaperFunctionError::new("invalid position in razor edit"))
+}
+
+fn parse_guid(token: &str) -> Option<String> {
+    if token.is_empty() {
+        None
+    } else {
+        Some(token.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUID: &str = "{5C5D9C2F-3E7A-4C4B-9A5E-0D3F6E0A1B2C}";
+
+    fn pos(v: f64) -> PositionInSeconds {
+        PositionInSeconds::new_panic(v)
+    }
+
+    #[test]
+    fn parse_list() {
+        let text = format!("0.0 1.5 \"\" 2.0 3.0 \"{GUID}\"");
+        let edits = RazorEdit::parse_list(&text).unwrap();
+        assert_eq!(
+            edits,
+            vec![
+                RazorEdit::new(pos(0.0), pos(1.5)),
+                RazorEdit {
+                    envelope_guid: Some(GUID.to_string()),
+                    ..RazorEdit::new(pos(2.0), pos(3.0))
+                }
+            ]
+        );
+        assert!(RazorEdit::parse_list("").unwrap().is_empty());
+        assert!(RazorEdit::parse_list("0.0 1.0").is_err());
+        assert!(RazorEdit::parse_list("0.0 1.0 \"").is_err());
+        assert!(RazorEdit::parse_list("a 1.0 \"\"").is_err());
+    }
+
+    #[test]
+    fn parse_ext_list() {
+        let text = format!("0.0 1.0,0.0 1.0 \"{GUID}\",1.0 2.0 \"\" 0.25 0.75");
+        let edits = RazorEdit::parse_ext_list(&text).unwrap();
+        assert_eq!(
+            edits,
+            vec![
+                RazorEdit::new(pos(0.0), pos(1.0)),
+                RazorEdit {
+                    envelope_guid: Some(GUID.to_string()),
+                    ..RazorEdit::new(pos(0.0), pos(1.0))
+                },
+                RazorEdit {
+                    lane_extent: Some(RazorEditLaneExtent {
+                        top: 0.25,
+                        bottom: 0.75
+                    }),
+                    ..RazorEdit::new(pos(1.0), pos(2.0))
+                }
+            ]
+        );
+        assert!(RazorEdit::parse_ext_list("").unwrap().is_empty());
+        assert!(RazorEdit::parse_ext_list("0.0 1.0 \"\" 0.5").is_err());
+    }
+
+    #[test]
+    fn format_roundtrip() {
+        let edits = vec![
+            RazorEdit::new(pos(0.5), pos(1.25)),
+            RazorEdit {
+                envelope_guid: Some(GUID.to_string()),
+                ..RazorEdit::new(pos(2.0), pos(3.0))
+            },
+            RazorEdit {
+                lane_extent: Some(RazorEditLaneExtent {
+                    top: 0.0,
+                    bottom: 0.5,
+                }),
+                ..RazorEdit::new(pos(4.0), pos(5.0))
+            },
+        ];
+        let ext = RazorEdit::format_ext_list(&edits);
+        assert_eq!(ext, format!("0.5 1.25,2 3 \"{GUID}\",4 5 \"\" 0 0.5"));
+        assert_eq!(RazorEdit::parse_ext_list(&ext).unwrap(), edits);
+        let plain = RazorEdit::format_list(&edits);
+        assert_eq!(plain, format!("0.5 1.25 \"\" 2 3 \"{GUID}\" 4 5 \"\""));
+        let parsed = RazorEdit::parse_list(&plain).unwrap();
+        assert_eq!(parsed[2], RazorEdit::new(pos(4.0), pos(5.0)));
+    }
+}
@@ -4,31 +4,34 @@ use std::ptr::{null, null_mut, NonNull};
 
 use reaper_low::raw;
 
+use crate::fn_traits::delegating_screenset_callback;
 use crate::ProjectContext::CurrentProject;
 use crate::{
     require_media_track_panic, Accel, ActionValueChange, AddFxBehavior,
     AdvancePlaybackPositionEvent, AudioDeviceAttributeKey, AutoSeekBehavior, AutomationMode,
-    BeatAttachMode, BookmarkId, BookmarkRef, Bpm, ChunkCacheHint, CommandId, CommandItem, Db,
-    DurationInSeconds, EditMode, EnvChunkName, FadeCurvature, FadeShape, FullPitchShiftMode,
-    FxAddByNameBehavior, FxChainVisibility, FxPresetRef, FxShowInstruction, GangBehavior,
-    GetThemeColorFlags, GlobalAutomationModeOverride, HelpMode, Hidden, Hwnd, InitialAction,
-    InputMonitoringMode, InsertMediaFlag, InsertMediaMode, ItemAttributeKey, ItemGroupId,
-    KbdSectionInfo, MarkerOrRegionPosition, MasterTrackBehavior, MeasureMode, MediaItem,
-    MediaItemTake, MediaTrack, MenuOrToolbarItem, MessageBoxResult, MessageBoxType,
-    MidiImportBehavior, MidiInput, MidiInputDeviceId, MidiOutput, MidiOutputDeviceId, NativeColor,
+    BeatAttachMode, BookmarkId, BookmarkRef, Bpm, ChunkCacheHint, CommandId, CommandItem,
+    CursorContext, Db, DockRegistration, DurationInSeconds, EditMode, EnvChunkName, FadeCurvature,
+    FadeShape, FullPitchShiftMode, FxAddByNameBehavior, FxChainVisibility, FxPresetRef,
+    FxShowInstruction, GangBehavior, GetThemeColorFlags, GlobalAutomationModeOverride, HelpMode,
+    Hidden, Hmenu, Hwnd, InitialAction, InputMonitoringMode, InsertMediaFlag, InsertMediaMode,
+    ItemAttributeKey, ItemGroupId, KbdSectionInfo, LocalOscCallback, LocalOscHandler,
+    MarkerOrRegionPosition, MasterTrackBehavior, MeasureMode, MediaItem, MediaItemTake, MediaTrack,
+    MenuOrToolbarItem, MessageBoxResult, MessageBoxType, MidiImportBehavior, MidiInput,
+    MidiInputDeviceId, MidiOutput, MidiOutputDeviceId, MouseModifierFlag, NativeColor,
     NormalizedPlayRate, NotificationBehavior, OpenMediaExplorerMode, OpenProjectBehavior,
     OwnedPcmSource, OwnedReaperPitchShift, OwnedReaperResample, PanMode, ParamId, PcmSource,
-    PeakFileMode, PitchShiftMode, PitchShiftSubMode, PlaybackSpeedFactor, PluginContext,
+    PeakFileMode, PitchShiftMode, PitchShiftSubMode, PlaybackSpeedFactor, PluginContext, Point,
     PositionDescriptor, PositionInBeats, PositionInPulsesPerQuarterNote, PositionInQuarterNotes,
     PositionInSeconds, Progress, ProjectContext, ProjectInfoAttributeKey, ProjectRef,
-    PromptForActionResult, ReaProject, ReaperFunctionError, ReaperFunctionResult,
+    PromptForActionResult, RazorEdit, ReaProject, ReaperFunctionError, ReaperFunctionResult,
     ReaperNormalizedFxParamValue, ReaperPanLikeValue, ReaperPanValue, ReaperPointer, ReaperStr,
     ReaperString, ReaperStringArg, ReaperVersion, ReaperVolumeValue, ReaperWidthValue,
     RecordArmMode, RecordingInput, RecordingMode, ReorderTracksBehavior, RequiredViewMode,
-    ResampleMode, SectionContext, SectionId, SendTarget, SetTrackUiFlags, SoloMode,
-    StuffMidiMessageTarget, SubMenuStart, TakeAttributeKey, TimeModeOverride, TimeRangeType,
-    TrackArea, TrackAttributeKey, TrackDefaultsBehavior, TrackEnvelope, TrackFxChainType,
-    TrackFxLocation, TrackLocation, TrackMuteOperation, TrackMuteState, TrackPolarity,
+    ResampleMode, ScreensetCallback, ScreensetRegistration, SectionContext, SectionId, SendTarget,
+    SetThemeColorFlags, SetTrackUiFlags, SoloMode, StuffMidiMessageTarget, SubMenuStart,
+    TakeAttributeKey, ThemeColorKey, ThemeLayoutIndex, TimeModeOverride, TimeRangeType, TrackArea,
+    TrackAttributeKey, TrackDefaultsBehavior, TrackEnvelope, TrackFxChainType, TrackFxLocation,
+    TrackLocation, TrackMuteOperation, TrackMuteState, TrackPart, TrackPolarity,
     TrackPolarityOperation, TrackRecArmOperation, TrackSendAttributeKey, TrackSendCategory,
     TrackSendDirection, TrackSendRef, TrackSoloOperation, TransferBehavior, UiRefreshBehavior,
     UndoBehavior, UndoScope, ValueChange, VolumeSliderValue, WindowContext,
@@ -450,6 +453,253 @@ where
         self.low.UpdateArrange();
     }
 
+    /// Recalculates the lane arrangement of fixed lane tracks.
+    ///
+    /// This includes auto-removing empty lanes at the bottom of the track.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    pub fn update_item_lanes(&self, project: ProjectContext)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.require_valid_project(project);
+        unsafe {
+            self.update_item_lanes_unchecked(project);
+        }
+    }
+
+    /// Like [`update_item_lanes()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`update_item_lanes()`]: #method.update_item_lanes
+    pub unsafe fn update_item_lanes_unchecked(&self, project: ProjectContext)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.UpdateItemLanes(project.to_raw());
+    }
+
+    /// Returns the current cursor context.
+    ///
+    /// If `want_last_valid` is `true`, returns the last valid context instead of
+    /// [`CursorContext::Unknown`] if there's no valid context at the moment.
+    ///
+    /// [`CursorContext::Unknown`]: enum.CursorContext.html#variant.Unknown
+    pub fn get_cursor_context_2(&self, want_last_valid: bool) -> CursorContext
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        CursorContext::from_raw(self.low.GetCursorContext2(want_last_valid))
+    }
+
+    /// Changes the focus programmatically.
+    ///
+    /// [`CursorContext::Tracks`] focuses the track panels, [`CursorContext::Items`] focuses the
+    /// arrange window. [`CursorContext::Envelopes`] focuses the arrange window and selects the
+    /// given envelope (or clears the current track/take envelope selection if `None`).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    ///
+    /// [`CursorContext::Tracks`]: enum.CursorContext.html#variant.Tracks
+    /// [`CursorContext::Items`]: enum.CursorContext.html#variant.Items
+    /// [`CursorContext::Envelopes`]: enum.CursorContext.html#variant.Envelopes
+    pub unsafe fn set_cursor_context(&self, context: CursorContext, envelope: Option<TrackEnvelope>)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.SetCursorContext(
+            context.to_raw(),
+            envelope.map(|e| e.as_ptr()).unwrap_or(null_mut()),
+        );
+    }
+
+    /// Returns the current mouse position in screen coordinates.
+    ///
+    /// Coordinates left of or above the primary screen are negative. Cast them to `i32` to get
+    /// the signed value.
+    pub fn get_mouse_position(&self) -> Point
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut x = MaybeUninit::zeroed();
+        let mut y = MaybeUninit::zeroed();
+        unsafe {
+            self.low.GetMousePosition(x.as_mut_ptr(), y.as_mut_ptr());
+            Point {
+                x: x.assume_init() as u32,
+                y: y.assume_init() as u32,
+            }
+        }
+    }
+
+    /// Hit tests the given point in screen coordinates.
+    ///
+    /// The returned info string describes what has been hit, e.g. `arrange`, `fx_chain`, `fx_0`
+    /// (first FX in chain, floating) or `spacer_0` (spacer before first track). If a track panel is
+    /// hit, it begins with `tcp` or `mcp`, e.g. `tcp.mute`.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the info string you want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    pub fn get_thing_from_point(&self, point: Point, buffer_size: u32) -> GetThingFromPointResult
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (info, ptr) = with_string_buffer(buffer_size, |buffer, max_size| unsafe {
+            self.low
+                .GetThingFromPoint(point.x as _, point.y as _, buffer, max_size)
+        });
+        GetThingFromPointResult {
+            track: MediaTrack::new(ptr),
+            info,
+        }
+    }
+
+    /// Returns the first item (and the take) at the given point in screen coordinates.
+    ///
+    /// If `allow_locked` is `false`, locked items are ignored.
+    pub fn get_item_from_point(
+        &self,
+        point: Point,
+        allow_locked: bool,
+    ) -> Option<GetItemFromPointResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut take = MaybeUninit::zeroed();
+        let ptr = unsafe {
+            self.low
+                .GetItemFromPoint(point.x as _, point.y as _, allow_locked, take.as_mut_ptr())
+        };
+        let item = MediaItem::new(ptr)?;
+        let result = GetItemFromPointResult {
+            item,
+            take: MediaItemTake::new(unsafe { take.assume_init() }),
+        };
+        Some(result)
+    }
+
+    /// Returns the track at the given point in screen coordinates.
+    ///
+    /// If the point refers to a window associated to the track (such as FX), the track will be
+    /// returned as well.
+    pub fn get_track_from_point(&self, point: Point) -> Option<GetTrackFromPointResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut info = MaybeUninit::zeroed();
+        let ptr = unsafe {
+            self.low
+                .GetTrackFromPoint(point.x as _, point.y as _, info.as_mut_ptr())
+        };
+        let track = MediaTrack::new(ptr)?;
+        let info: i32 = unsafe { info.assume_init() };
+        let result = GetTrackFromPointResult {
+            track,
+            part: TrackPart::from_raw(info & 0xFF),
+            lane: ((info >> 8) & 0xFF) as u32,
+        };
+        Some(result)
+    }
+
+    /// Grants temporary access to the current mouse modifier assignment for the given modifier
+    /// keys in the given context.
+    ///
+    /// The context is a string such as `MM_CTX_ITEM` (see `reaper-mouse.ini`) or
+    /// `Media item left drag` (unlocalized). The action is either the command ID of a built-in mouse
+    /// modifier or REAPER action, or a custom action ID string. It may have a space and `c` or `m`
+    /// appended to it to specify command ID vs. mouse modifier ID.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the action string you want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    pub fn get_mouse_modifier<'a>(
+        &self,
+        context: impl Into<ReaperStringArg<'a>>,
+        modifiers: BitFlags<MouseModifierFlag>,
+        buffer_size: u32,
+    ) -> ReaperString
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (action, _) = with_string_buffer(buffer_size, |buffer, max_size| unsafe {
+            self.low.GetMouseModifier(
+                context.into().as_ptr(),
+                modifiers.bits() as _,
+                buffer,
+                max_size,
+            )
+        });
+        action
+    }
+
+    /// Sets the mouse modifier assignment for the given modifier keys in the given context.
+    ///
+    /// See [`get_mouse_modifier()`] for the format of context and action.
+    ///
+    /// [`get_mouse_modifier()`]: #method.get_mouse_modifier
+    pub fn set_mouse_modifier<'a>(
+        &self,
+        context: impl Into<ReaperStringArg<'a>>,
+        modifiers: BitFlags<MouseModifierFlag>,
+        action: impl Into<ReaperStringArg<'a>>,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        unsafe {
+            self.low.SetMouseModifier(
+                context.into().as_ptr(),
+                modifiers.bits() as _,
+                action.into().as_ptr(),
+            );
+        }
+    }
+
+    /// Resets the mouse modifier assignment for the given modifier keys in the given context to
+    /// the default.
+    ///
+    /// If `modifiers` is `None`, the entire context is reset.
+    pub fn reset_mouse_modifier<'a>(
+        &self,
+        context: impl Into<ReaperStringArg<'a>>,
+        modifiers: Option<BitFlags<MouseModifierFlag>>,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let modifier_flag = modifiers.map(|m| m.bits() as i32).unwrap_or(-1);
+        unsafe {
+            self.low.SetMouseModifier(
+                context.into().as_ptr(),
+                modifier_flag,
+                reaper_str!("-1").as_ptr(),
+            );
+        }
+    }
+
     pub fn get_theme_color<'a>(
         &self,
         ini_key: impl Into<ReaperStringArg<'a>>,
@@ -469,6 +719,196 @@ where
         Ok(NativeColor::new(color))
     }
 
+    /// Temporarily changes the given theme color or resets it to the theme default if `color` is
+    /// `None`.
+    ///
+    /// Returns the color as it's applied (potentially transformed). The UI is not updated by this,
+    /// you should call [`update_arrange()`] or similar functions as necessary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the color couldn't be set (e.g. if the key is unknown).
+    ///
+    /// [`update_arrange()`]: #method.update_arrange
+    pub fn set_theme_color(
+        &self,
+        key: ThemeColorKey,
+        color: Option<RgbColor>,
+        flags: BitFlags<SetThemeColorFlags>,
+    ) -> ReaperFunctionResult<RgbColor>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let raw_color = match color {
+            None => -1,
+            Some(c) => self.color_to_native(c).to_raw(),
+        };
+        let result = unsafe {
+            self.low
+                .SetThemeColor(key.into_raw().as_ptr(), raw_color, flags.bits() as _)
+        };
+        if result == -1 {
+            return Err(ReaperFunctionError::new("failed to set theme color"));
+        }
+        Ok(self.color_from_native(NativeColor::new(result)))
+    }
+
+    /// Returns the theme layout parameter at the given index or `None` if the index is out of
+    /// range.
+    pub fn theme_layout_get_parameter(&self, index: u32) -> Option<ThemeLayoutParameter>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut desc = null();
+        let mut value = MaybeUninit::zeroed();
+        let mut default_value = MaybeUninit::zeroed();
+        let mut min_value = MaybeUninit::zeroed();
+        let mut max_value = MaybeUninit::zeroed();
+        let name = unsafe {
+            let ptr = self.low.ThemeLayout_GetParameter(
+                index as i32,
+                &mut desc,
+                value.as_mut_ptr(),
+                default_value.as_mut_ptr(),
+                min_value.as_mut_ptr(),
+                max_value.as_mut_ptr(),
+            );
+            create_passing_c_str(ptr)?
+        };
+        if name.to_str().is_empty() {
+            return None;
+        }
+        let parameter = unsafe {
+            ThemeLayoutParameter {
+                name: name.to_reaper_string(),
+                description: create_passing_c_str(desc)
+                    .map(|d| d.to_reaper_string())
+                    .unwrap_or_default(),
+                value: value.assume_init(),
+                default_value: default_value.assume_init(),
+                min_value: min_value.assume_init(),
+                max_value: max_value.assume_init(),
+            }
+        };
+        Some(parameter)
+    }
+
+    /// Sets the value of the theme layout parameter at the given index.
+    ///
+    /// If `persist` is `true`, the value is also used the next time the theme is loaded. Call
+    /// [`theme_layout_refresh_all()`] to make the change visible.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index is out of range or the value is invalid.
+    ///
+    /// [`theme_layout_refresh_all()`]: #method.theme_layout_refresh_all
+    pub fn theme_layout_set_parameter(
+        &self,
+        index: u32,
+        value: i32,
+        persist: bool,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful = self
+            .low
+            .ThemeLayout_SetParameter(index as i32, value, persist);
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't set theme layout parameter",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Refreshes all layouts, e.g. after changing theme layout parameters.
+    pub fn theme_layout_refresh_all(&self)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.ThemeLayout_RefreshAll();
+    }
+
+    /// Returns layout information of the given theme layout section (e.g. `tcp`, `mcp`, `trans`
+    /// or `global` for the global layout override).
+    ///
+    /// Pass `seclist` as section in order to enumerate the available sections.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the name you want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    pub fn theme_layout_get_layout<'a>(
+        &self,
+        section: impl Into<ReaperStringArg<'a>>,
+        index: ThemeLayoutIndex,
+        buffer_size: u32,
+    ) -> Option<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (name, successful) = with_string_buffer(buffer_size, |buffer, max_size| unsafe {
+            self.low.ThemeLayout_GetLayout(
+                section.into().as_ptr(),
+                index.to_raw(),
+                buffer,
+                max_size,
+            )
+        });
+        if !successful {
+            return None;
+        }
+        Some(name)
+    }
+
+    /// Sets the active layout of the given theme layout section (e.g. `global` for the global
+    /// layout override). Pass an empty layout name in order to clear it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout couldn't be set.
+    pub fn theme_layout_set_layout<'a>(
+        &self,
+        section: impl Into<ReaperStringArg<'a>>,
+        layout: impl Into<ReaperStringArg<'a>>,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful = unsafe {
+            self.low
+                .ThemeLayout_SetLayout(section.into().as_ptr(), layout.into().as_ptr())
+        };
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't set theme layout"));
+        }
+        Ok(())
+    }
+
+    /// Returns the path of the most recently loaded theme file.
+    pub fn get_last_color_theme_file(&self) -> Option<Utf8PathBuf>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.low.GetLastColorThemeFile();
+        let file = unsafe { create_passing_c_str(ptr)? };
+        if file.to_str().is_empty() {
+            return None;
+        }
+        Some(Utf8PathBuf::from(file.to_str()))
+    }
+
     /// Updates the track list after a minor change.
     pub fn track_list_adjust_windows_minor(&self)
     where
@@ -630,6 +1070,101 @@ where
         self.low.AddExtensionsMainMenu();
     }
 
+    /// Registers a menu which the user can customize in REAPER's "Customize menus/toolbars" dialog.
+    ///
+    /// The menu ID must be unique. Its default content can be populated with
+    /// [`crate::ReaperSession::plugin_register_add_hook_custom_menu`], which gets passed the menu
+    /// ID. The menu name is only relevant for main menus (displayed in the menu bar). Pass the name
+    /// of a keyboard section registered by this extension as `kbd_section_name` if the menu
+    /// contains actions of that section, otherwise actions of the main section are assumed.
+    ///
+    /// Returns `true` if the menu was added.
+    pub fn add_customizable_menu<'a>(
+        &self,
+        menu_id: impl Into<ReaperStringArg<'a>>,
+        menu_name: impl Into<ReaperStringArg<'a>>,
+        kbd_section_name: Option<&ReaperStr>,
+        add_to_main_menu: bool,
+    ) -> bool
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        unsafe {
+            self.low.AddCustomizableMenu(
+                menu_id.into().as_ptr(),
+                menu_name.into().as_ptr(),
+                kbd_section_name.map(|n| n.as_ptr()).unwrap_or(null()),
+                add_to_main_menu,
+            )
+        }
+    }
+
+    /// Returns one of REAPER's built-in context menus.
+    ///
+    /// Index 0 is the track control panel context menu, 1 the item context menu, 2 the ruler
+    /// context menu and 3 the empty track area context menu.
+    pub fn get_context_menu(&self, index: u32) -> Option<Hmenu>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.low.GetContextMenu(index as i32);
+        Hmenu::new(ptr)
+    }
+
+    /// Shows a context menu at the given screen location.
+    ///
+    /// Valid names are those of built-in context menus (e.g. `track_panel`, `track_area`, `item`,
+    /// `ruler` or `envelope`) and IDs of menus registered via [`add_customizable_menu()`].
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid window handle.
+    ///
+    /// [`add_customizable_menu()`]: #method.add_customizable_menu
+    pub unsafe fn show_popup_menu<'a>(
+        &self,
+        name: impl Into<ReaperStringArg<'a>>,
+        location: Point,
+        parent_window: Option<Hwnd>,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.ShowPopupMenu(
+            name.into().as_ptr(),
+            location.x as i32,
+            location.y as i32,
+            parent_window.map(|w| w.as_ptr()).unwrap_or(null_mut()),
+            null_mut(),
+            0,
+            0,
+        );
+    }
+
+    /// Refreshes the toolbar buttons (and other UI elements) which show the toggle state of the
+    /// given main section action.
+    pub fn refresh_toolbar(&self, command_id: CommandId)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.RefreshToolbar(command_id.to_raw());
+    }
+
+    /// Like [`refresh_toolbar()`] but for actions of arbitrary sections.
+    ///
+    /// [`refresh_toolbar()`]: #method.refresh_toolbar
+    pub fn refresh_toolbar_2(&self, section_id: SectionId, command_id: CommandId)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low
+            .RefreshToolbar2(section_id.get() as i32, command_id.to_raw());
+    }
+
     /// Gets or sets an item attribute.
     ///
     /// Returns the current value if `new_value` is `null_mut()`.
@@ -805,6 +1340,20 @@ where
         NonNull::new(previous_source_ptr).map(|raw| OwnedPcmSource::from_raw(raw))
     }
 
+    /// Convenience function which returns the take's GUID (`GUID`).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    pub unsafe fn get_set_media_item_take_info_get_guid(&self, take: MediaItemTake) -> GUID
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.get_set_media_item_take_info(take, TakeAttributeKey::Guid, null_mut());
+        deref_as::<GUID>(ptr).expect("GUID pointer is null")
+    }
+
     /// Convenience function which returns the take's custom color (`I_CUSTOMCOLOR`).
     ///
     /// # Safety
@@ -944,79 +1493,210 @@ where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self.get_set_media_track_info(track, TrackAttributeKey::Project, null_mut())
-            as *mut raw::ReaProject;
-        ReaProject::new(ptr)
+        let ptr = self.get_set_media_track_info(track, TrackAttributeKey::Project, null_mut())
+            as *mut raw::ReaProject;
+        ReaProject::new(ptr)
+    }
+
+    /// Convenience function which grants temporary access to the given track's name (`P_NAME`).
+    ///
+    /// Returns `None` if the given track is the master track.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use reaper_medium::ProjectContext::CurrentProject;
+    /// let session = reaper_medium::ReaperSession::default();
+    ///
+    /// let track = session.reaper().get_track(CurrentProject, 0).ok_or("no track")?;
+    /// let track_name = unsafe {
+    ///     session.reaper().get_set_media_track_info_get_name(
+    ///         track,
+    ///         |name| name.to_owned()
+    ///     )
+    /// };
+    /// let track_name = match &track_name {
+    ///     None => "Master track",
+    ///     Some(name) => name.to_str()
+    /// };
+    /// session.reaper().show_console_msg(format!("Track name is {}", track_name));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn get_set_media_track_info_get_name<R>(
+        &self,
+        track: MediaTrack,
+        use_name: impl FnOnce(&ReaperStr) -> R,
+    ) -> Option<R>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.get_set_media_track_info(track, TrackAttributeKey::Name, null_mut());
+        create_passing_c_str(ptr as *const c_char).map(use_name)
+    }
+
+    /// Convenience function which sets the track's name (`P_NAME`).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use reaper_medium::ProjectContext::CurrentProject;
+    /// let session = reaper_medium::ReaperSession::default();
+    ///
+    /// let track = session.reaper().get_track(CurrentProject, 0).ok_or("no track")?;
+    /// unsafe {
+    ///     session.reaper().get_set_media_track_info_set_name(track, "Guitar");
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn get_set_media_track_info_set_name<'a>(
+        &self,
+        track: MediaTrack,
+        name: impl Into<ReaperStringArg<'a>>,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.get_set_media_track_info(track, TrackAttributeKey::Name, name.into().as_ptr() as _);
+    }
+
+    /// Convenience function which grants temporary access to the name of the given fixed lane
+    /// (`P_LANENAME:n`).
+    ///
+    /// Returns `None` if the given track is not in fixed lanes mode.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn get_set_media_track_info_get_lane_name<R>(
+        &self,
+        track: MediaTrack,
+        lane_index: u32,
+        use_name: impl FnOnce(&ReaperStr) -> R,
+    ) -> Option<R>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.get_set_media_track_info(
+            track,
+            TrackAttributeKey::LaneName(lane_index),
+            null_mut(),
+        );
+        create_passing_c_str(ptr as *const c_char).map(use_name)
+    }
+
+    /// Convenience function which sets the name of the given fixed lane (`P_LANENAME:n`).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn get_set_media_track_info_set_lane_name<'a>(
+        &self,
+        track: MediaTrack,
+        lane_index: u32,
+        name: impl Into<ReaperStringArg<'a>>,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.get_set_media_track_info(
+            track,
+            TrackAttributeKey::LaneName(lane_index),
+            name.into().as_ptr() as _,
+        );
+    }
+
+    /// Convenience function which grants temporary access to the name of the layout used by the
+    /// given track in the given area (`P_TCP_LAYOUT` or `P_MCP_LAYOUT`).
+    ///
+    /// An empty name means that the default layout is used.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn get_set_media_track_info_get_layout<R>(
+        &self,
+        track: MediaTrack,
+        area: TrackArea,
+        use_name: impl FnOnce(&ReaperStr) -> R,
+    ) -> Option<R>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.get_set_media_track_info(track, layout_attribute_key(area), null_mut());
+        create_passing_c_str(ptr as *const c_char).map(use_name)
+    }
+
+    /// Convenience function which sets the layout used by the given track in the given area
+    /// (`P_TCP_LAYOUT` or `P_MCP_LAYOUT`). Pass an empty name in order to use the default layout.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn get_set_media_track_info_set_layout<'a>(
+        &self,
+        track: MediaTrack,
+        area: TrackArea,
+        name: impl Into<ReaperStringArg<'a>>,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.get_set_media_track_info(track, layout_attribute_key(area), name.into().as_ptr() as _);
     }
 
-    /// Convenience function which grants temporary access to the given track's name (`P_NAME`).
-    ///
-    /// Returns `None` if the given track is the master track.
-    ///
-    /// # Example
+    /// Convenience function which returns the razor edit areas of the given track
+    /// (`P_RAZOREDITS_EXT`).
     ///
-    /// ```no_run
-    /// # use reaper_medium::ProjectContext::CurrentProject;
-    /// let session = reaper_medium::ReaperSession::default();
+    /// # Errors
     ///
-    /// let track = session.reaper().get_track(CurrentProject, 0).ok_or("no track")?;
-    /// let track_name = unsafe {
-    ///     session.reaper().get_set_media_track_info_get_name(
-    ///         track,
-    ///         |name| name.to_owned()
-    ///     )
-    /// };
-    /// let track_name = match &track_name {
-    ///     None => "Master track",
-    ///     Some(name) => name.to_str()
-    /// };
-    /// session.reaper().show_console_msg(format!("Track name is {}", track_name));
-    /// # Ok::<_, Box<dyn std::error::Error>>(())
-    /// ```
+    /// Returns an error if REAPER returns a string which can't be parsed.
     ///
     /// # Safety
     ///
     /// REAPER can crash if you pass an invalid track.
-    pub unsafe fn get_set_media_track_info_get_name<R>(
+    pub unsafe fn get_set_media_track_info_get_razor_edits(
         &self,
         track: MediaTrack,
-        use_name: impl FnOnce(&ReaperStr) -> R,
-    ) -> Option<R>
+    ) -> ReaperFunctionResult<Vec<RazorEdit>>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self.get_set_media_track_info(track, TrackAttributeKey::Name, null_mut());
-        create_passing_c_str(ptr as *const c_char).map(use_name)
+        let ptr =
+            self.get_set_media_track_info(track, TrackAttributeKey::RazorEditsExt, null_mut());
+        match create_passing_c_str(ptr as *const c_char) {
+            None => Ok(vec![]),
+            Some(s) => RazorEdit::parse_ext_list(s.to_str()),
+        }
     }
 
-    /// Convenience function which sets the track's name (`P_NAME`).
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// # use reaper_medium::ProjectContext::CurrentProject;
-    /// let session = reaper_medium::ReaperSession::default();
-    ///
-    /// let track = session.reaper().get_track(CurrentProject, 0).ok_or("no track")?;
-    /// unsafe {
-    ///     session.reaper().get_set_media_track_info_set_name(track, "Guitar");
-    /// }
-    /// # Ok::<_, Box<dyn std::error::Error>>(())
-    /// ```
+    /// Convenience function which replaces the razor edit areas of the given track
+    /// (`P_RAZOREDITS_EXT`).
     ///
     /// # Safety
     ///
     /// REAPER can crash if you pass an invalid track.
-    pub unsafe fn get_set_media_track_info_set_name<'a>(
+    pub unsafe fn get_set_media_track_info_set_razor_edits(
         &self,
         track: MediaTrack,
-        name: impl Into<ReaperStringArg<'a>>,
+        edits: &[RazorEdit],
     ) where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        self.get_set_media_track_info(track, TrackAttributeKey::Name, name.into().as_ptr() as _);
+        let text = ReaperString::from_string(RazorEdit::format_ext_list(edits));
+        self.get_set_media_track_info(track, TrackAttributeKey::RazorEditsExt, text.as_ptr() as _);
     }
 
     /// Convenience function which returns the item's beat attach mode (`C_BEATATTACHMODE`).
@@ -1457,6 +2137,20 @@ where
         ItemGroupId::new(raw)
     }
 
+    /// Convenience function which returns the item's GUID (`GUID`).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid item.
+    pub unsafe fn get_set_media_item_info_get_guid(&self, item: MediaItem) -> GUID
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.get_set_media_item_info(item, ItemAttributeKey::Guid, null_mut());
+        deref_as::<GUID>(ptr).expect("GUID pointer is null")
+    }
+
     /// Convenience function which sets the item's group ID (`D_GROUPID`).
     ///
     /// # Safety
@@ -3291,6 +3985,38 @@ where
         )
     }
 
+    /// Performs an action belonging to the given section by running it through the registered
+    /// action hooks.
+    ///
+    /// This is the way to invoke actions of custom sections registered by extensions. Returns
+    /// whether a hook has handled the action.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid section or window.
+    pub unsafe fn kbd_run_command_through_hooks(
+        &self,
+        section: SectionContext,
+        command_id: CommandId,
+        value_change: ActionValueChange,
+        window: WindowContext,
+    ) -> bool
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let command_id = command_id.to_raw();
+        let (val, valhw, relmode) = value_change.to_raw();
+        self.low.kbd_RunCommandThroughHooks(
+            section.to_raw(),
+            &command_id,
+            &val,
+            &valhw,
+            &relmode,
+            window.to_raw(),
+        )
+    }
+
     /// Opens an action picker window for prompting the user to select an action.
     pub fn prompt_for_action_create(&self, initial: InitialAction, section_id: SectionId)
     where
@@ -5494,95 +6220,329 @@ where
             .SetTrackAutomationMode(track.as_ptr(), automation_mode.to_raw());
     }
 
-    /// Returns the global track automation override, if any.
-    pub fn get_global_automation_override(&self) -> Option<GlobalAutomationModeOverride>
+    /// Returns the global track automation override, if any.
+    pub fn get_global_automation_override(&self) -> Option<GlobalAutomationModeOverride>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        use GlobalAutomationModeOverride::*;
+        match self.low.GetGlobalAutomationOverride() {
+            -1 => None,
+            6 => Some(Bypass),
+            x => Some(Mode(AutomationMode::from_raw(x))),
+        }
+    }
+
+    /// Sets the global track automation override.
+    pub fn set_global_automation_override(
+        &self,
+        mode_override: Option<GlobalAutomationModeOverride>,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        use GlobalAutomationModeOverride::*;
+        let raw = match mode_override {
+            None => -1,
+            Some(Bypass) => 6,
+            Some(Mode(x)) => x.to_raw(),
+        };
+        self.low.SetGlobalAutomationOverride(raw);
+    }
+
+    /// Returns the track envelope for the given track and configuration chunk name.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    // TODO-low Test
+    pub unsafe fn get_track_envelope_by_chunk_name(
+        &self,
+        track: MediaTrack,
+        chunk_name: EnvChunkName,
+    ) -> Option<TrackEnvelope>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self
+            .low
+            .GetTrackEnvelopeByChunkName(track.as_ptr(), chunk_name.into_raw().as_ptr());
+        TrackEnvelope::new(ptr)
+    }
+
+    /// Returns the track envelope for the given track and envelope display name.
+    ///
+    /// For getting common envelopes (like volume or pan) using
+    /// [`get_track_envelope_by_chunk_name()`] is better because it provides more type safety.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    ///
+    /// [`get_track_envelope_by_chunk_name()`]: #method.get_track_envelope_by_chunk_name
+    pub unsafe fn get_track_envelope_by_name<'a>(
+        &self,
+        track: MediaTrack,
+        env_name: impl Into<ReaperStringArg<'a>>,
+    ) -> Option<TrackEnvelope>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self
+            .low
+            .GetTrackEnvelopeByName(track.as_ptr(), env_name.into().as_ptr());
+        TrackEnvelope::new(ptr)
+    }
+
+    /// Counts the number of envelopes of the given track.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn count_track_envelopes(&self, track: MediaTrack) -> u32
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.CountTrackEnvelopes(track.as_ptr()) as u32
+    }
+
+    /// Returns the envelope of the given track at the given index.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn get_track_envelope(
+        &self,
+        track: MediaTrack,
+        envelope_index: u32,
+    ) -> Option<TrackEnvelope>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self
+            .low
+            .GetTrackEnvelope(track.as_ptr(), envelope_index as i32);
+        TrackEnvelope::new(ptr)
+    }
+
+    /// Returns the RPPXML state of the given envelope.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the chunk you want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    pub unsafe fn get_envelope_state_chunk(
+        &self,
+        envelope: TrackEnvelope,
+        buffer_size: u32,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (chunk_content, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.GetEnvelopeStateChunk(
+                envelope.as_ptr(),
+                buffer,
+                max_size,
+                cache_hint == ChunkCacheHint::UndoMode,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't get envelope chunk"));
+        }
+        Ok(chunk_content)
+    }
+
+    /// Returns the name of the given envelope.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    pub unsafe fn get_envelope_name(
+        &self,
+        envelope: TrackEnvelope,
+        buffer_size: u32,
+    ) -> ReaperString
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (name, _) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low
+                .GetEnvelopeName(envelope.as_ptr(), buffer, max_size)
+        });
+        name
+    }
+
+    /// Returns a string attribute of the given envelope, e.g. `ACTIVE`, `VISIBLE` or `ARM`.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the value you want.
+    ///
+    /// Returns `None` if the attribute is not supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    pub unsafe fn get_set_envelope_info_string_get<'a>(
+        &self,
+        envelope: TrackEnvelope,
+        attribute_key: impl Into<ReaperStringArg<'a>>,
+        buffer_size: u32,
+    ) -> Option<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let attribute_key = attribute_key.into();
+        let (value, successful) = with_string_buffer(buffer_size, |buffer, _| {
+            self.low.GetSetEnvelopeInfo_String(
+                envelope.as_ptr(),
+                attribute_key.as_ptr(),
+                buffer,
+                false,
+            )
+        });
+        if !successful {
+            return None;
+        }
+        Some(value)
+    }
+
+    /// Returns the number of points of the given envelope.
+    ///
+    /// Doesn't count the points of automation items.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    pub unsafe fn count_envelope_points(&self, envelope: TrackEnvelope) -> u32
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        use GlobalAutomationModeOverride::*;
-        match self.low.GetGlobalAutomationOverride() {
-            -1 => None,
-            6 => Some(Bypass),
-            x => Some(Mode(AutomationMode::from_raw(x))),
-        }
+        self.low.CountEnvelopePoints(envelope.as_ptr()) as u32
     }
 
-    /// Sets the global track automation override.
-    pub fn set_global_automation_override(
+    /// Returns the envelope point at the given index.
+    ///
+    /// If `automation_item_index` is `None`, this queries the points of the envelope itself,
+    /// otherwise the points of the given automation item. Returns `None` if there's no point at
+    /// the given index.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    pub unsafe fn get_envelope_point_ex(
         &self,
-        mode_override: Option<GlobalAutomationModeOverride>,
-    ) where
+        envelope: TrackEnvelope,
+        automation_item_index: Option<u32>,
+        point_index: u32,
+    ) -> Option<GetEnvelopePointExResult>
+    where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        use GlobalAutomationModeOverride::*;
-        let raw = match mode_override {
-            None => -1,
-            Some(Bypass) => 6,
-            Some(Mode(x)) => x.to_raw(),
+        let mut time = MaybeUninit::zeroed();
+        let mut value = MaybeUninit::zeroed();
+        let mut shape = MaybeUninit::zeroed();
+        let mut tension = MaybeUninit::zeroed();
+        let mut is_selected = MaybeUninit::zeroed();
+        let successful = self.low.GetEnvelopePointEx(
+            envelope.as_ptr(),
+            automation_item_index.map(|i| i as i32).unwrap_or(-1),
+            point_index as _,
+            time.as_mut_ptr(),
+            value.as_mut_ptr(),
+            shape.as_mut_ptr(),
+            tension.as_mut_ptr(),
+            is_selected.as_mut_ptr(),
+        );
+        if !successful {
+            return None;
+        }
+        let result = GetEnvelopePointExResult {
+            position: PositionInSeconds::new_panic(time.assume_init()),
+            value: value.assume_init(),
+            shape: shape.assume_init() as u32,
+            tension: tension.assume_init(),
+            is_selected: is_selected.assume_init(),
         };
-        self.low.SetGlobalAutomationOverride(raw);
+        Some(result)
     }
 
-    /// Returns the track envelope for the given track and configuration chunk name.
+    /// Returns the current peak volume for the given track channel.
     ///
     /// # Safety
     ///
     /// REAPER can crash if you pass an invalid track.
-    // TODO-low Test
-    pub unsafe fn get_track_envelope_by_chunk_name(
-        &self,
-        track: MediaTrack,
-        chunk_name: EnvChunkName,
-    ) -> Option<TrackEnvelope>
+    pub unsafe fn track_get_peak_info(&self, track: MediaTrack, channel: u32) -> ReaperVolumeValue
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self
-            .low
-            .GetTrackEnvelopeByChunkName(track.as_ptr(), chunk_name.into_raw().as_ptr());
-        TrackEnvelope::new(ptr)
+        let result = self.low.Track_GetPeakInfo(track.as_ptr(), channel as _);
+        ReaperVolumeValue::new_panic(result)
     }
 
-    /// Returns the track envelope for the given track and envelope display name.
+    /// Returns the peak hold value for the given track channel.
     ///
-    /// For getting common envelopes (like volume or pan) using
-    /// [`get_track_envelope_by_chunk_name()`] is better because it provides more type safety.
+    /// If `clear` is `true`, the peak hold is reset after querying it.
+    ///
+    /// Use channel 1024 or 1025 to query loudness values. This only works if this is the master
+    /// track or if the VU meters of this track are set to display loudness.
     ///
     /// # Safety
     ///
     /// REAPER can crash if you pass an invalid track.
-    ///
-    /// [`get_track_envelope_by_chunk_name()`]: #method.get_track_envelope_by_chunk_name
-    pub unsafe fn get_track_envelope_by_name<'a>(
-        &self,
-        track: MediaTrack,
-        env_name: impl Into<ReaperStringArg<'a>>,
-    ) -> Option<TrackEnvelope>
+    pub unsafe fn track_get_peak_hold_db(&self, track: MediaTrack, channel: u32, clear: bool) -> Db
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self
+        let result = self
             .low
-            .GetTrackEnvelopeByName(track.as_ptr(), env_name.into().as_ptr());
-        TrackEnvelope::new(ptr)
+            .Track_GetPeakHoldDB(track.as_ptr(), channel as _, clear);
+        // REAPER returns dB * 0.01
+        let db = result * 100.0;
+        if db.is_nan() {
+            return Db::NAN;
+        }
+        Db::new_panic(db.max(Db::MIN.get()))
     }
 
-    /// Returns the current peak volume for the given track channel.
+    /// Returns the approximate current level of the given hardware input.
     ///
-    /// # Safety
-    ///
-    /// REAPER can crash if you pass an invalid track.
-    pub unsafe fn track_get_peak_info(&self, track: MediaTrack, channel: u32) -> ReaperVolumeValue
+    /// Returns `None` if the level is not available.
+    pub fn get_input_activity_level(&self, input: RecordingInput) -> Option<ReaperVolumeValue>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let result = self.low.Track_GetPeakInfo(track.as_ptr(), channel as _);
-        ReaperVolumeValue::new_panic(result)
+        let result = self.low.GetInputActivityLevel(input.to_raw());
+        ReaperVolumeValue::new(result).ok()
     }
 
     /// Gets a track attribute as numerical value.
@@ -6014,6 +6974,83 @@ where
         self.low.CountTempoTimeSigMarkers(project.to_raw()) as u32
     }
 
+    /// Returns information about the tempo/time signature marker with the given index.
+    ///
+    /// Returns `None` if there's no marker at that index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    pub fn get_tempo_time_sig_marker(
+        &self,
+        project: ProjectContext,
+        index: u32,
+    ) -> Option<GetTempoTimeSigMarkerResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_valid_project(project);
+        unsafe { self.get_tempo_time_sig_marker_unchecked(project, index) }
+    }
+
+    /// Like [`get_tempo_time_sig_marker()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`get_tempo_time_sig_marker()`]: #method.get_tempo_time_sig_marker
+    pub unsafe fn get_tempo_time_sig_marker_unchecked(
+        &self,
+        project: ProjectContext,
+        index: u32,
+    ) -> Option<GetTempoTimeSigMarkerResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut position = MaybeUninit::zeroed();
+        let mut measure_index = MaybeUninit::zeroed();
+        let mut beats_since_measure = MaybeUninit::zeroed();
+        let mut bpm = MaybeUninit::zeroed();
+        let mut num = MaybeUninit::zeroed();
+        let mut denom = MaybeUninit::zeroed();
+        let mut is_linear = MaybeUninit::zeroed();
+        let successful = self.low.GetTempoTimeSigMarker(
+            project.to_raw(),
+            index as _,
+            position.as_mut_ptr(),
+            measure_index.as_mut_ptr(),
+            beats_since_measure.as_mut_ptr(),
+            bpm.as_mut_ptr(),
+            num.as_mut_ptr(),
+            denom.as_mut_ptr(),
+            is_linear.as_mut_ptr(),
+        );
+        if !successful {
+            return None;
+        }
+        let num = num.assume_init();
+        let denom = denom.assume_init();
+        let time_signature = if num > 0 && denom > 0 {
+            Some(TimeSignature {
+                numerator: NonZeroU32::new(num as _).unwrap(),
+                denominator: NonZeroU32::new(denom as _).unwrap(),
+            })
+        } else {
+            None
+        };
+        let result = GetTempoTimeSigMarkerResult {
+            position: PositionInSeconds::new_panic(position.assume_init()),
+            measure_index: measure_index.assume_init(),
+            beats_since_measure: PositionInBeats::new_panic(beats_since_measure.assume_init()),
+            tempo: Bpm::new_panic(bpm.assume_init()),
+            time_signature,
+            is_linear: is_linear.assume_init(),
+        };
+        Some(result)
+    }
+
     /// Converts the given playback speed factor to a normalized play rate.
     pub fn master_normalize_play_rate_normalize(
         &self,
@@ -7234,6 +8271,36 @@ where
         })
     }
 
+    /// Convenience function which returns the given envelope (`P_ENV`) of the given track send,
+    /// hardware output send or track receive.
+    ///
+    /// Use [`EnvChunkName::VolEnv`], [`EnvChunkName::PanEnv`] or [`EnvChunkName::MuteEnv`] for
+    /// the send volume, pan and mute envelope. Returns `None` if the envelope doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn get_track_send_info_env(
+        &self,
+        track: MediaTrack,
+        category: TrackSendCategory,
+        send_index: u32,
+        env_chunk_name: EnvChunkName,
+    ) -> Option<TrackEnvelope>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.get_set_track_send_info(
+            track,
+            category,
+            send_index,
+            TrackSendAttributeKey::Env(env_chunk_name),
+            null_mut(),
+        ) as *mut raw::TrackEnvelope;
+        TrackEnvelope::new(ptr)
+    }
+
     /// Returns the RPPXML state of the given track.
     ///
     /// With `buffer_size` you can tell REAPER how many bytes of the chunk you want.
@@ -7717,51 +8784,207 @@ where
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
-    pub unsafe fn csurf_on_send_pan_change(
+    /// REAPER can crash if you pass an invalid track.
+    pub unsafe fn csurf_on_send_pan_change(
+        &self,
+        track: MediaTrack,
+        send_index: u32,
+        value_change: ValueChange<ReaperPanValue>,
+    ) -> ReaperPanValue
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let raw = self.low.CSurf_OnSendPanChange(
+            track.as_ptr(),
+            send_index as i32,
+            value_change.value(),
+            value_change.is_relative(),
+        );
+        ReaperPanValue::new_panic(raw)
+    }
+
+    /// Grants temporary access to the name of the action registered under the given command ID
+    /// within the specified section.
+    ///
+    /// Returns `None` if the action doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid section.
+    pub unsafe fn kbd_get_text_from_cmd<R>(
+        &self,
+        command_id: CommandId,
+        section: SectionContext,
+        use_action_name: impl FnOnce(&ReaperStr) -> R,
+    ) -> Option<R>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self
+            .low
+            .kbd_getTextFromCmd(command_id.get() as _, section.to_raw());
+        create_passing_c_str(ptr)
+            // Removed action returns empty string for some reason. We want None in this case!
+            .filter(|s| !s.as_c_str().to_bytes().is_empty())
+            .map(use_action_name)
+    }
+
+    /// Grants temporary access to the action at the given index within the given section.
+    ///
+    /// In contrast to [`KbdSectionInfo::get_action_by_index()`], this also covers custom actions,
+    /// scripts and actions registered by extensions. The closure receives the command ID and the
+    /// description of the action.
+    ///
+    /// Returns `None` if there's no action at the given index.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid section.
+    ///
+    /// [`KbdSectionInfo::get_action_by_index()`]: struct.KbdSectionInfo.html#method.get_action_by_index
+    pub unsafe fn kbd_enumerate_actions<R>(
+        &self,
+        section: SectionContext,
+        index: u32,
+        use_action: impl FnOnce(CommandId, &ReaperStr) -> R,
+    ) -> Option<R>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut name = null();
+        let id = self
+            .low
+            .kbd_enumerateActions(section.to_raw(), index as _, &mut name);
+        if id <= 0 {
+            return None;
+        }
+        let name = create_passing_c_str(name).unwrap_or_default();
+        Some(use_action(CommandId::new(id as _), name))
+    }
+
+    /// Returns the number of shortcuts assigned to the given action.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid section.
+    pub unsafe fn count_action_shortcuts(
+        &self,
+        section: SectionContext,
+        command_id: CommandId,
+    ) -> u32
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let count = self
+            .low
+            .CountActionShortcuts(section.to_raw(), command_id.to_raw());
+        count.max(0) as u32
+    }
+
+    /// Returns the text description of a specific shortcut assigned to the given action, e.g.
+    /// `Ctrl+Shift+A`.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the description you want.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's no such shortcut.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid section.
+    pub unsafe fn get_action_shortcut_desc(
+        &self,
+        section: SectionContext,
+        command_id: CommandId,
+        shortcut_index: u32,
+        buffer_size: u32,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (desc, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.GetActionShortcutDesc(
+                section.to_raw(),
+                command_id.to_raw(),
+                shortcut_index as _,
+                buffer,
+                max_size,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new("shortcut doesn't exist"));
+        }
+        Ok(desc)
+    }
+
+    /// Deletes a specific shortcut assigned to the given action.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's no such shortcut.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid section.
+    pub unsafe fn delete_action_shortcut(
         &self,
-        track: MediaTrack,
-        send_index: u32,
-        value_change: ValueChange<ReaperPanValue>,
-    ) -> ReaperPanValue
+        section: SectionContext,
+        command_id: CommandId,
+        shortcut_index: u32,
+    ) -> ReaperFunctionResult<()>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let raw = self.low.CSurf_OnSendPanChange(
-            track.as_ptr(),
-            send_index as i32,
-            value_change.value(),
-            value_change.is_relative(),
+        let successful = self.low.DeleteActionShortcut(
+            section.to_raw(),
+            command_id.to_raw(),
+            shortcut_index as _,
         );
-        ReaperPanValue::new_panic(raw)
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't delete shortcut"));
+        }
+        Ok(())
     }
 
-    /// Grants temporary access to the name of the action registered under the given command ID
-    /// within the specified section.
+    /// Opens the dialog for assigning a shortcut to the given action.
     ///
-    /// Returns `None` if the action doesn't exist.
+    /// If `shortcut_index` refers to an existing shortcut, that one will be replaced. Otherwise a
+    /// new shortcut will be added.
+    ///
+    /// Returns whether a shortcut has been assigned.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid section.
-    pub unsafe fn kbd_get_text_from_cmd<R>(
+    /// REAPER can crash if you pass an invalid window or section.
+    pub unsafe fn do_action_shortcut_dialog(
         &self,
-        command_id: CommandId,
+        parent_window: Hwnd,
         section: SectionContext,
-        use_action_name: impl FnOnce(&ReaperStr) -> R,
-    ) -> Option<R>
+        command_id: CommandId,
+        shortcut_index: Option<u32>,
+    ) -> bool
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self
-            .low
-            .kbd_getTextFromCmd(command_id.get() as _, section.to_raw());
-        create_passing_c_str(ptr)
-            // Removed action returns empty string for some reason. We want None in this case!
-            .filter(|s| !s.as_c_str().to_bytes().is_empty())
-            .map(use_action_name)
+        self.low.DoActionShortcutDialog(
+            parent_window.as_ptr(),
+            section.to_raw(),
+            command_id.to_raw(),
+            shortcut_index.map(|i| i as i32).unwrap_or(-1),
+        )
     }
 
     /// Grants temporary access to the name of the given input channel.
@@ -7786,6 +9009,30 @@ where
         use_name(passing_c_str)
     }
 
+    /// Grants temporary access to the name of the given output channel.
+    ///
+    /// Like [`get_input_channel_name()`], the name is exposed as `CStr` because it's not
+    /// necessarily properly UTF-8 encoded.
+    ///
+    /// [`get_input_channel_name()`]: #method.get_input_channel_name
+    pub fn get_output_channel_name<R>(
+        &self,
+        channel_index: u32,
+        use_name: impl FnOnce(Option<&CStr>) -> R,
+    ) -> R
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.low.GetOutputChannelName(channel_index as _);
+        let passing_c_str = if ptr.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(ptr) })
+        };
+        use_name(passing_c_str)
+    }
+
     /// Grants temporary access to the REAPER resource path.
     ///
     /// This is the path to the directory where INI files are stored and other things in
@@ -7801,6 +9048,88 @@ where
         use_resource_path(path)
     }
 
+    /// Grants temporary access to the extended state value stored for the given section and key.
+    ///
+    /// Returns `None` if no value has been stored.
+    pub fn get_ext_state<'a, R>(
+        &self,
+        section: impl Into<ReaperStringArg<'a>>,
+        key: impl Into<ReaperStringArg<'a>>,
+        use_value: impl FnOnce(&ReaperStr) -> R,
+    ) -> Option<R>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let section = section.into();
+        let key = key.into();
+        // GetExtState() returns an empty string if there's no value
+        if !unsafe { self.low.HasExtState(section.as_ptr(), key.as_ptr()) } {
+            return None;
+        }
+        let ptr = unsafe { self.low.GetExtState(section.as_ptr(), key.as_ptr()) };
+        let value = unsafe { create_passing_c_str(ptr) }?;
+        Some(use_value(value))
+    }
+
+    /// Returns whether an extended state value is stored for the given section and key.
+    pub fn has_ext_state<'a>(
+        &self,
+        section: impl Into<ReaperStringArg<'a>>,
+        key: impl Into<ReaperStringArg<'a>>,
+    ) -> bool
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        unsafe {
+            self.low
+                .HasExtState(section.into().as_ptr(), key.into().as_ptr())
+        }
+    }
+
+    /// Stores an extended state value for the given section and key.
+    ///
+    /// If `persist` is `true`, the value will be written to `reaper-extstate.ini` and therefore
+    /// survive a REAPER restart.
+    pub fn set_ext_state<'a>(
+        &self,
+        section: impl Into<ReaperStringArg<'a>>,
+        key: impl Into<ReaperStringArg<'a>>,
+        value: impl Into<ReaperStringArg<'a>>,
+        persist: bool,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        unsafe {
+            self.low.SetExtState(
+                section.into().as_ptr(),
+                key.into().as_ptr(),
+                value.into().as_ptr(),
+                persist,
+            );
+        }
+    }
+
+    /// Removes the extended state value stored for the given section and key.
+    ///
+    /// If `persist` is `true`, the value will be removed from `reaper-extstate.ini` as well.
+    pub fn delete_ext_state<'a>(
+        &self,
+        section: impl Into<ReaperStringArg<'a>>,
+        key: impl Into<ReaperStringArg<'a>>,
+        persist: bool,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        unsafe {
+            self.low
+                .DeleteExtState(section.into().as_ptr(), key.into().as_ptr(), persist);
+        }
+    }
+
     /// Grants temporary access to the name of the given take.
     ///
     /// # Error
@@ -8672,6 +10001,205 @@ where
         self.low.IsWindowTextField(window.as_ptr())
     }
 
+    /// Makes the given window dockable and adds it to the docker if it was docked last time.
+    ///
+    /// The identifier is used for remembering the dock position (e.g. in `reaper.ini`). The window
+    /// stays registered until the returned registration is dropped.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid window handle.
+    pub unsafe fn dock_window_add_ex<'a>(
+        &self,
+        window: Hwnd,
+        name: impl Into<ReaperStringArg<'a>>,
+        ident: impl Into<ReaperStringArg<'a>>,
+        allow_show: bool,
+    ) -> DockRegistration
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.DockWindowAddEx(
+            window.as_ptr(),
+            name.into().as_ptr(),
+            ident.into().as_ptr(),
+            allow_show,
+        );
+        DockRegistration::new(self.low, window)
+    }
+
+    /// Shows the given docked window and makes it the active tab of its docker.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid window handle.
+    pub unsafe fn dock_window_activate(&self, window: Hwnd)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.DockWindowActivate(window.as_ptr());
+    }
+
+    /// Removes the given window from the docker.
+    ///
+    /// Usually it's enough to drop the [`DockRegistration`].
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid window handle.
+    pub unsafe fn dock_window_remove(&self, window: Hwnd)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.DockWindowRemove(window.as_ptr());
+    }
+
+    /// Returns where the given window is docked or `None` if it's not docked.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid window handle.
+    pub unsafe fn dock_is_child_of_dock(&self, window: Hwnd) -> Option<DockLocation>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut is_floating = false;
+        let index = self
+            .low
+            .DockIsChildOfDock(window.as_ptr(), &mut is_floating as *mut _);
+        if index < 0 {
+            return None;
+        }
+        let location = DockLocation {
+            dock_index: index as u32,
+            is_floating,
+        };
+        Some(location)
+    }
+
+    /// Moves the window with the given identifier (as passed to [`dock_window_add_ex()`]) to the
+    /// docker with the given index.
+    ///
+    /// [`dock_window_add_ex()`]: #method.dock_window_add_ex
+    pub fn dock_update_dock_id<'a>(&self, ident: impl Into<ReaperStringArg<'a>>, dock_index: u32)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        unsafe {
+            self.low
+                .Dock_UpdateDockID(ident.into().as_ptr(), dock_index as i32);
+        }
+    }
+
+    /// Registers a window in screensets, so its state and dock position is saved and restored
+    /// together with screensets.
+    ///
+    /// REAPER calls the given callback type with the given ID. The window stays registered until
+    /// the returned registration is dropped.
+    pub fn screenset_register_new<'a, T: ScreensetCallback>(
+        &self,
+        id: impl Into<ReaperStringArg<'a>>,
+    ) -> ScreensetRegistration
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let id = id.into().into_inner().to_reaper_string();
+        unsafe {
+            self.low.screenset_registerNew(
+                id.as_ptr() as *mut _,
+                Some(delegating_screenset_callback::<T>),
+                null_mut(),
+            );
+        }
+        ScreensetRegistration::new(self.low, id)
+    }
+
+    /// Creates a local OSC handler which acts like an OSC control surface without network
+    /// connection.
+    ///
+    /// REAPER sends OSC feedback to the given callback. The handler stays active until the
+    /// returned value is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if REAPER couldn't create the handler.
+    pub fn create_local_osc_handler<T: LocalOscCallback>(
+        &self,
+        callback: T,
+    ) -> ReaperFunctionResult<LocalOscHandler<T>>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        LocalOscHandler::create(self.low, callback)
+    }
+
+    /// Sends an OSC message to REAPER as if it came from the default OSC control surface
+    /// configuration.
+    ///
+    /// The message is an OSC address such as `/action/40044`. Pass a value if the message
+    /// expects one.
+    pub fn osc_local_message_to_host<'a>(
+        &self,
+        message: impl Into<ReaperStringArg<'a>>,
+        value: Option<f64>,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let value_ptr = match value.as_ref() {
+            None => null(),
+            Some(v) => v as *const f64,
+        };
+        unsafe {
+            self.low
+                .OscLocalMessageToHost(message.into().as_ptr(), value_ptr);
+        }
+    }
+
+    /// Returns the installed joystick device at the given index.
+    ///
+    /// Returns `None` if the index is out of range.
+    pub fn joystick_enum(&self, index: u32) -> Option<JoystickEnumResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut name_ptr = null();
+        let guid_ptr = unsafe { self.low.joystick_enum(index as i32, &mut name_ptr) };
+        let guid = unsafe { create_passing_c_str(guid_ptr) }?;
+        let name = unsafe { create_passing_c_str(name_ptr) };
+        let result = JoystickEnumResult {
+            guid: guid.to_reaper_string(),
+            name: name.map(|n| n.to_reaper_string()),
+        };
+        Some(result)
+    }
+
+    /// Opens the joystick device with the given GUID (see [`joystick_enum()`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device couldn't be opened.
+    ///
+    /// [`joystick_enum()`]: #method.joystick_enum
+    pub fn joystick_create(&self, guid: &GUID) -> ReaperFunctionResult<OwnedJoystickDevice>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = unsafe { self.low.joystick_create(guid as *const _) };
+        let device = NonNull::new(ptr)
+            .ok_or_else(|| ReaperFunctionError::new("couldn't open joystick device"))?;
+        Ok(OwnedJoystickDevice::new(self.low, device))
+    }
+
     // TODO-high document
     pub fn get_custom_menu_or_toolbar_item<'a, R>(
         &self,
@@ -9095,6 +10623,21 @@ pub struct TimeMapQnToMeasuresResult {
     pub end: PositionInQuarterNotes,
 }
 
+/// A tempo/time signature marker as returned by [`Reaper::get_tempo_time_sig_marker()`].
+///
+/// [`Reaper::get_tempo_time_sig_marker()`]: struct.Reaper.html#method.get_tempo_time_sig_marker
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GetTempoTimeSigMarkerResult {
+    pub position: PositionInSeconds,
+    pub measure_index: i32,
+    pub beats_since_measure: PositionInBeats,
+    pub tempo: Bpm,
+    /// `None` if this marker doesn't change the time signature.
+    pub time_signature: Option<TimeSignature>,
+    /// Whether the tempo changes gradually until the next marker.
+    pub is_linear: bool,
+}
+
 /// Time signature.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct TimeSignature {
@@ -9104,6 +10647,19 @@ pub struct TimeSignature {
     pub denominator: NonZeroU32,
 }
 
+/// An envelope point as returned by [`Reaper::get_envelope_point_ex()`].
+///
+/// [`Reaper::get_envelope_point_ex()`]: struct.Reaper.html#method.get_envelope_point_ex
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GetEnvelopePointExResult {
+    pub position: PositionInSeconds,
+    /// Raw value, as stored in the envelope (see `ScaleFromEnvelopeMode`).
+    pub value: f64,
+    pub shape: u32,
+    pub tension: f64,
+    pub is_selected: bool,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct CountProjectMarkersResult {
     pub total_count: u32,
@@ -9176,6 +10732,42 @@ pub enum GetLastTouchedFxResult {
     },
 }
 
+/// A theme layout parameter as returned by [`Reaper::theme_layout_get_parameter()`].
+///
+/// [`Reaper::theme_layout_get_parameter()`]: struct.Reaper.html#method.theme_layout_get_parameter
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ThemeLayoutParameter {
+    /// Config name of the parameter.
+    pub name: ReaperString,
+    /// Human-readable description.
+    pub description: ReaperString,
+    pub value: i32,
+    pub default_value: i32,
+    pub min_value: i32,
+    pub max_value: i32,
+}
+
+/// An installed joystick device as returned by [`Reaper::joystick_enum()`].
+///
+/// [`Reaper::joystick_enum()`]: struct.Reaper.html#method.joystick_enum
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct JoystickEnumResult {
+    /// GUID of the device as string with braces, to be converted via
+    /// [`Reaper::string_to_guid()`](struct.Reaper.html#method.string_to_guid).
+    pub guid: ReaperString,
+    /// Product name of the device.
+    pub name: Option<ReaperString>,
+}
+
+/// Where a window is docked.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct DockLocation {
+    /// Index of the docker.
+    pub dock_index: u32,
+    /// Whether the docker is floating (not attached to the main window).
+    pub is_floating: bool,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct GetFocusedFx2Result {
     /// Whether the FX is still focused (vs. unfocused but still open).
@@ -9239,6 +10831,34 @@ pub enum FxLocation {
     },
 }
 
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GetThingFromPointResult {
+    /// The hit track, if any.
+    ///
+    /// Can be `None` even if something has been hit, see `info`.
+    pub track: Option<MediaTrack>,
+    /// Describes what has been hit.
+    pub info: ReaperString,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GetItemFromPointResult {
+    pub item: MediaItem,
+    /// The hit take.
+    pub take: Option<MediaItemTake>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GetTrackFromPointResult {
+    pub track: MediaTrack,
+    /// The part of the track which is likely at the given point.
+    pub part: TrackPart,
+    /// The fixed lane at the given point (approximate for tracks with free item positioning).
+    ///
+    /// Only meaningful for tracks with free item positioning or fixed lanes.
+    pub lane: u32,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct NativeColorValue {
     /// The OS-dependent color.
@@ -9317,6 +10937,13 @@ fn convert_path_to_reaper_string(path: &Utf8Path) -> ReaperString {
     ReaperString::from_str(path.as_str())
 }
 
+fn layout_attribute_key(area: TrackArea) -> TrackAttributeKey<'static> {
+    match area {
+        TrackArea::Tcp => TrackAttributeKey::TcpLayout,
+        TrackArea::Mcp => TrackAttributeKey::McpLayout,
+    }
+}
+
 mod private {
     use crate::{MainThreadScope, RealTimeAudioThreadScope};
 
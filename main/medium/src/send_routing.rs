@@ -0,0 +1,346 @@
+use crate::{Hidden, TryFromGreaterError};
+
+use helgoboss_midi::Channel;
+use std::convert::TryInto;
+
+/// Send mode of a track send, hardware output send or track receive (`I_SENDMODE`).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SendMode {
+    /// Post-fader (post-pan).
+    PostFader,
+    /// Pre-FX.
+    PreFx,
+    /// Post-FX (deprecated variant, still reported by old projects).
+    PostFxDeprecated,
+    /// Post-FX (pre-fader).
+    PostFx,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<i32>),
+}
+
+impl SendMode {
+    /// Converts an integer as returned by the low-level API to a send mode.
+    pub fn from_raw(v: i32) -> SendMode {
+        use SendMode::*;
+        match v {
+            0 => PostFader,
+            1 => PreFx,
+            2 => PostFxDeprecated,
+            3 => PostFx,
+            x => Unknown(Hidden(x)),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        use SendMode::*;
+        match self {
+            PostFader => 0,
+            PreFx => 1,
+            PostFxDeprecated => 2,
+            PostFx => 3,
+            Unknown(Hidden(x)) => x,
+        }
+    }
+}
+
+/// Audio source channels of a send (`I_SRCCHAN`).
+///
+/// REAPER uses `-1` if the send doesn't send audio at all. This is represented as `None` in
+/// [`from_raw()`](#method.from_raw).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SendSourceChannels {
+    offset: u32,
+    count: u32,
+}
+
+impl SendSourceChannels {
+    /// Creates a channel range starting at the given zero-based channel index.
+    ///
+    /// The channel count must be 1 (mono) or an even number up to 64 (2 → stereo, 4, 6, ...).
+    /// Other counts can't be expressed by REAPER and result in an error.
+    pub fn new(offset: u32, count: u32) -> Result<Self, TryFromGreaterError<u32>> {
+        let is_valid = count == 1 || (count % 2 == 0 && (2..=MAX_CHANNEL_COUNT).contains(&count));
+        if !is_valid {
+            return Err(TryFromGreaterError::new(
+                "channel count must be 1 or an even number up to 64",
+                count,
+            ));
+        }
+        Ok(Self { offset, count })
+    }
+
+    /// Creates a mono channel range.
+    pub fn mono(offset: u32) -> Self {
+        Self { offset, count: 1 }
+    }
+
+    /// Creates a stereo channel range.
+    pub fn stereo(offset: u32) -> Self {
+        Self { offset, count: 2 }
+    }
+
+    /// Returns the zero-based index of the first source channel.
+    pub fn offset(self) -> u32 {
+        self.offset
+    }
+
+    /// Returns the number of channels.
+    pub fn count(self) -> u32 {
+        self.count
+    }
+
+    /// Converts an integer as returned by the low-level API to source channels.
+    ///
+    /// Returns `None` if audio sending is disabled.
+    pub fn from_raw(v: i32) -> Option<SendSourceChannels> {
+        if v < 0 {
+            return None;
+        }
+        let v = v as u32;
+        let count = match v >> CHANNEL_COUNT_SHIFT {
+            0 => 2,
+            1 => 1,
+            n => n * 2,
+        };
+        let channels = SendSourceChannels {
+            offset: v & CHANNEL_OFFSET_MASK,
+            count,
+        };
+        Some(channels)
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        // The constructors ensure that the count is valid
+        let count_code = match self.count {
+            1 => 1,
+            2 => 0,
+            n => n / 2,
+        };
+        ((count_code << CHANNEL_COUNT_SHIFT) | (self.offset & CHANNEL_OFFSET_MASK)) as i32
+    }
+
+    /// Converts optional source channels to an integer as expected by the low-level API.
+    ///
+    /// `None` disables audio sending.
+    pub fn option_to_raw(channels: Option<SendSourceChannels>) -> i32 {
+        match channels {
+            None => -1,
+            Some(c) => c.to_raw(),
+        }
+    }
+}
+
+/// Audio destination channels of a send (`I_DSTCHAN`).
+///
+/// The number of destination channels is implied by the number of source channels.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct SendDestinationChannels {
+    /// Zero-based index of the first destination channel.
+    ///
+    /// For hardware output sends, this is the index of the first hardware output channel.
+    pub offset: u32,
+    /// Whether the source channels are mixed down to mono.
+    pub mix_to_mono: bool,
+    /// Whether the destination is a ReaRoute channel (hardware output sends only).
+    pub rea_route: bool,
+}
+
+impl SendDestinationChannels {
+    /// Converts an integer as returned by the low-level API to destination channels.
+    pub fn from_raw(v: i32) -> SendDestinationChannels {
+        let v = v.max(0) as u32;
+        SendDestinationChannels {
+            offset: v & DST_OFFSET_MASK,
+            mix_to_mono: v & DST_MONO_FLAG != 0,
+            rea_route: v & DST_REA_ROUTE_FLAG != 0,
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        let mut v = self.offset & DST_OFFSET_MASK;
+        if self.mix_to_mono {
+            v |= DST_MONO_FLAG;
+        }
+        if self.rea_route {
+            v |= DST_REA_ROUTE_FLAG;
+        }
+        v as i32
+    }
+}
+
+/// MIDI routing of a send (`I_MIDIFLAGS`).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SendMidiFlags {
+    /// Whether the send sends MIDI at all.
+    pub enabled: bool,
+    /// Source channel (`None` means all channels).
+    ///
+    /// Ignored if MIDI sending is disabled.
+    pub src_channel: Option<Channel>,
+    /// Destination channel (`None` means original channel).
+    pub dst_channel: Option<Channel>,
+    /// Zero-based source bus index (`None` means all buses).
+    pub src_bus: Option<u8>,
+    /// Zero-based destination bus index (`None` means all buses).
+    pub dst_bus: Option<u8>,
+    /// Whether the send faders send MIDI volume/pan messages.
+    pub faders_send_midi_vol_pan: bool,
+}
+
+impl Default for SendMidiFlags {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            src_channel: None,
+            dst_channel: None,
+            src_bus: None,
+            dst_bus: None,
+            faders_send_midi_vol_pan: false,
+        }
+    }
+}
+
+impl SendMidiFlags {
+    /// Converts an integer as returned by the low-level API to MIDI flags.
+    pub fn from_raw(v: i32) -> SendMidiFlags {
+        let v = v as u32;
+        let raw_src_channel = v & MIDI_CHANNEL_MASK;
+        let raw_dst_channel = (v >> MIDI_DST_CHANNEL_SHIFT) & MIDI_CHANNEL_MASK;
+        SendMidiFlags {
+            enabled: raw_src_channel != MIDI_DISABLED,
+            src_channel: decode_midi_channel(raw_src_channel),
+            dst_channel: decode_midi_channel(raw_dst_channel),
+            src_bus: decode_midi_bus((v >> MIDI_SRC_BUS_SHIFT) & MIDI_BUS_MASK),
+            dst_bus: decode_midi_bus((v >> MIDI_DST_BUS_SHIFT) & MIDI_BUS_MASK),
+            faders_send_midi_vol_pan: v & MIDI_FADERS_FLAG != 0,
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        let raw_src_channel = if self.enabled {
+            encode_midi_channel(self.src_channel)
+        } else {
+            MIDI_DISABLED
+        };
+        let mut v = raw_src_channel
+            | (encode_midi_channel(self.dst_channel) << MIDI_DST_CHANNEL_SHIFT)
+            | (encode_midi_bus(self.src_bus) << MIDI_SRC_BUS_SHIFT)
+            | (encode_midi_bus(self.dst_bus) << MIDI_DST_BUS_SHIFT);
+        if self.faders_send_midi_vol_pan {
+            v |= MIDI_FADERS_FLAG;
+        }
+        v as i32
+    }
+}
+
+fn decode_midi_channel(raw: u32) -> Option<Channel> {
+    if raw == 0 || raw > 16 {
+        return None;
+    }
+    (raw - 1).try_into().ok()
+}
+
+fn encode_midi_channel(channel: Option<Channel>) -> u32 {
+    match channel {
+        None => 0,
+        Some(ch) => u32::from(ch) + 1,
+    }
+}
+
+fn decode_midi_bus(raw: u32) -> Option<u8> {
+    if raw == 0 {
+        return None;
+    }
+    Some((raw - 1) as u8)
+}
+
+fn encode_midi_bus(bus: Option<u8>) -> u32 {
+    match bus {
+        None => 0,
+        Some(b) => (b as u32 + 1).min(MIDI_BUS_MASK),
+    }
+}
+
+const CHANNEL_OFFSET_MASK: u32 = 0x3FF;
+const CHANNEL_COUNT_SHIFT: u32 = 10;
+const MAX_CHANNEL_COUNT: u32 = 64;
+const DST_OFFSET_MASK: u32 = 0x1FF;
+const DST_REA_ROUTE_FLAG: u32 = 512;
+const DST_MONO_FLAG: u32 = 1024;
+const MIDI_CHANNEL_MASK: u32 = 0x1F;
+const MIDI_DISABLED: u32 = 31;
+const MIDI_DST_CHANNEL_SHIFT: u32 = 5;
+const MIDI_FADERS_FLAG: u32 = 1024;
+const MIDI_BUS_MASK: u32 = 0xFF;
+const MIDI_SRC_BUS_SHIFT: u32 = 14;
+const MIDI_DST_BUS_SHIFT: u32 = 22;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn source_channels() {
+        assert_eq!(SendSourceChannels::from_raw(-1), None);
+        assert_eq!(
+            SendSourceChannels::from_raw(2),
+            Some(SendSourceChannels::stereo(2))
+        );
+        assert_eq!(
+            SendSourceChannels::from_raw(1024 + 3),
+            Some(SendSourceChannels::mono(3))
+        );
+        let quad = SendSourceChannels::new(4, 4).unwrap();
+        assert_eq!(SendSourceChannels::from_raw(2048 + 4), Some(quad));
+        assert_eq!(quad.to_raw(), 2048 + 4);
+        assert_eq!(SendSourceChannels::mono(3).to_raw(), 1024 + 3);
+        assert_eq!(SendSourceChannels::stereo(2).to_raw(), 2);
+        assert_eq!(SendSourceChannels::option_to_raw(None), -1);
+    }
+
+    #[test]
+    fn invalid_source_channel_counts() {
+        for count in [0, 3, 5, 63, 66] {
+            assert!(SendSourceChannels::new(0, count).is_err(), "{count}");
+        }
+        let channels = SendSourceChannels::new(2, 64).unwrap();
+        assert_eq!(channels.count(), 64);
+        assert_eq!(
+            SendSourceChannels::from_raw(channels.to_raw()),
+            Some(channels)
+        );
+    }
+
+    #[test]
+    fn destination_channels() {
+        let channels = SendDestinationChannels::from_raw(1024 + 512 + 6);
+        assert_eq!(channels.offset, 6);
+        assert!(channels.mix_to_mono);
+        assert!(channels.rea_route);
+        assert_eq!(channels.to_raw(), 1024 + 512 + 6);
+    }
+
+    #[test]
+    fn midi_flags() {
+        let flags = SendMidiFlags::from_raw(0);
+        assert_eq!(flags, SendMidiFlags::default());
+        let flags = SendMidiFlags::from_raw(31);
+        assert!(!flags.enabled);
+        let flags = SendMidiFlags {
+            enabled: true,
+            src_channel: Some(Channel::new(2)),
+            dst_channel: Some(Channel::new(15)),
+            src_bus: Some(0),
+            dst_bus: Some(3),
+            faders_send_midi_vol_pan: true,
+        };
+        let raw = flags.to_raw();
+        assert_eq!(raw, 3 | (16 << 5) | 1024 | (1 << 14) | (4 << 22));
+        assert_eq!(SendMidiFlags::from_raw(raw), flags);
+    }
+}
@@ -90,6 +90,13 @@ impl<'a> From<&'a ReaperStr> for ReaperStringArg<'a> {
     }
 }
 
+// Used for typed keys which are converted into REAPER strings (see e.g. `ThemeColorKey`).
+impl<'a> From<Cow<'a, ReaperStr>> for ReaperStringArg<'a> {
+    fn from(s: Cow<'a, ReaperStr>) -> Self {
+        ReaperStringArg(s)
+    }
+}
+
 // Sometimes a function needs an owned string because it wants to store it somewhere. The resulting
 // inner `Cow` is owned, no string copy occurs. The function can then use `into_owned()` to get
 // hold of the `ReaperString`.
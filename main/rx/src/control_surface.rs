@@ -1,9 +1,18 @@
 use crate::{EventStreamSubject, ReactiveEvent};
-use reaper_high::{AvailablePanValue, ChangeEvent, Fx, FxParameter, Project, Track, TrackRoute};
-use reaper_medium::Pan;
+use reaper_high::{
+    AvailablePanValue, ChangeEvent, Envelope, Fx, FxParameter, Item, MarkerInfo, Project, Take,
+    Track, TrackRoute,
+};
+use reaper_medium::{Pan, PositionInSeconds};
 use rxrust::prelude::*;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt;
+#[cfg(feature = "serde")]
+use {
+    reaper_high::{ChangeEventRecord, ChangeEventReplayer},
+    std::io::{self, BufRead},
+};
 
 #[derive(Debug)]
 pub struct ControlSurfaceRxMiddleware {
@@ -19,26 +28,53 @@ impl ControlSurfaceRxMiddleware {
         self.rx.main_thread_idle.borrow_mut().next(());
     }
 
+    /// Emits the given record via [`ControlSurfaceRx::change_event_records()`].
+    ///
+    /// Doesn't need a running REAPER.
+    #[cfg(feature = "serde")]
+    pub fn handle_record(&self, record: ChangeEventRecord) {
+        self.rx.change_event_records.borrow_mut().next(record);
+    }
+
+    /// Emits all records of a recorded session via [`ControlSurfaceRx::change_event_records()`].
+    ///
+    /// Doesn't need a running REAPER. Stops at the first record which can't be read.
+    #[cfg(feature = "serde")]
+    pub fn replay(&self, replayer: ChangeEventReplayer<impl BufRead>) -> io::Result<()> {
+        replayer.replay(|record| self.handle_record(record))
+    }
+
     pub fn handle_change(&self, event: ChangeEvent) {
+        // Converting events into records queries REAPER, so only do it if someone listens
+        #[cfg(feature = "serde")]
+        if self.rx.change_event_records.borrow().subscribed_size() > 0 {
+            self.handle_record((&event).into());
+        }
         use ChangeEvent::*;
         match event {
             ProjectSwitched(e) => self.rx.project_switched.borrow_mut().next(e.new_project),
             TrackVolumeChanged(e) => {
+                let Some(track) = e.track.to_track() else {
+                    return;
+                };
                 self.rx
                     .track_volume_changed
                     .borrow_mut()
-                    .next(e.track.clone());
+                    .next(track.clone());
                 if e.touched {
-                    self.rx.track_volume_touched.borrow_mut().next(e.track);
+                    self.rx.track_volume_touched.borrow_mut().next(track);
                 }
             }
             TrackPanChanged(e) => {
-                self.rx.track_pan_changed.borrow_mut().next(e.track.clone());
+                let Some(track) = e.track.to_track() else {
+                    return;
+                };
+                self.rx.track_pan_changed.borrow_mut().next(track.clone());
                 if e.touched {
                     // When it's touched, it should always be complete.
                     if let AvailablePanValue::Complete(new_value) = e.new_value {
                         self.rx.track_pan_touched.borrow_mut().next((
-                            e.track,
+                            track,
                             e.old_value,
                             new_value,
                         ));
@@ -46,74 +82,148 @@ impl ControlSurfaceRxMiddleware {
                 }
             }
             TrackRouteVolumeChanged(e) => {
+                let Some(route) = e.route.to_route() else {
+                    return;
+                };
                 self.rx
                     .track_route_volume_changed
                     .borrow_mut()
-                    .next(e.route.clone());
+                    .next(route.clone());
                 if e.touched {
-                    self.rx
-                        .track_route_volume_touched
-                        .borrow_mut()
-                        .next(e.route);
+                    self.rx.track_route_volume_touched.borrow_mut().next(route);
                 }
             }
             TrackRoutePanChanged(e) => {
+                let Some(route) = e.route.to_route() else {
+                    return;
+                };
                 self.rx
                     .track_route_pan_changed
                     .borrow_mut()
-                    .next(e.route.clone());
+                    .next(route.clone());
                 if e.touched {
-                    self.rx.track_route_pan_touched.borrow_mut().next(e.route);
+                    self.rx.track_route_pan_touched.borrow_mut().next(route);
                 }
             }
-            TrackAdded(e) => self.rx.track_added.borrow_mut().next(e.track),
-            TrackRemoved(e) => self.rx.track_removed.borrow_mut().next(e.track),
-            TracksReordered(e) => self.rx.tracks_reordered.borrow_mut().next(e.project),
-            TrackNameChanged(e) => self.rx.track_name_changed.borrow_mut().next(e.track),
-            TrackInputChanged(e) => self.rx.track_input_changed.borrow_mut().next(e.track),
-            TrackInputMonitoringChanged(e) => self
-                .rx
-                .track_input_monitoring_changed
-                .borrow_mut()
-                .next(e.track),
-            TrackAutomationModeChanged(e) => self
-                .rx
-                .track_automation_mode_changed
-                .borrow_mut()
-                .next(e.track),
-            TrackArmChanged(e) => self.rx.track_arm_changed.borrow_mut().next(e.track),
-            TrackMuteChanged(e) => {
+            TrackAdded(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx.track_added.borrow_mut().next(track);
+                }
+            }
+            TrackRemoved(e) => {
                 self.rx
-                    .track_mute_changed
+                    .track_envelope_changed
                     .borrow_mut()
-                    .next(e.track.clone());
+                    .retain(|track, _| *track.guid() != e.guid);
+                if let Ok(track) = e.track.project().track_by_guid(&e.guid) {
+                    self.rx.track_removed.borrow_mut().next(track);
+                }
+            }
+            TracksReordered(e) => self.rx.tracks_reordered.borrow_mut().next(e.project),
+            TrackNameChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx.track_name_changed.borrow_mut().next(track);
+                }
+            }
+            TrackInputChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx.track_input_changed.borrow_mut().next(track);
+                }
+            }
+            TrackInputMonitoringChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx
+                        .track_input_monitoring_changed
+                        .borrow_mut()
+                        .next(track);
+                }
+            }
+            TrackAutomationModeChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx
+                        .track_automation_mode_changed
+                        .borrow_mut()
+                        .next(track);
+                }
+            }
+            TrackArmChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx.track_arm_changed.borrow_mut().next(track);
+                }
+            }
+            TrackMuteChanged(e) => {
+                let Some(track) = e.track.to_track() else {
+                    return;
+                };
+                self.rx.track_mute_changed.borrow_mut().next(track.clone());
                 if e.touched {
-                    self.rx.track_mute_touched.borrow_mut().next(e.track);
+                    self.rx.track_mute_touched.borrow_mut().next(track);
+                }
+            }
+            TrackSoloChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx.track_solo_changed.borrow_mut().next(track);
+                }
+            }
+            TrackSelectedChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx
+                        .track_selected_changed
+                        .borrow_mut()
+                        .next((track, e.new_value));
+                }
+            }
+            FxAdded(e) => {
+                if let Some(fx) = e.fx.to_fx() {
+                    self.rx.fx_added.borrow_mut().next(fx);
+                }
+            }
+            FxRemoved(e) => {
+                if let Some(fx) = e.fx.to_fx() {
+                    self.rx.fx_removed.borrow_mut().next(fx);
+                }
+            }
+            FxEnabledChanged(e) => {
+                if let Some(fx) = e.fx.to_fx() {
+                    self.rx.fx_enabled_changed.borrow_mut().next(fx);
+                }
+            }
+            FxOpened(e) => {
+                if let Some(fx) = e.fx.to_fx() {
+                    self.rx.fx_opened.borrow_mut().next(fx);
+                }
+            }
+            FxClosed(e) => {
+                if let Some(fx) = e.fx.to_fx() {
+                    self.rx.fx_closed.borrow_mut().next(fx);
+                }
+            }
+            FxFocused(e) => {
+                let fx = e.fx.and_then(|fx| fx.to_fx());
+                self.rx.fx_focused.borrow_mut().next(fx);
+            }
+            FxReordered(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx.fx_reordered.borrow_mut().next(track);
                 }
             }
-            TrackSoloChanged(e) => self.rx.track_solo_changed.borrow_mut().next(e.track),
-            TrackSelectedChanged(e) => self
-                .rx
-                .track_selected_changed
-                .borrow_mut()
-                .next((e.track, e.new_value)),
-            FxAdded(e) => self.rx.fx_added.borrow_mut().next(e.fx),
-            FxRemoved(e) => self.rx.fx_removed.borrow_mut().next(e.fx),
-            FxEnabledChanged(e) => self.rx.fx_enabled_changed.borrow_mut().next(e.fx),
-            FxOpened(e) => self.rx.fx_opened.borrow_mut().next(e.fx),
-            FxClosed(e) => self.rx.fx_closed.borrow_mut().next(e.fx),
-            FxFocused(e) => self.rx.fx_focused.borrow_mut().next(e.fx),
-            FxReordered(e) => self.rx.fx_reordered.borrow_mut().next(e.track),
             FxParameterValueChanged(e) => {
+                let Some(parameter) = e.parameter.to_parameter() else {
+                    return;
+                };
                 self.rx
                     .fx_parameter_value_changed
                     .borrow_mut()
-                    .next(e.parameter.clone());
+                    .next(parameter.clone());
                 if e.touched {
-                    self.rx.fx_parameter_touched.borrow_mut().next(e.parameter);
+                    self.rx.fx_parameter_touched.borrow_mut().next(parameter);
+                }
+            }
+            FxPresetChanged(e) => {
+                if let Some(fx) = e.fx.to_fx() {
+                    self.rx.fx_preset_changed.borrow_mut().next(fx);
                 }
             }
-            FxPresetChanged(e) => self.rx.fx_preset_changed.borrow_mut().next(e.fx),
             MasterTempoChanged(e) => {
                 self.rx.master_tempo_changed.borrow_mut().next(());
                 if e.touched {
@@ -135,17 +245,124 @@ impl ControlSurfaceRxMiddleware {
                 .borrow_mut()
                 .next(()),
             BookmarksChanged(_) => self.rx.bookmarks_changed.borrow_mut().next(()),
-            ReceiveCountChanged(e) => self.rx.receive_count_changed.borrow_mut().next(e.track),
-            HardwareOutputSendCountChanged(e) => self
+            ReceiveCountChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx.receive_count_changed.borrow_mut().next(track);
+                }
+            }
+            HardwareOutputSendCountChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx
+                        .hardware_output_send_count_changed
+                        .borrow_mut()
+                        .next(track);
+                }
+            }
+            TrackSendCountChanged(e) => {
+                if let Some(track) = e.track.to_track() {
+                    self.rx.track_send_count_changed.borrow_mut().next(track);
+                }
+            }
+            ItemAdded(e) => self.rx.item_added.borrow_mut().next(e.item),
+            ItemRemoved(e) => self.rx.item_removed.borrow_mut().next(e.item),
+            ItemMoved(e) => self.rx.item_moved.borrow_mut().next(e.item),
+            ItemSelectionChanged(e) => self
+                .rx
+                .item_selection_changed
+                .borrow_mut()
+                .next((e.item, e.new_value)),
+            ItemMuteChanged(e) => self
+                .rx
+                .item_mute_changed
+                .borrow_mut()
+                .next((e.item, e.new_value)),
+            ActiveTakeChanged(e) => self
                 .rx
-                .hardware_output_send_count_changed
+                .item_active_take_changed
                 .borrow_mut()
-                .next(e.track),
-            TrackSendCountChanged(e) => self.rx.track_send_count_changed.borrow_mut().next(e.track),
+                .next((e.item, e.new_value)),
+            TakeSourceChanged(e) => self.rx.item_take_source_changed.borrow_mut().next(e.take),
+            MarkerAdded(e) => self.rx.marker_added.borrow_mut().next(e.marker),
+            MarkerRemoved(e) => self.rx.marker_removed.borrow_mut().next(e.marker),
+            MarkerChanged(e) => self.rx.marker_changed.borrow_mut().next((e.old, e.new)),
+            TempoMapChanged(e) => self
+                .rx
+                .tempo_map_changed
+                .borrow_mut()
+                .next(e.first_affected_position),
+            EnvelopeAdded(e) => {
+                let Some(envelope) = e.envelope.to_envelope() else {
+                    return;
+                };
+                self.notify_track_envelope_changed(&envelope);
+                self.rx.envelope_added.borrow_mut().next(envelope);
+            }
+            EnvelopeRemoved(e) => {
+                // The track might have been removed together with the envelope
+                let Ok(track) = e.project.track_by_guid(&e.track_guid) else {
+                    return;
+                };
+                let envelope = Envelope::new(track, e.envelope.raw());
+                self.notify_track_envelope_changed(&envelope);
+                self.rx.envelope_removed.borrow_mut().next(envelope);
+            }
+            EnvelopePointsChanged(e) => {
+                let Some(envelope) = e.envelope.to_envelope() else {
+                    return;
+                };
+                self.notify_track_envelope_changed(&envelope);
+                self.rx
+                    .envelope_points_changed
+                    .borrow_mut()
+                    .next((envelope, e.start, e.end));
+            }
+            EnvelopeActiveChanged(e) => {
+                let Some(envelope) = e.envelope.to_envelope() else {
+                    return;
+                };
+                self.notify_track_envelope_changed(&envelope);
+                self.rx
+                    .envelope_active_changed
+                    .borrow_mut()
+                    .next((envelope, e.new_value));
+            }
+            EnvelopeVisibilityChanged(e) => {
+                let Some(envelope) = e.envelope.to_envelope() else {
+                    return;
+                };
+                self.notify_track_envelope_changed(&envelope);
+                self.rx
+                    .envelope_visibility_changed
+                    .borrow_mut()
+                    .next((envelope, e.new_value));
+            }
+            EnvelopeArmChanged(e) => {
+                let Some(envelope) = e.envelope.to_envelope() else {
+                    return;
+                };
+                self.notify_track_envelope_changed(&envelope);
+                self.rx
+                    .envelope_arm_changed
+                    .borrow_mut()
+                    .next((envelope, e.new_value));
+            }
             // Don't implement the new stuff, ReaLearn doesn't use rx anymore for the most part.
             _ => {}
         };
     }
+
+    fn notify_track_envelope_changed(&self, envelope: &Envelope) {
+        // Clone the subject so that subscribers can create further track-scoped subjects
+        let subject = self
+            .rx
+            .track_envelope_changed
+            .borrow()
+            .get(envelope.track())
+            .cloned();
+        if let Some(mut subject) = subject {
+            subject.next(envelope.clone());
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -195,6 +412,31 @@ pub struct ControlSurfaceRx {
     pub repeat_state_changed: EventStreamSubject<()>,
     pub project_closed: EventStreamSubject<Project>,
     pub bookmarks_changed: EventStreamSubject<()>,
+    pub item_added: EventStreamSubject<Item>,
+    pub item_removed: EventStreamSubject<Item>,
+    pub item_moved: EventStreamSubject<Item>,
+    pub item_selection_changed: EventStreamSubject<(Item, bool)>,
+    pub item_mute_changed: EventStreamSubject<(Item, bool)>,
+    pub item_active_take_changed: EventStreamSubject<(Item, Option<Take>)>,
+    pub item_take_source_changed: EventStreamSubject<Take>,
+    pub marker_added: EventStreamSubject<MarkerInfo>,
+    pub marker_removed: EventStreamSubject<MarkerInfo>,
+    /// Old, New.
+    pub marker_changed: EventStreamSubject<(MarkerInfo, MarkerInfo)>,
+    pub tempo_map_changed: EventStreamSubject<PositionInSeconds>,
+    pub envelope_added: EventStreamSubject<Envelope>,
+    pub envelope_removed: EventStreamSubject<Envelope>,
+    /// Envelope, start and end of affected time range.
+    pub envelope_points_changed:
+        EventStreamSubject<(Envelope, PositionInSeconds, PositionInSeconds)>,
+    pub envelope_active_changed: EventStreamSubject<(Envelope, bool)>,
+    pub envelope_visibility_changed: EventStreamSubject<(Envelope, bool)>,
+    pub envelope_arm_changed: EventStreamSubject<(Envelope, bool)>,
+    /// All change events as records, see [`ControlSurfaceRx::change_event_records()`].
+    #[cfg(feature = "serde")]
+    pub change_event_records: EventStreamSubject<ChangeEventRecord>,
+    /// Created on demand for each track with subscribers.
+    track_envelope_changed: RefCell<HashMap<Track, ReactiveEvent<Envelope>>>,
 }
 
 impl fmt::Debug for ControlSurfaceRx {
@@ -253,6 +495,26 @@ impl ControlSurfaceRx {
             repeat_state_changed: default(),
             project_closed: default(),
             bookmarks_changed: default(),
+            item_added: default(),
+            item_removed: default(),
+            item_moved: default(),
+            item_selection_changed: default(),
+            item_mute_changed: default(),
+            item_active_take_changed: default(),
+            item_take_source_changed: default(),
+            marker_added: default(),
+            marker_removed: default(),
+            marker_changed: default(),
+            tempo_map_changed: default(),
+            envelope_added: default(),
+            envelope_removed: default(),
+            envelope_points_changed: default(),
+            envelope_active_changed: default(),
+            envelope_visibility_changed: default(),
+            envelope_arm_changed: default(),
+            #[cfg(feature = "serde")]
+            change_event_records: default(),
+            track_envelope_changed: Default::default(),
         }
     }
 
@@ -268,6 +530,99 @@ impl ControlSurfaceRx {
         self.bookmarks_changed.borrow().clone()
     }
 
+    pub fn item_added(&self) -> ReactiveEvent<Item> {
+        self.item_added.borrow().clone()
+    }
+
+    // Delivers an item which is not valid anymore (just for identification)
+    pub fn item_removed(&self) -> ReactiveEvent<Item> {
+        self.item_removed.borrow().clone()
+    }
+
+    /// Fires when position, length or track of an item has changed.
+    pub fn item_moved(&self) -> ReactiveEvent<Item> {
+        self.item_moved.borrow().clone()
+    }
+
+    /// New
+    pub fn item_selection_changed(&self) -> ReactiveEvent<(Item, bool)> {
+        self.item_selection_changed.borrow().clone()
+    }
+
+    /// New
+    pub fn item_mute_changed(&self) -> ReactiveEvent<(Item, bool)> {
+        self.item_mute_changed.borrow().clone()
+    }
+
+    /// New
+    pub fn item_active_take_changed(&self) -> ReactiveEvent<(Item, Option<Take>)> {
+        self.item_active_take_changed.borrow().clone()
+    }
+
+    pub fn item_take_source_changed(&self) -> ReactiveEvent<Take> {
+        self.item_take_source_changed.borrow().clone()
+    }
+
+    /// Fires for markers and regions.
+    pub fn marker_added(&self) -> ReactiveEvent<MarkerInfo> {
+        self.marker_added.borrow().clone()
+    }
+
+    pub fn marker_removed(&self) -> ReactiveEvent<MarkerInfo> {
+        self.marker_removed.borrow().clone()
+    }
+
+    /// Delivers old and new marker or region.
+    pub fn marker_changed(&self) -> ReactiveEvent<(MarkerInfo, MarkerInfo)> {
+        self.marker_changed.borrow().clone()
+    }
+
+    /// Delivers the first position affected by the tempo map change.
+    pub fn tempo_map_changed(&self) -> ReactiveEvent<PositionInSeconds> {
+        self.tempo_map_changed.borrow().clone()
+    }
+
+    pub fn envelope_added(&self) -> ReactiveEvent<Envelope> {
+        self.envelope_added.borrow().clone()
+    }
+
+    // Delivers an envelope which is not valid anymore (just for identification)
+    pub fn envelope_removed(&self) -> ReactiveEvent<Envelope> {
+        self.envelope_removed.borrow().clone()
+    }
+
+    /// Delivers the envelope and the time range which contains all changed points.
+    pub fn envelope_points_changed(
+        &self,
+    ) -> ReactiveEvent<(Envelope, PositionInSeconds, PositionInSeconds)> {
+        self.envelope_points_changed.borrow().clone()
+    }
+
+    /// New
+    pub fn envelope_active_changed(&self) -> ReactiveEvent<(Envelope, bool)> {
+        self.envelope_active_changed.borrow().clone()
+    }
+
+    /// New
+    pub fn envelope_visibility_changed(&self) -> ReactiveEvent<(Envelope, bool)> {
+        self.envelope_visibility_changed.borrow().clone()
+    }
+
+    /// New
+    pub fn envelope_arm_changed(&self) -> ReactiveEvent<(Envelope, bool)> {
+        self.envelope_arm_changed.borrow().clone()
+    }
+
+    /// Fires whenever one of the envelopes of the given track (see `Track::envelopes()`) has been
+    /// added, removed or changed in any way.
+    pub fn track_envelope_changed(&self, track: &Track) -> ReactiveEvent<Envelope> {
+        self.track_envelope_changed
+            .borrow_mut()
+            .entry(track.clone())
+            .or_insert_with(LocalSubject::new)
+            .clone()
+    }
+
     pub fn fx_opened(&self) -> ReactiveEvent<Fx> {
         self.fx_opened.borrow().clone()
     }
@@ -433,4 +788,51 @@ impl ControlSurfaceRx {
     pub fn main_thread_idle(&self) -> ReactiveEvent<()> {
         self.main_thread_idle.borrow().clone()
     }
+
+    /// Fires for live change events as well as for records replayed via
+    /// [`ControlSurfaceRxMiddleware::replay()`].
+    ///
+    /// Consumers written against this event can be tested with recorded sessions, without REAPER.
+    #[cfg(feature = "serde")]
+    pub fn change_event_records(&self) -> ReactiveEvent<ChangeEventRecord> {
+        self.change_event_records.borrow().clone()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use reaper_high::ProjectRecord;
+    use std::rc::Rc;
+
+    #[test]
+    fn replay_into_change_event_records() {
+        let session = concat!(
+            r#"{"type":"FxFocused","fx":null}"#,
+            "\n",
+            r#"{"type":"MasterTempoChanged","project":{"index":0},"touched":true,"new_value":90.0}"#,
+            "\n",
+        );
+        let rx = ControlSurfaceRx::new();
+        let received = Rc::new(RefCell::new(vec![]));
+        rx.change_event_records().subscribe({
+            let received = received.clone();
+            move |record| received.borrow_mut().push(record)
+        });
+        let middleware = ControlSurfaceRxMiddleware::new(rx);
+        middleware
+            .replay(ChangeEventReplayer::new(session.as_bytes()))
+            .unwrap();
+        assert_eq!(
+            *received.borrow(),
+            vec![
+                ChangeEventRecord::FxFocused { fx: None },
+                ChangeEventRecord::MasterTempoChanged {
+                    project: ProjectRecord { index: Some(0) },
+                    touched: true,
+                    new_value: 90.0,
+                },
+            ]
+        );
+    }
 }
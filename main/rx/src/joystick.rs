@@ -0,0 +1,97 @@
+use crate::ReactiveEvent;
+use reaper_high::{Guid, Joystick, JoystickChange, JoystickState};
+use rxrust::prelude::*;
+
+/// Polls opened joysticks and emits their changes.
+///
+/// [`run()`](Self::run) should be called on every main loop cycle, e.g. from
+/// `ControlSurface::run()`.
+#[derive(Default)]
+pub struct JoystickRxMiddleware {
+    joysticks: Vec<PolledJoystick>,
+    rx: JoystickRx,
+}
+
+struct PolledJoystick {
+    joystick: Joystick,
+    last_state: JoystickState,
+}
+
+#[derive(Clone, Default)]
+pub struct JoystickRx {
+    joystick_changed: LocalSubject<'static, JoystickEvent, ()>,
+}
+
+impl JoystickRxMiddleware {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn rx(&self) -> &JoystickRx {
+        &self.rx
+    }
+
+    /// Starts polling the given joystick.
+    ///
+    /// Its initial state doesn't cause any events.
+    pub fn add_joystick(&mut self, joystick: Joystick) {
+        let last_state = if joystick.update().is_ok() {
+            joystick.state()
+        } else {
+            JoystickState::default()
+        };
+        self.joysticks.push(PolledJoystick {
+            joystick,
+            last_state,
+        });
+    }
+
+    /// Stops polling the joystick with the given GUID and returns it.
+    pub fn remove_joystick(&mut self, guid: &Guid) -> Option<Joystick> {
+        let index = self
+            .joysticks
+            .iter()
+            .position(|j| &j.joystick.descriptor().guid == guid)?;
+        Some(self.joysticks.remove(index).joystick)
+    }
+
+    /// Polls all joysticks and emits their changes.
+    ///
+    /// Polls even if there are no subscribers, so that new subscribers don't get changes which
+    /// happened before they subscribed.
+    pub fn run(&mut self) {
+        let subject = &mut self.rx.joystick_changed;
+        let has_subscribers = subject.subscribed_size() > 0;
+        for j in &mut self.joysticks {
+            if j.joystick.update().is_err() {
+                continue;
+            }
+            let state = j.joystick.state();
+            if !has_subscribers {
+                j.last_state = state;
+                continue;
+            }
+            for change in j.last_state.changes_to(&state) {
+                let event = JoystickEvent {
+                    guid: j.joystick.descriptor().guid,
+                    change,
+                };
+                subject.next(event);
+            }
+            j.last_state = state;
+        }
+    }
+}
+
+impl JoystickRx {
+    pub fn joystick_changed(&self) -> ReactiveEvent<JoystickEvent> {
+        self.joystick_changed.clone()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct JoystickEvent {
+    /// GUID of the joystick which changed.
+    pub guid: Guid,
+    pub change: JoystickChange,
+}
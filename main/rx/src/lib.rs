@@ -7,8 +7,16 @@ pub use action::*;
 mod midi;
 pub use midi::*;
 
+mod joystick;
+pub use joystick::*;
+
 mod main_thread;
 pub use main_thread::*;
 
 mod types;
 pub use types::*;
+
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "futures")]
+pub use stream::*;
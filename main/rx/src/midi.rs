@@ -1,4 +1,6 @@
 use crate::ReactiveEvent;
+#[cfg(feature = "futures")]
+use crate::{real_time_event_stream, EventStream, LagPolicy, RealTimeEventPump};
 use helgoboss_midi::{RawShortMessage, ShortMessage, ShortMessageType};
 use reaper_medium::{MidiInputDeviceId, OnAudioBufferArgs, RealTimeAudioThreadScope};
 use rxrust::prelude::*;
@@ -52,6 +54,28 @@ impl MidiRx {
     pub fn midi_message_received(&self) -> ReactiveEvent<MidiEvent<RawShortMessage>> {
         self.midi_message_received.clone()
     }
+
+    /// Returns a stream of all received MIDI messages, to be consumed in the main thread.
+    ///
+    /// The messages are forwarded real-time-safely (see [`real_time_event_stream()`]). Call
+    /// [`RealTimeEventPump::run()`] in each main loop cycle to move them into the stream. The
+    /// subscription ends with the next message after the pump or the stream has been dropped.
+    #[cfg(feature = "futures")]
+    pub fn midi_message_received_stream(
+        &self,
+        capacity: usize,
+        lag_policy: LagPolicy,
+    ) -> (
+        RealTimeEventPump<MidiEvent<RawShortMessage>>,
+        EventStream<MidiEvent<RawShortMessage>>,
+    ) {
+        let (sender, pump, stream) = real_time_event_stream(capacity, lag_policy);
+        let connection = sender.clone();
+        self.midi_message_received()
+            .take_while(move |_| connection.is_connected())
+            .subscribe(move |evt| sender.send(evt));
+        (pump, stream)
+    }
 }
 
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
@@ -64,4 +88,12 @@ impl<M> MidiEvent<M> {
     pub fn new(frame_offset: u32, msg: M) -> MidiEvent<M> {
         MidiEvent { frame_offset, msg }
     }
+
+    pub fn frame_offset(&self) -> u32 {
+        self.frame_offset
+    }
+
+    pub fn msg(&self) -> &M {
+        &self.msg
+    }
 }
@@ -0,0 +1,387 @@
+use crate::ReactiveEvent;
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use futures::Stream;
+use rxrust::prelude::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
+/// Offers reactive events as [`futures::Stream`]s.
+///
+/// Works with all events of [`ControlSurfaceRx`](crate::ControlSurfaceRx) and
+/// [`ActionRx`](crate::ActionRx), which fire in the main thread. Don't use it with
+/// [`MidiRx`](crate::MidiRx): Its events fire in the real-time audio thread, but the stream
+/// buffer is neither thread-safe nor real-time-safe (it allocates and wakes the executor). Use
+/// [`MidiRx::midi_message_received_stream()`](crate::MidiRx::midi_message_received_stream) or
+/// [`real_time_event_stream()`] instead.
+pub trait ReactiveEventExt<T> {
+    /// Subscribes to this event and returns a stream of its values.
+    ///
+    /// Values are buffered until the stream is polled. If more than `capacity` values are pending,
+    /// the given lag policy decides which ones get discarded. The stream never ends. After
+    /// dropping it, the subscription ends with the next emitted value.
+    ///
+    /// The stream is not `Send`, so it must be consumed in the main thread, e.g. with
+    /// `FutureSupport::spawn_in_main_thread_from_main_thread()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    fn into_stream(self, capacity: usize, lag_policy: LagPolicy) -> EventStream<T>;
+}
+
+impl<T: Clone + 'static> ReactiveEventExt<T> for ReactiveEvent<T> {
+    fn into_stream(self, capacity: usize, lag_policy: LagPolicy) -> EventStream<T> {
+        let buffer = Rc::new(RefCell::new(EventBuffer::new(capacity, lag_policy)));
+        let weak_buffer = Rc::downgrade(&buffer);
+        self.take_while(move |_| weak_buffer.strong_count() > 0)
+            .subscribe({
+                let weak_buffer = Rc::downgrade(&buffer);
+                move |value| {
+                    let Some(buffer) = weak_buffer.upgrade() else {
+                        return;
+                    };
+                    // Don't wake while borrowed, the executor might poll immediately
+                    let waker = {
+                        let mut buffer = buffer.borrow_mut();
+                        buffer.push(value);
+                        buffer.waker.take()
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            });
+        EventStream { buffer }
+    }
+}
+
+/// Creates a stream of values which are sent from a real-time thread.
+///
+/// The sender only pushes into a preallocated ring buffer of the given capacity. It never
+/// allocates, locks or wakes the executor. Pending values are moved into the stream by calling
+/// [`RealTimeEventPump::run()`] in the main thread, e.g. in each main loop cycle. If the ring buffer
+/// or the stream buffer is full, the lag policy decides which values get discarded.
+///
+/// # Panics
+///
+/// Panics if `capacity` is 0.
+pub fn real_time_event_stream<T>(
+    capacity: usize,
+    lag_policy: LagPolicy,
+) -> (RealTimeEventSender<T>, RealTimeEventPump<T>, EventStream<T>) {
+    let buffer = Rc::new(RefCell::new(EventBuffer::new(capacity, lag_policy)));
+    let (sender, receiver) = crossbeam_channel::bounded(capacity);
+    let skipped_count = Arc::new(AtomicU64::new(0));
+    let is_connected = Arc::new(AtomicBool::new(true));
+    let sender = RealTimeEventSender {
+        sender,
+        receiver: receiver.clone(),
+        lag_policy,
+        skipped_count: skipped_count.clone(),
+        is_connected: is_connected.clone(),
+    };
+    let pump = RealTimeEventPump {
+        receiver,
+        skipped_count,
+        is_connected,
+        buffer: Rc::downgrade(&buffer),
+    };
+    (sender, pump, EventStream { buffer })
+}
+
+/// Sends values from a real-time thread, created via [`real_time_event_stream()`].
+#[derive(Clone, Debug)]
+pub struct RealTimeEventSender<T> {
+    sender: Sender<T>,
+    /// For discarding the oldest value if the ring buffer is full.
+    receiver: Receiver<T>,
+    lag_policy: LagPolicy,
+    skipped_count: Arc<AtomicU64>,
+    is_connected: Arc<AtomicBool>,
+}
+
+impl<T> RealTimeEventSender<T> {
+    /// Pushes the given value into the ring buffer.
+    ///
+    /// Real-time-safe as long as dropping a discarded value doesn't deallocate.
+    pub fn send(&self, value: T) {
+        let Err(TrySendError::Full(value)) = self.sender.try_send(value) else {
+            return;
+        };
+        self.skipped_count.fetch_add(1, Ordering::Relaxed);
+        if self.lag_policy == LagPolicy::DropOldest {
+            let _ = self.receiver.try_recv();
+            let _ = self.sender.try_send(value);
+        }
+    }
+
+    /// Returns `false` if the pump has been dropped or has noticed that the stream has been
+    /// dropped.
+    pub fn is_connected(&self) -> bool {
+        self.is_connected.load(Ordering::Relaxed)
+    }
+}
+
+/// Moves values sent from a real-time thread into the corresponding [`EventStream`], created via
+/// [`real_time_event_stream()`].
+#[derive(Debug)]
+pub struct RealTimeEventPump<T> {
+    receiver: Receiver<T>,
+    skipped_count: Arc<AtomicU64>,
+    is_connected: Arc<AtomicBool>,
+    buffer: Weak<RefCell<EventBuffer<T>>>,
+}
+
+impl<T> RealTimeEventPump<T> {
+    /// Moves all pending values into the stream and wakes it.
+    ///
+    /// Must be called in the main thread. Returns `false` if the stream has been dropped, in which
+    /// case the pump is not needed anymore.
+    pub fn run(&self) -> bool {
+        let Some(buffer) = self.buffer.upgrade() else {
+            self.is_connected.store(false, Ordering::Relaxed);
+            return false;
+        };
+        // Don't wake while borrowed, the executor might poll immediately
+        let waker = {
+            let mut buffer = buffer.borrow_mut();
+            buffer.skipped_count += self.skipped_count.swap(0, Ordering::Relaxed);
+            for value in self.receiver.try_iter() {
+                buffer.push(value);
+            }
+            if buffer.has_pending() {
+                buffer.waker.take()
+            } else {
+                None
+            }
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        true
+    }
+}
+
+impl<T> Drop for RealTimeEventPump<T> {
+    fn drop(&mut self) {
+        self.is_connected.store(false, Ordering::Relaxed);
+    }
+}
+
+/// What to do if a value arrives while the buffer of an [`EventStream`] is full.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum LagPolicy {
+    /// Discards the oldest pending value to make room for the new one.
+    DropOldest,
+    /// Discards the new value.
+    DropNewest,
+}
+
+/// Yielded by an [`EventStream`] if values had to be discarded because the consumer didn't keep
+/// up.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Lagged {
+    pub skipped_count: u64,
+}
+
+/// A stream of reactive event values, created via [`ReactiveEventExt::into_stream()`].
+///
+/// Yields `Err(Lagged)` before the next value if values have been discarded.
+pub struct EventStream<T> {
+    buffer: Rc<RefCell<EventBuffer<T>>>,
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = Result<T, Lagged>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut buffer = self.buffer.borrow_mut();
+        match buffer.pop() {
+            Some(item) => Poll::Ready(Some(item)),
+            None => {
+                buffer.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+struct EventBuffer<T> {
+    values: VecDeque<T>,
+    capacity: usize,
+    lag_policy: LagPolicy,
+    skipped_count: u64,
+    waker: Option<Waker>,
+}
+
+impl<T> EventBuffer<T> {
+    fn new(capacity: usize, lag_policy: LagPolicy) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        Self {
+            values: VecDeque::with_capacity(capacity),
+            capacity,
+            lag_policy,
+            skipped_count: 0,
+            waker: None,
+        }
+    }
+
+    fn push(&mut self, value: T) {
+        if self.values.len() < self.capacity {
+            self.values.push_back(value);
+            return;
+        }
+        self.skipped_count += 1;
+        if self.lag_policy == LagPolicy::DropOldest {
+            self.values.pop_front();
+            self.values.push_back(value);
+        }
+    }
+
+    fn has_pending(&self) -> bool {
+        self.skipped_count > 0 || !self.values.is_empty()
+    }
+
+    fn pop(&mut self) -> Option<Result<T, Lagged>> {
+        if self.skipped_count > 0 {
+            let lagged = Lagged {
+                skipped_count: self.skipped_count,
+            };
+            self.skipped_count = 0;
+            return Some(Err(lagged));
+        }
+        self.values.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::task::{waker, ArcWake};
+    use futures::StreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct CountingWaker(AtomicUsize);
+
+    impl ArcWake for CountingWaker {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn stream_from_subject() {
+        let mut subject: ReactiveEvent<u32> = LocalSubject::new();
+        let mut stream = subject.clone().into_stream(2, LagPolicy::DropOldest);
+        let wake_count = Arc::new(CountingWaker::default());
+        let waker = waker(wake_count.clone());
+        let mut cx = Context::from_waker(&waker);
+        assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+        subject.next(0);
+        assert_eq!(wake_count.0.load(Ordering::SeqCst), 1);
+        subject.next(1);
+        subject.next(2);
+        assert_eq!(
+            stream.poll_next_unpin(&mut cx),
+            Poll::Ready(Some(Err(Lagged { skipped_count: 1 })))
+        );
+        assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(1))));
+        assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(2))));
+        assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+        drop(stream);
+        // Ends the subscription
+        subject.next(3);
+    }
+
+    #[test]
+    fn real_time_event_stream_drop_oldest() {
+        let (sender, pump, mut stream) = real_time_event_stream(2, LagPolicy::DropOldest);
+        let wake_count = Arc::new(CountingWaker::default());
+        let waker = waker(wake_count.clone());
+        let mut cx = Context::from_waker(&waker);
+        assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+        std::thread::spawn(move || {
+            for i in 0..5 {
+                sender.send(i);
+            }
+        })
+        .join()
+        .unwrap();
+        // Only the pump wakes the stream
+        assert_eq!(wake_count.0.load(Ordering::SeqCst), 0);
+        assert!(pump.run());
+        assert_eq!(wake_count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            stream.poll_next_unpin(&mut cx),
+            Poll::Ready(Some(Err(Lagged { skipped_count: 3 })))
+        );
+        assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(3))));
+        assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(4))));
+        assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+        // Nothing pending, so no wake
+        assert!(pump.run());
+        assert_eq!(wake_count.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn real_time_event_stream_drop_newest() {
+        let (sender, pump, stream) = real_time_event_stream(2, LagPolicy::DropNewest);
+        for i in 0..5 {
+            sender.send(i);
+        }
+        assert!(pump.run());
+        assert_eq!(
+            futures::executor::block_on_stream(stream)
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![Err(Lagged { skipped_count: 3 }), Ok(0), Ok(1)]
+        );
+    }
+
+    #[test]
+    fn real_time_event_stream_disconnects() {
+        let (sender, pump, stream) = real_time_event_stream::<u32>(1, LagPolicy::DropNewest);
+        assert!(pump.run());
+        assert!(sender.is_connected());
+        drop(stream);
+        assert!(!pump.run());
+        assert!(!sender.is_connected());
+    }
+
+    fn drain(buffer: &mut EventBuffer<u32>) -> Vec<Result<u32, Lagged>> {
+        std::iter::from_fn(|| buffer.pop()).collect()
+    }
+
+    #[test]
+    fn drop_oldest() {
+        let mut buffer = EventBuffer::new(2, LagPolicy::DropOldest);
+        for i in 0..5 {
+            buffer.push(i);
+        }
+        assert_eq!(
+            drain(&mut buffer),
+            vec![Err(Lagged { skipped_count: 3 }), Ok(3), Ok(4)]
+        );
+        buffer.push(5);
+        assert_eq!(drain(&mut buffer), vec![Ok(5)]);
+    }
+
+    #[test]
+    fn drop_newest() {
+        let mut buffer = EventBuffer::new(2, LagPolicy::DropNewest);
+        for i in 0..5 {
+            buffer.push(i);
+        }
+        assert_eq!(
+            drain(&mut buffer),
+            vec![Err(Lagged { skipped_count: 3 }), Ok(0), Ok(1)]
+        );
+    }
+}
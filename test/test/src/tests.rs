@@ -536,9 +536,13 @@ fn main_section_functions() -> TestStep {
         // Given
         let section = Reaper::get().main_section();
         // When
-        let actions = unsafe { section.actions() };
+        let actions: Vec<_> = section.actions().collect();
         // Then
-        assert_eq!(actions.count() as u32, section.action_count());
+        assert_eq!(actions.len() as u32, section.action_count());
+        assert!(actions
+            .iter()
+            .enumerate()
+            .all(|(i, a)| a.section == section && a.index == i as u32));
         Ok(())
     })
 }